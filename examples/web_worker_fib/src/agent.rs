@@ -1,8 +1,8 @@
 use js_sys::Uint8Array;
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::JsValue;
-use yew_agent::Codec;
 use yew_agent::prelude::*;
+use yew_agent::{Codec, InThreadCodec};
 
 /// Example to use a custom codec.
 pub struct Postcard;
@@ -25,6 +25,8 @@ impl Codec for Postcard {
     }
 }
 
+impl InThreadCodec for Postcard {}
+
 #[oneshot]
 pub async fn FibonacciTask(n: u32) -> u32 {
     fn fib(n: u32) -> u32 {
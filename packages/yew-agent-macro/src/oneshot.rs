@@ -1,14 +1,48 @@
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
-use syn::{Ident, ReturnType, Signature, Type, parse_quote};
+use syn::{GenericArgument, Ident, PathArguments, ReturnType, Signature, Type, parse_quote};
 
 use crate::agent_fn::{AgentFn, AgentFnType, AgentName};
 
 pub struct OneshotFn {}
 
+pub struct OneshotRecv {
+    input_type: Type,
+    /// The type reported to the `ProgressSink` argument, if any.
+    progress_type: Option<Type>,
+}
+
+impl OneshotFn {
+    /// Extracts `P` from an argument of type `ProgressSink<P>`.
+    fn parse_progress_type(ty: &Type) -> syn::Result<Type> {
+        let err = || {
+            syn::Error::new_spanned(
+                ty,
+                "expected the progress sink of the oneshot agent, `ProgressSink<P>`",
+            )
+        };
+
+        let Type::Path(path) = ty else {
+            return Err(err());
+        };
+        let segment = path.path.segments.last().ok_or_else(err)?;
+        if segment.ident != "ProgressSink" {
+            return Err(err());
+        }
+
+        match &segment.arguments {
+            PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+                GenericArgument::Type(ty) => Ok(ty.clone()),
+                _ => Err(err()),
+            },
+            _ => Err(err()),
+        }
+    }
+}
+
 impl AgentFnType for OneshotFn {
     type OutputType = Type;
-    type RecvType = Type;
+    type RecvType = OneshotRecv;
 
     fn attr_name() -> &'static str {
         "oneshot"
@@ -24,11 +58,19 @@ impl AgentFnType for OneshotFn {
             .next()
             .ok_or_else(|| syn::Error::new_spanned(&sig.ident, "expected 1 argument"))?;
 
-        let ty = Self::extract_fn_arg_type(arg)?;
+        let input_type = Self::extract_fn_arg_type(arg)?;
 
-        Self::assert_no_left_argument(inputs, 1)?;
+        let progress_type = match inputs.next() {
+            Some(arg) => Some(Self::parse_progress_type(&Self::extract_fn_arg_type(arg)?)?),
+            None => None,
+        };
 
-        Ok(ty)
+        Self::assert_no_left_argument(inputs, 2)?;
+
+        Ok(OneshotRecv {
+            input_type,
+            progress_type,
+        })
     }
 
     fn parse_output_type(sig: &Signature) -> syn::Result<Self::OutputType> {
@@ -54,7 +96,10 @@ pub fn oneshot_impl(name: AgentName, mut agent_fn: AgentFn<OneshotFn>) -> syn::R
     let inner_fn = agent_fn.print_inner_fn();
 
     let AgentFn {
-        recv_type: input_type,
+        recv_type: OneshotRecv {
+            input_type,
+            progress_type,
+        },
         generics,
         output_type,
         vis,
@@ -65,14 +110,57 @@ pub fn oneshot_impl(name: AgentName, mut agent_fn: AgentFn<OneshotFn>) -> syn::R
     let fn_generics = ty_generics.as_turbofish();
 
     let in_ident = Ident::new("_input", Span::mixed_site());
+    let progress_ident = Ident::new("_progress", Span::mixed_site());
 
+    let fn_args = match progress_type {
+        Some(_) => quote! { #in_ident, #progress_ident },
+        None => quote! { #in_ident },
+    };
     let fn_call = if is_async {
-        quote! { #fn_name #fn_generics (#in_ident).await }
+        quote! { #fn_name #fn_generics (#fn_args).await }
     } else {
-        quote! { #fn_name #fn_generics (#in_ident) }
+        quote! { #fn_name #fn_generics (#fn_args) }
     };
     let crate_name = quote! { yew_agent };
 
+    let create_body = quote! {
+        #inner_fn
+
+        Self {
+            inner: ::std::boxed::Box::pin(
+                async move {
+                    #fn_call
+                }
+            ),
+            _marker: ::std::marker::PhantomData,
+        }
+    };
+    // Agents without a sink are `Oneshot`s, which report `()` as their progress.
+    let create_impl = match progress_type {
+        Some(progress_type) => quote! {
+            impl #impl_generics ::#crate_name::oneshot::ReportProgress for #oneshot_name #ty_generics #where_clause {
+                type Input = #input_type;
+                type Progress = #progress_type;
+
+                fn create_with_progress(
+                    #in_ident: Self::Input,
+                    #progress_ident: ::#crate_name::oneshot::ProgressSink<Self::Progress>,
+                ) -> Self {
+                    #create_body
+                }
+            }
+        },
+        None => quote! {
+            impl #impl_generics ::#crate_name::oneshot::Oneshot for #oneshot_name #ty_generics #where_clause {
+                type Input = #input_type;
+
+                fn create(#in_ident: Self::Input) -> Self {
+                    #create_body
+                }
+            }
+        },
+    };
+
     let quoted = quote! {
         #(#struct_attrs)*
         #[allow(unused_parens)]
@@ -84,22 +172,7 @@ pub fn oneshot_impl(name: AgentName, mut agent_fn: AgentFn<OneshotFn>) -> syn::R
         // we cannot disable any lints here because it will be applied to the function body
         // as well.
         #(#oneshot_impl_attrs)*
-        impl #impl_generics ::#crate_name::oneshot::Oneshot for #oneshot_name #ty_generics #where_clause {
-            type Input = #input_type;
-
-            fn create(#in_ident: Self::Input) -> Self {
-                #inner_fn
-
-                Self {
-                    inner: ::std::boxed::Box::pin(
-                        async move {
-                            #fn_call
-                        }
-                    ),
-                    _marker: ::std::marker::PhantomData,
-                }
-            }
-        }
+        #create_impl
 
         impl #impl_generics ::std::future::Future for #oneshot_name #ty_generics #where_clause {
             type Output = #output_type;
@@ -110,18 +183,18 @@ pub fn oneshot_impl(name: AgentName, mut agent_fn: AgentFn<OneshotFn>) -> syn::R
         }
 
         impl #impl_generics ::#crate_name::Registrable for #oneshot_name #ty_generics #where_clause {
-            type Registrar = ::#crate_name::oneshot::OneshotRegistrar<Self>;
+            type Registrar = ::#crate_name::worker::WorkerRegistrar<::#crate_name::oneshot::OneshotWorker<Self>>;
 
             fn registrar() -> Self::Registrar {
-                ::#crate_name::oneshot::OneshotRegistrar::<Self>::new()
+                <::#crate_name::oneshot::OneshotWorker<Self> as ::#crate_name::Registrable>::registrar()
             }
         }
 
         impl #impl_generics ::#crate_name::Spawnable for #oneshot_name #ty_generics #where_clause {
-            type Spawner = ::#crate_name::oneshot::OneshotSpawner<Self>;
+            type Spawner = ::#crate_name::worker::WorkerSpawner<::#crate_name::oneshot::OneshotWorker<Self>>;
 
             fn spawner() -> Self::Spawner {
-                ::#crate_name::oneshot::OneshotSpawner::<Self>::new()
+                <::#crate_name::oneshot::OneshotWorker<Self> as ::#crate_name::Spawnable>::spawner()
             }
         }
     };
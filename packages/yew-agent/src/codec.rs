@@ -0,0 +1,85 @@
+use gloo_worker::{Bincode, Codec};
+use serde::{Deserialize, Serialize};
+
+/// A [`Codec`] that can pass the messages of agents running [in thread](crate::Reach::InThread).
+///
+/// The inputs and outputs of an in thread agent go through the codec of its provider, so
+/// serialization bugs show up as they would with a web worker. Codecs encode to a `JsValue`,
+/// which is only available on `wasm32`. A codec that is used on other targets, such as during
+/// server-side rendering, overrides [`round_trip`](Self::round_trip) to encode without
+/// JavaScript, like [`Bincode`] does.
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use wasm_bindgen::JsValue;
+/// use yew_agent::{Codec, InThreadCodec};
+///
+/// struct MyCodec;
+///
+/// impl Codec for MyCodec {
+///     // ...
+/// #   fn encode<I: Serialize>(_input: I) -> JsValue {
+/// #       unimplemented!()
+/// #   }
+/// #   fn decode<O: for<'de> Deserialize<'de>>(_input: JsValue) -> O {
+/// #       unimplemented!()
+/// #   }
+/// }
+///
+/// impl InThreadCodec for MyCodec {}
+/// ```
+pub trait InThreadCodec: Codec {
+    /// Encodes and decodes `value`, like a message sent to a web worker.
+    ///
+    /// Defaults to decoding the output of [`Codec::encode`].
+    fn round_trip<T>(value: T) -> T
+    where
+        T: Serialize + for<'de> Deserialize<'de>,
+    {
+        Self::decode(Self::encode(value))
+    }
+}
+
+/// Outside of `wasm32`, messages go through `bincode` directly, which is what [`Bincode`]
+/// encodes them with.
+impl InThreadCodec for Bincode {
+    #[cfg(not(target_arch = "wasm32"))]
+    fn round_trip<T>(value: T) -> T
+    where
+        T: Serialize + for<'de> Deserialize<'de>,
+    {
+        let buf = bincode::serialize(&value).expect("can't serialize a worker message");
+        bincode::deserialize(&buf).expect("can't deserialize a worker message")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    /// A message whose `draft` is not sent to the worker.
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Message {
+        text: String,
+        #[serde(skip)]
+        draft: bool,
+    }
+
+    #[test]
+    fn bincode_round_trips() {
+        let message = Message {
+            text: "hello".into(),
+            draft: true,
+        };
+
+        assert_eq!(
+            Bincode::round_trip(message),
+            Message {
+                text: "hello".into(),
+                draft: false,
+            }
+        );
+    }
+}
@@ -17,9 +17,11 @@ pub mod worker;
 #[doc(inline)]
 pub use gloo_worker::{Bincode, Codec, Registrable, Spawnable};
 
+mod codec;
 mod reach;
 pub mod scope_ext;
 
+pub use codec::InThreadCodec;
 pub use reach::Reach;
 
 mod utils;
@@ -33,7 +35,7 @@ pub mod prelude {
     //! Prelude module to be imported when working with `yew-agent`.
     //!
     //! This module re-exports the frequently used types from the crate.
-    pub use crate::oneshot::{UseOneshotRunnerHandle, oneshot, use_oneshot_runner};
+    pub use crate::oneshot::{ProgressSink, UseOneshotRunnerHandle, oneshot, use_oneshot_runner};
     pub use crate::reach::Reach;
     pub use crate::reactor::{
         ReactorEvent, ReactorScope, UseReactorBridgeHandle, UseReactorSubscriptionHandle, reactor,
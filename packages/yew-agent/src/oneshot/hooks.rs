@@ -1,54 +1,183 @@
+use futures::stream::Stream;
 use yew::prelude::*;
 
-use super::Oneshot;
+use super::ReportProgress;
 use super::provider::OneshotProviderState;
 
 /// Hook handle for [`use_oneshot_runner`]
 #[derive(Debug)]
 pub struct UseOneshotRunnerHandle<T>
 where
-    T: Oneshot + 'static,
+    T: ReportProgress + 'static,
 {
     state: OneshotProviderState<T>,
+    queue_depth: usize,
 }
 
 impl<T> UseOneshotRunnerHandle<T>
 where
-    T: Oneshot + 'static,
+    T: ReportProgress + 'static,
 {
     /// Runs an oneshot agent.
+    ///
+    /// Dropping the returned future cancels the run, and stops the worker of a pool slot that is
+    /// still busy with it.
     pub async fn run(&self, input: T::Input) -> T::Output {
-        self.state.create_bridge().run(input).await
+        self.state.run(input).await
+    }
+
+    /// Runs an oneshot agent, returning a stream of the progress it reports to its
+    /// [`ProgressSink`](super::ProgressSink) along with its output.
+    ///
+    /// The run continues while only the stream is polled, and is cancelled like with
+    /// [`run`](Self::run) when the future is dropped.
+    pub fn run_with_progress(
+        &self,
+        input: T::Input,
+    ) -> (
+        impl Stream<Item = T::Progress> + use<T>,
+        impl Future<Output = T::Output> + use<T>,
+    ) {
+        self.state.run_with_progress(input)
+    }
+
+    /// Returns the number of runs waiting for an idle worker.
+    ///
+    /// This is always `0` unless the provider has a `pool_size`. The component re-renders when it
+    /// changes.
+    pub fn queue_depth(&self) -> usize {
+        self.queue_depth
     }
 }
 
 impl<T> Clone for UseOneshotRunnerHandle<T>
 where
-    T: Oneshot + 'static,
+    T: ReportProgress + 'static,
 {
     fn clone(&self) -> Self {
         Self {
             state: self.state.clone(),
+            queue_depth: self.queue_depth,
         }
     }
 }
 
 impl<T> PartialEq for UseOneshotRunnerHandle<T>
 where
-    T: Oneshot,
+    T: ReportProgress,
 {
     fn eq(&self, rhs: &Self) -> bool {
-        self.state == rhs.state
+        self.state == rhs.state && self.queue_depth == rhs.queue_depth
     }
 }
 
 /// A hook to create a runner to an oneshot agent.
+///
+/// During server-side rendering the agent runs in thread, so its output can be prepared with
+/// [`use_prepared_state`](yew::prelude::use_prepared_state) and sent to the client along with the
+/// page:
+///
+/// ```
+/// # use yew::prelude::*;
+/// # use yew_agent::oneshot::{oneshot, use_oneshot_runner};
+/// #[oneshot]
+/// async fn Square(n: u32) -> u32 {
+///     n * n
+/// }
+///
+/// #[component]
+/// fn Squared() -> HtmlResult {
+///     let runner = use_oneshot_runner::<Square>();
+///     let square = use_prepared_state!(12, async move |n| -> u32 { runner.run(*n).await })?;
+///
+///     Ok(html! { { square.unwrap_or_default() } })
+/// }
+/// ```
 #[hook]
 pub fn use_oneshot_runner<T>() -> UseOneshotRunnerHandle<T>
 where
-    T: Oneshot + 'static,
+    T: ReportProgress + 'static,
 {
     let state = use_context::<OneshotProviderState<T>>().expect("failed to find worker context");
+    let queue_depth = use_state_eq(|| state.queue_depth());
+
+    {
+        let queue_depth = queue_depth.setter();
+        use_effect_with(state.clone(), move |state| {
+            queue_depth.set(state.queue_depth());
+            let subscription =
+                state.subscribe_queue_depth(Callback::from(move |m| queue_depth.set(m)));
+
+            move || drop(subscription)
+        });
+    }
+
+    UseOneshotRunnerHandle {
+        state,
+        queue_depth: *queue_depth,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    use tokio::task::LocalSet;
 
-    UseOneshotRunnerHandle { state }
+    use super::*;
+    use crate::oneshot::{Oneshot, OneshotProvider};
+
+    struct Square(u32);
+
+    impl Future for Square {
+        type Output = u32;
+
+        fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<u32> {
+            Poll::Ready(self.0 * self.0)
+        }
+    }
+
+    impl Oneshot for Square {
+        type Input = u32;
+
+        fn create(input: u32) -> Self {
+            Self(input)
+        }
+    }
+
+    type Agent = Square;
+
+    #[component]
+    fn Squared() -> HtmlResult {
+        let runner = use_oneshot_runner::<Agent>();
+        let square = use_prepared_state!(12, async move |n| -> u32 { runner.run(*n).await })?;
+
+        Ok(html! { { square.unwrap_or_default() } })
+    }
+
+    #[component]
+    fn App() -> Html {
+        html! {
+            <OneshotProvider<Agent> path="/worker.js">
+                <Suspense fallback={html! { "loading" }}>
+                    <Squared />
+                </Suspense>
+            </OneshotProvider<Agent>>
+        }
+    }
+
+    #[tokio::test]
+    async fn outputs_are_prepared_on_the_server() {
+        let s = LocalSet::new()
+            .run_until(
+                yew::LocalServerRenderer::<App>::new()
+                    .hydratable(false)
+                    .render(),
+            )
+            .await;
+
+        // The output is rendered, followed by the state sent to the client.
+        assert!(s.starts_with("144<script"), "{s}");
+    }
 }
@@ -1,12 +1,26 @@
 //! This module provides task agent implementation.
 
 mod hooks;
+mod pool;
+mod progress;
 mod provider;
+mod worker;
 
 #[doc(inline)]
-pub use gloo_worker::oneshot::{Oneshot, OneshotBridge, OneshotRegistrar, OneshotSpawner};
+pub use gloo_worker::oneshot::{Oneshot, OneshotBridge, OneshotSpawner};
 pub use hooks::{UseOneshotRunnerHandle, use_oneshot_runner};
-pub use provider::OneshotProvider;
+pub use pool::QueueStrategy;
+pub use progress::{ProgressSink, ReportProgress};
 pub(crate) use provider::OneshotProviderState;
+pub use provider::{OneshotProvider, OneshotProviderProps};
+pub use worker::{OneshotMessage, OneshotWorker};
+
+/// The registrar of the worker that `gloo-worker` runs oneshot agents in.
+#[deprecated(
+    since = "0.6.0",
+    note = "providers cannot reach the workers it registers, use `OneshotWorker::<T>::registrar()` \
+            instead"
+)]
+pub type OneshotRegistrar<T, C = crate::Bincode> = gloo_worker::oneshot::OneshotRegistrar<T, C>;
 /// A procedural macro to create oneshot agents.
 pub use yew_agent_macro::oneshot;
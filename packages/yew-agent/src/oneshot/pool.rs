@@ -0,0 +1,300 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fmt;
+use std::rc::Rc;
+
+use yew::Callback;
+use yew::platform::pinned::oneshot;
+
+use crate::utils::get_next_id;
+
+/// The order in which the runs waiting for an idle worker of an
+/// [`OneshotProvider`](super::OneshotProvider) are dispatched.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy, Default)]
+pub enum QueueStrategy {
+    /// The run that has waited the longest is dispatched first.
+    #[default]
+    Fifo,
+    /// The latest run is dispatched first, e.g. when only the result of the latest input matters.
+    Lifo,
+}
+
+/// A pool of slots limiting the number of concurrent runs.
+///
+/// Each slot has an index, so that a run can reuse the worker of its slot.
+pub(crate) struct Pool {
+    size: usize,
+    strategy: QueueStrategy,
+    state: RefCell<PoolState>,
+}
+
+#[derive(Default)]
+struct PoolState {
+    /// The number of slots handed out so far, at most `size`.
+    created: usize,
+    idle: Vec<usize>,
+    queue: VecDeque<(usize, oneshot::Sender<()>)>,
+    /// Slots handed to runs of the queue that have not resumed yet.
+    handed: Vec<(usize, usize)>,
+    listeners: Vec<(usize, Callback<usize>)>,
+}
+
+impl fmt::Debug for Pool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Pool")
+            .field("size", &self.size)
+            .field("strategy", &self.strategy)
+            .field("queue_depth", &self.queue_depth())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Pool {
+    pub fn new(size: usize, strategy: QueueStrategy) -> Self {
+        Self {
+            size: size.max(1),
+            strategy,
+            state: RefCell::default(),
+        }
+    }
+
+    /// Returns the number of runs waiting for a slot.
+    pub fn queue_depth(&self) -> usize {
+        self.state.borrow().queue.len()
+    }
+
+    /// Calls `listener` with the queue depth whenever it changes, until the returned subscription
+    /// is dropped.
+    pub fn subscribe(self: &Rc<Self>, listener: Callback<usize>) -> PoolSubscription {
+        let id = get_next_id();
+        self.state.borrow_mut().listeners.push((id, listener));
+
+        PoolSubscription {
+            pool: self.clone(),
+            id,
+        }
+    }
+
+    /// Waits for an idle slot.
+    pub async fn acquire(self: &Rc<Self>) -> PoolSlot {
+        let id = get_next_id();
+        let rx = {
+            let mut state = self.state.borrow_mut();
+            if let Some(index) = state.idle.pop() {
+                return self.slot(index);
+            }
+            if state.created < self.size {
+                state.created += 1;
+                return self.slot(state.created - 1);
+            }
+
+            let (tx, rx) = oneshot::channel();
+            state.queue.push_back((id, tx));
+            rx
+        };
+        self.notify();
+
+        // Gives the slot back if the run is cancelled while it waits.
+        let _waiting = Waiting { pool: self, id };
+        let _ = rx.await;
+
+        let index = self
+            .take_handed(id)
+            .expect("a slot is handed before the run is woken up");
+        self.slot(index)
+    }
+
+    fn take_handed(&self, id: usize) -> Option<usize> {
+        let mut state = self.state.borrow_mut();
+        let position = state.handed.iter().position(|(m, _)| *m == id)?;
+        Some(state.handed.swap_remove(position).1)
+    }
+
+    fn slot(self: &Rc<Self>, index: usize) -> PoolSlot {
+        PoolSlot {
+            pool: self.clone(),
+            index,
+        }
+    }
+
+    fn release(&self, index: usize) {
+        let next = {
+            let mut state = self.state.borrow_mut();
+            let next = match self.strategy {
+                QueueStrategy::Fifo => state.queue.pop_front(),
+                QueueStrategy::Lifo => state.queue.pop_back(),
+            };
+            match next {
+                Some((id, tx)) => {
+                    state.handed.push((id, index));
+                    tx
+                }
+                None => {
+                    state.idle.push(index);
+                    return;
+                }
+            }
+        };
+
+        let _ = next.send(());
+        self.notify();
+    }
+
+    fn notify(&self) {
+        let (depth, listeners) = {
+            let state = self.state.borrow();
+            let listeners: Vec<_> = state.listeners.iter().map(|(_, m)| m.clone()).collect();
+            (state.queue.len(), listeners)
+        };
+
+        for listener in listeners {
+            listener.emit(depth);
+        }
+    }
+}
+
+/// A slot of a [`Pool`], released when dropped.
+pub(crate) struct PoolSlot {
+    pool: Rc<Pool>,
+    index: usize,
+}
+
+impl PoolSlot {
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl Drop for PoolSlot {
+    fn drop(&mut self) {
+        self.pool.release(self.index);
+    }
+}
+
+/// A subscription to the queue depth of a [`Pool`], removed when dropped.
+pub(crate) struct PoolSubscription {
+    pool: Rc<Pool>,
+    id: usize,
+}
+
+impl Drop for PoolSubscription {
+    fn drop(&mut self) {
+        self.pool
+            .state
+            .borrow_mut()
+            .listeners
+            .retain(|(id, _)| *id != self.id);
+    }
+}
+
+/// A run waiting in the queue of a [`Pool`].
+struct Waiting<'a> {
+    pool: &'a Pool,
+    id: usize,
+}
+
+impl Drop for Waiting<'_> {
+    fn drop(&mut self) {
+        if let Some(index) = self.pool.take_handed(self.id) {
+            self.pool.release(index);
+            return;
+        }
+
+        let removed = {
+            let mut state = self.pool.state.borrow_mut();
+            let len = state.queue.len();
+            state.queue.retain(|(id, _)| *id != self.id);
+            state.queue.len() != len
+        };
+        if removed {
+            self.pool.notify();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::pin::Pin;
+
+    use futures::FutureExt;
+
+    use super::*;
+
+    type Acquire = Pin<Box<dyn Future<Output = PoolSlot>>>;
+
+    /// Starts waiting for a slot, which must not be idle.
+    fn queue(pool: &Rc<Pool>) -> Acquire {
+        let pool = pool.clone();
+        let mut acquire: Acquire = Box::pin(async move { pool.acquire().await });
+        assert!((&mut acquire).now_or_never().is_none());
+        acquire
+    }
+
+    fn dispatch_order(strategy: QueueStrategy) -> Vec<usize> {
+        let pool = Rc::new(Pool::new(1, strategy));
+        let slot = pool.acquire().now_or_never().unwrap();
+        let mut runs: Vec<_> = (0..3).map(|_| Some(queue(&pool))).collect();
+
+        drop(slot);
+        let mut order = Vec::new();
+        for _ in 0..runs.len() {
+            for (i, run) in runs.iter_mut().enumerate() {
+                // The slot of a resumed run is released right away, handing it to the next run.
+                if run.as_mut().is_some_and(|m| m.now_or_never().is_some()) {
+                    *run = None;
+                    order.push(i);
+                }
+            }
+        }
+        order
+    }
+
+    #[test]
+    fn fifo_dispatches_the_oldest_run_first() {
+        assert_eq!(dispatch_order(QueueStrategy::Fifo), [0, 1, 2]);
+    }
+
+    #[test]
+    fn lifo_dispatches_the_latest_run_first() {
+        assert_eq!(dispatch_order(QueueStrategy::Lifo), [2, 1, 0]);
+    }
+
+    #[test]
+    fn slots_handed_to_dropped_runs_are_released() {
+        let pool = Rc::new(Pool::new(1, QueueStrategy::Fifo));
+        let slot = pool.acquire().now_or_never().unwrap();
+        let run = queue(&pool);
+
+        // The slot is handed to the run, which is dropped before it resumes.
+        drop(slot);
+        drop(run);
+
+        assert_eq!(pool.acquire().now_or_never().unwrap().index(), 0);
+    }
+
+    #[test]
+    fn queue_depth_is_notified() {
+        let pool = Rc::new(Pool::new(1, QueueStrategy::Fifo));
+        let depths = Rc::new(RefCell::new(Vec::new()));
+        let subscription = {
+            let depths = depths.clone();
+            pool.subscribe(Callback::from(move |m| depths.borrow_mut().push(m)))
+        };
+
+        let slot = pool.acquire().now_or_never().unwrap();
+        let first = queue(&pool);
+        let second = queue(&pool);
+        assert_eq!(pool.queue_depth(), 2);
+
+        // A cancelled run leaves the queue.
+        drop(second);
+        drop(slot);
+        assert_eq!(pool.queue_depth(), 0);
+        assert_eq!(*depths.borrow(), [1, 2, 1, 0]);
+
+        drop(subscription);
+        let _second = queue(&pool);
+        assert_eq!(*depths.borrow(), [1, 2, 1, 0]);
+        drop(first);
+    }
+}
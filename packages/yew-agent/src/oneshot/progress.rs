@@ -0,0 +1,130 @@
+use std::any::type_name;
+use std::fmt;
+use std::rc::Rc;
+
+use super::Oneshot;
+
+/// A sink to report the progress of an oneshot agent.
+///
+/// An oneshot agent created with the [`oneshot`](super::oneshot) macro receives it as its second
+/// argument:
+///
+/// ```
+/// # use yew_agent::oneshot::{oneshot, ProgressSink};
+/// #[oneshot]
+/// async fn Thumbnails(images: Vec<String>, progress: ProgressSink<usize>) -> usize {
+///     for (done, _image) in images.iter().enumerate() {
+///         // Resizes the image...
+///         progress.report(done + 1);
+///     }
+///
+///     images.len()
+/// }
+/// ```
+pub struct ProgressSink<P> {
+    report: Rc<dyn Fn(P)>,
+}
+
+impl<P> ProgressSink<P> {
+    /// Creates a sink that calls `report` with the progress.
+    pub fn new<F>(report: F) -> Self
+    where
+        F: 'static + Fn(P),
+    {
+        Self {
+            report: Rc::new(report),
+        }
+    }
+
+    /// Creates a sink that discards the progress.
+    pub fn discard() -> Self {
+        Self::new(|_| {})
+    }
+
+    /// Reports the progress to the runner of the agent.
+    pub fn report(&self, progress: P) {
+        (self.report)(progress)
+    }
+}
+
+impl<P> Clone for ProgressSink<P> {
+    fn clone(&self) -> Self {
+        Self {
+            report: self.report.clone(),
+        }
+    }
+}
+
+impl<P> fmt::Debug for ProgressSink<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish_non_exhaustive()
+    }
+}
+
+/// An oneshot agent that can report its progress while it runs.
+///
+/// This is implemented by the [`oneshot`](super::oneshot) macro for functions that accept a
+/// [`ProgressSink`]. Every [`Oneshot`] implements it with `()` as its progress, so agents
+/// written by hand or without a sink are used as is:
+///
+/// ```
+/// # use std::pin::Pin;
+/// # use std::task::{Context, Poll};
+/// # use yew::prelude::*;
+/// # use yew_agent::Registrable;
+/// # use yew_agent::oneshot::{Oneshot, OneshotProvider, OneshotWorker};
+/// struct Double(u32);
+///
+/// impl Future for Double {
+///     type Output = u32;
+///
+///     fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<u32> {
+///         Poll::Ready(self.0 * 2)
+///     }
+/// }
+///
+/// impl Oneshot for Double {
+///     type Input = u32;
+///
+///     fn create(input: u32) -> Self {
+///         Self(input)
+///     }
+/// }
+///
+/// // In the worker.
+/// fn register() {
+///     OneshotWorker::<Double>::registrar().register();
+/// }
+///
+/// // In the application.
+/// #[component]
+/// fn App() -> Html {
+///     html! {
+///         <OneshotProvider<Double> path="/worker.js">
+///             // Children run the agent with `use_oneshot_runner::<Double>()`.
+///         </OneshotProvider<Double>>
+///     }
+/// }
+/// ```
+pub trait ReportProgress: Future {
+    /// The input message.
+    type Input;
+
+    /// The progress reported by the agent.
+    type Progress;
+
+    /// Creates an oneshot agent that reports its progress to `progress`.
+    fn create_with_progress(input: Self::Input, progress: ProgressSink<Self::Progress>) -> Self;
+}
+
+impl<T> ReportProgress for T
+where
+    T: Oneshot,
+{
+    type Input = T::Input;
+    type Progress = ();
+
+    fn create_with_progress(input: Self::Input, _progress: ProgressSink<()>) -> Self {
+        T::create(input)
+    }
+}
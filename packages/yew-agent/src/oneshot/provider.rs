@@ -1,30 +1,97 @@
 use core::fmt;
 use std::any::type_name;
 use std::cell::RefCell;
+use std::pin::pin;
 use std::rc::Rc;
 
-use gloo_worker::{Bincode, Codec};
+use futures::future::{Either, FutureExt, LocalBoxFuture, select};
+use futures::stream::StreamExt;
+use gloo_worker::{Bincode, Spawnable};
 use serde::{Deserialize, Serialize};
+use yew::platform::pinned::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use yew::platform::pinned::oneshot;
+use yew::platform::spawn_local;
 use yew::prelude::*;
 
-use super::{Oneshot, OneshotBridge, OneshotSpawner};
-use crate::Reach;
+use super::pool::{Pool, PoolSubscription, QueueStrategy};
+use super::{OneshotMessage, OneshotWorker, ProgressSink, ReportProgress};
 use crate::utils::get_next_id;
-use crate::worker::WorkerProviderProps;
+use crate::worker::WorkerBridge;
+use crate::{InThreadCodec, Reach};
+
+/// Properties for [OneshotProvider].
+#[derive(Debug, Properties, PartialEq, Clone)]
+pub struct OneshotProviderProps {
+    /// The path to an agent.
+    pub path: AttrValue,
+
+    /// The reachability of an agent.
+    ///
+    /// Oneshot agents cannot be shared, [`Shared`](Reach::Shared) falls back to
+    /// [`Private`](Reach::Private).
+    ///
+    /// Default: [`Public`](Reach::Public).
+    #[prop_or(Reach::Public)]
+    pub reach: Reach,
+
+    /// Whether the agent should be created
+    /// with type `Module`.
+    #[prop_or(false)]
+    pub module: bool,
+
+    /// Lazily spawn the agent.
+    ///
+    /// The agent will be spawned when the first time a hook requests a bridge.
+    ///
+    /// Does not affect private agents.
+    ///
+    /// Default: `true`
+    #[prop_or(true)]
+    pub lazy: bool,
+
+    /// The maximum number of concurrent runs.
+    ///
+    /// A public agent spawns up to this many workers, each running one input at a time, and
+    /// runs beyond the limit wait in a queue until a worker is idle. Private agents spawn a
+    /// worker per run as usual, but at most this many at once.
+    ///
+    /// Default: `None`, runs are not limited and public agents share a single worker.
+    #[prop_or_default]
+    pub pool_size: Option<usize>,
+
+    /// The order in which queued runs are dispatched when `pool_size` is set.
+    ///
+    /// Default: [`Fifo`](QueueStrategy::Fifo).
+    #[prop_or_default]
+    pub queue: QueueStrategy,
+
+    /// Children of the provider.
+    #[prop_or_default]
+    pub children: Html,
+}
+
+type OneshotWorkerBridge<T> = WorkerBridge<OneshotWorker<T>>;
+
+type RunInThreadFn<T> = dyn Fn(
+    <T as ReportProgress>::Input,
+    UnboundedSender<<T as ReportProgress>::Progress>,
+) -> LocalBoxFuture<'static, <T as Future>::Output>;
 
 pub(crate) struct OneshotProviderState<T>
 where
-    T: Oneshot + 'static,
+    T: ReportProgress + 'static,
 {
     id: usize,
-    spawn_bridge_fn: Rc<dyn Fn() -> OneshotBridge<T>>,
+    spawn_bridge_fn: Rc<dyn Fn() -> OneshotWorkerBridge<T>>,
+    run_in_thread_fn: Rc<RunInThreadFn<T>>,
     reach: Reach,
-    held_bridge: Rc<RefCell<Option<OneshotBridge<T>>>>,
+    pool: Option<Rc<Pool>>,
+    held_bridges: Rc<RefCell<Vec<Option<OneshotWorkerBridge<T>>>>>,
 }
 
 impl<T> fmt::Debug for OneshotProviderState<T>
 where
-    T: Oneshot,
+    T: ReportProgress,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct(type_name::<Self>()).finish_non_exhaustive()
@@ -33,50 +100,194 @@ where
 
 impl<T> OneshotProviderState<T>
 where
-    T: Oneshot,
+    T: ReportProgress + 'static,
 {
-    fn get_held_bridge(&self) -> OneshotBridge<T> {
-        let mut held_bridge = self.held_bridge.borrow_mut();
+    /// Returns the worker of a pool slot, spawning it if needed.
+    fn get_held_bridge(&self, index: usize) -> OneshotWorkerBridge<T> {
+        let mut held_bridges = self.held_bridges.borrow_mut();
 
-        match held_bridge.as_mut() {
-            Some(m) => m.fork(),
-            None => {
-                let bridge = (self.spawn_bridge_fn)();
-                *held_bridge = Some(bridge.fork());
-                bridge
-            }
+        if held_bridges.len() <= index {
+            held_bridges.resize_with(index + 1, || None);
         }
+        held_bridges[index]
+            .get_or_insert_with(|| (self.spawn_bridge_fn)())
+            .fork(None::<fn(_)>)
+    }
+
+    /// Runs the agent.
+    pub async fn run(&self, input: T::Input) -> T::Output {
+        let (_progress, output) = self.run_with_progress(input);
+        output.await
     }
 
-    /// Creates a bridge, uses "fork" for public agents.
-    pub fn create_bridge(&self) -> OneshotBridge<T> {
+    /// Runs the agent, reporting its progress to the returned stream.
+    ///
+    /// The run is cancelled when the returned future is dropped.
+    pub fn run_with_progress(
+        &self,
+        input: T::Input,
+    ) -> (
+        UnboundedReceiver<T::Progress>,
+        impl Future<Output = T::Output> + use<T>,
+    ) {
+        let (progress_tx, progress_rx) = mpsc::unbounded();
+        let (output_tx, output_rx) = oneshot::channel();
+        // Dropping the sender cancels the run.
+        let (cancel_tx, cancel_rx) = oneshot::channel::<()>();
+
+        let state = self.clone();
+        spawn_local(async move {
+            let run = pin!(state.run_inner(input, progress_tx));
+            if let Either::Left((output, _)) = select(run, cancel_rx).await {
+                let _ = output_tx.send(output);
+            }
+        });
+
+        let output = async move {
+            let _cancel_tx = cancel_tx;
+            output_rx
+                .await
+                .expect("failed to receive result from agent")
+        };
+        (progress_rx, output)
+    }
+
+    /// Runs the agent on a bridge, uses "fork" for public agents.
+    ///
+    /// Waits for an idle worker first if the provider has a `pool_size`.
+    async fn run_inner(
+        &self,
+        input: T::Input,
+        progress: UnboundedSender<T::Progress>,
+    ) -> T::Output {
+        let slot = match &self.pool {
+            Some(pool) => Some(pool.acquire().await),
+            None => None,
+        };
+
         match self.reach {
             Reach::Public => {
-                let held_bridge = self.get_held_bridge();
-                held_bridge.fork()
+                let index = slot.as_ref().map(|m| m.index()).unwrap_or_default();
+                // The worker of a slot only runs one input at a time, so a worker still busy with
+                // an abandoned run is replaced instead of delaying the next run of the slot.
+                let mut recycle = RecycleOnDrop {
+                    held_bridges: &self.held_bridges,
+                    index: slot.is_some().then_some(index),
+                };
+
+                let bridge = self.get_held_bridge(index);
+                let output = run_on_bridge(&bridge, input, progress).await;
+                recycle.index = None;
+                output
+            }
+            Reach::InThread => (self.run_in_thread_fn)(input, progress).await,
+            // The provider replaces the other reaches with `Private`.
+            _ => {
+                let bridge = (self.spawn_bridge_fn)();
+                run_on_bridge(&bridge, input, progress).await
+            }
+        }
+    }
+
+    /// Returns the number of runs waiting for an idle worker.
+    pub fn queue_depth(&self) -> usize {
+        self.pool
+            .as_ref()
+            .map(|m| m.queue_depth())
+            .unwrap_or_default()
+    }
+
+    /// Calls `listener` whenever the queue depth changes.
+    pub fn subscribe_queue_depth(&self, listener: Callback<usize>) -> Option<PoolSubscription> {
+        self.pool.as_ref().map(|m| m.subscribe(listener))
+    }
+}
+
+/// Runs the agent on a fork of `bridge`, which cancels the run in the worker when dropped.
+async fn run_on_bridge<T>(
+    bridge: &OneshotWorkerBridge<T>,
+    input: T::Input,
+    progress: UnboundedSender<T::Progress>,
+) -> T::Output
+where
+    T: ReportProgress + 'static,
+{
+    let (tx, mut rx) = mpsc::unbounded();
+    let bridge = bridge.fork(Some(move |m| {
+        let _ = tx.send_now(m);
+    }));
+    bridge.send(input);
+
+    loop {
+        match rx.next().await.expect("the bridge is alive") {
+            OneshotMessage::Progress(m) => {
+                let _ = progress.send_now(m);
+            }
+            OneshotMessage::Finished(m) => return m,
+        }
+    }
+}
+
+/// Drops the worker of a pool slot if its run is abandoned.
+struct RecycleOnDrop<'a, B> {
+    held_bridges: &'a RefCell<Vec<Option<B>>>,
+    index: Option<usize>,
+}
+
+impl<B> Drop for RecycleOnDrop<'_, B> {
+    fn drop(&mut self) {
+        if let Some(index) = self.index {
+            if let Some(m) = self.held_bridges.borrow_mut().get_mut(index) {
+                *m = None;
             }
-            Reach::Private => (self.spawn_bridge_fn)(),
         }
     }
 }
 
+/// Runs an oneshot agent on the current thread, passing its input, progress and output through
+/// `C`.
+fn run_in_thread<T, C>(
+    input: T::Input,
+    progress: UnboundedSender<T::Progress>,
+) -> LocalBoxFuture<'static, T::Output>
+where
+    T: ReportProgress<
+            Input: Serialize + for<'de> Deserialize<'de> + 'static,
+            Progress: Serialize + for<'de> Deserialize<'de> + 'static,
+        > + Future<Output: Serialize + for<'de> Deserialize<'de> + 'static>
+        + 'static,
+    C: InThreadCodec + 'static,
+{
+    let progress = ProgressSink::new(move |m| {
+        let _ = progress.send_now(C::round_trip(m));
+    });
+
+    async move {
+        let output = T::create_with_progress(C::round_trip(input), progress).await;
+        C::round_trip(output)
+    }
+    .boxed_local()
+}
+
 impl<T> Clone for OneshotProviderState<T>
 where
-    T: Oneshot,
+    T: ReportProgress,
 {
     fn clone(&self) -> Self {
         Self {
             id: self.id,
             spawn_bridge_fn: self.spawn_bridge_fn.clone(),
+            run_in_thread_fn: self.run_in_thread_fn.clone(),
             reach: self.reach,
-            held_bridge: self.held_bridge.clone(),
+            pool: self.pool.clone(),
+            held_bridges: self.held_bridges.clone(),
         }
     }
 }
 
 impl<T> PartialEq for OneshotProviderState<T>
 where
-    T: Oneshot,
+    T: ReportProgress,
 {
     fn eq(&self, rhs: &Self) -> bool {
         self.id == rhs.id
@@ -86,27 +297,42 @@ where
 /// The Oneshot Agent Provider.
 ///
 /// This component provides its children access to an oneshot agent.
+///
+/// The agent runs in thread with [`Reach::InThread`], or when web workers are not available.
+///
+/// Set `pool_size` to spread CPU-heavy runs over several workers.
+///
+/// The agent must be registered with its [`Registrable`](crate::Registrable) implementation,
+/// which runs it in an [`OneshotWorker`].
 #[component]
-pub fn OneshotProvider<T, C = Bincode>(props: &WorkerProviderProps) -> Html
+pub fn OneshotProvider<T, C = Bincode>(props: &OneshotProviderProps) -> Html
 where
-    T: Oneshot<Input: Serialize + for<'de> Deserialize<'de> + 'static>
-        + Future<Output: Serialize + for<'de> Deserialize<'de> + 'static>
+    T: ReportProgress<
+            Input: Serialize + for<'de> Deserialize<'de> + 'static,
+            Progress: Serialize + for<'de> Deserialize<'de> + 'static,
+        > + Future<Output: Serialize + for<'de> Deserialize<'de> + 'static>
         + 'static,
-    C: Codec + 'static,
+    C: InThreadCodec + 'static,
 {
-    let WorkerProviderProps {
+    let OneshotProviderProps {
         children,
         path,
         lazy,
         module,
         reach,
+        pool_size,
+        queue,
     } = props.clone();
+    let reach = match reach.or_in_thread() {
+        reach @ (Reach::Public | Reach::InThread) => reach,
+        _ => Reach::Private,
+    };
 
     // Creates a spawning function so Codec is can be erased from contexts.
-    let spawn_bridge_fn: Rc<dyn Fn() -> OneshotBridge<T>> = {
+    let spawn_bridge_fn: Rc<dyn Fn() -> OneshotWorkerBridge<T>> = {
         let path = path.clone();
         Rc::new(move || {
-            OneshotSpawner::<T>::new()
+            OneshotWorker::<T>::spawner()
                 .as_module(module)
                 .encoding::<C>()
                 .spawn(&path)
@@ -114,19 +340,24 @@ where
     };
 
     let state = {
-        use_memo((path, lazy, reach), move |(_path, lazy, reach)| {
-            let state = OneshotProviderState::<T> {
-                id: get_next_id(),
-                spawn_bridge_fn,
-                reach: *reach,
-                held_bridge: Rc::default(),
-            };
-
-            if *reach == Reach::Public && !*lazy {
-                state.get_held_bridge();
-            }
-            state
-        })
+        use_memo(
+            (path, lazy, reach, pool_size, queue),
+            move |(_path, lazy, reach, pool_size, queue)| {
+                let state = OneshotProviderState::<T> {
+                    id: get_next_id(),
+                    spawn_bridge_fn,
+                    run_in_thread_fn: Rc::new(run_in_thread::<T, C>),
+                    reach: *reach,
+                    pool: pool_size.map(|size| Rc::new(Pool::new(size, *queue))),
+                    held_bridges: Rc::default(),
+                };
+
+                if *reach == Reach::Public && !*lazy {
+                    state.get_held_bridge(0);
+                }
+                state
+            },
+        )
     };
 
     html! {
@@ -135,3 +366,184 @@ where
         </ContextProvider<OneshotProviderState<T>>>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+    use std::time::Duration;
+
+    use gloo_worker::Codec;
+    use tokio::task::LocalSet;
+    use wasm_bindgen::JsValue;
+    use yew::platform::time::sleep;
+
+    use super::*;
+    use crate::oneshot::Oneshot;
+
+    thread_local! {
+        /// The number of [`Wait`] agents that have not been dropped.
+        static RUNNING: Cell<usize> = const { Cell::new(0) };
+    }
+
+    fn running() -> usize {
+        RUNNING.get()
+    }
+
+    /// Finishes with an even input right away, and never with an odd one.
+    struct Wait(u32);
+
+    impl Future for Wait {
+        type Output = u32;
+
+        fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<u32> {
+            match self.0 % 2 {
+                0 => Poll::Ready(self.0),
+                _ => Poll::Pending,
+            }
+        }
+    }
+
+    impl Oneshot for Wait {
+        type Input = u32;
+
+        fn create(input: u32) -> Self {
+            RUNNING.set(RUNNING.get() + 1);
+            Self(input)
+        }
+    }
+
+    impl Drop for Wait {
+        fn drop(&mut self) {
+            RUNNING.set(RUNNING.get() - 1);
+        }
+    }
+
+    type State = OneshotProviderState<Wait>;
+
+    fn in_thread(pool_size: Option<usize>) -> State {
+        in_thread_with::<Bincode>(pool_size)
+    }
+
+    fn in_thread_with<C: InThreadCodec + 'static>(pool_size: Option<usize>) -> State {
+        OneshotProviderState {
+            id: get_next_id(),
+            spawn_bridge_fn: Rc::new(|| unreachable!("in thread agents do not spawn workers")),
+            run_in_thread_fn: Rc::new(run_in_thread::<Wait, C>),
+            reach: Reach::InThread,
+            pool: pool_size.map(|size| Rc::new(Pool::new(size, QueueStrategy::Fifo))),
+            held_bridges: Rc::default(),
+        }
+    }
+
+    thread_local! {
+        /// The number of messages passed through [`Counting`].
+        static ROUND_TRIPS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    /// A codec that counts the messages it passes.
+    struct Counting;
+
+    impl Codec for Counting {
+        fn encode<I: Serialize>(_input: I) -> JsValue {
+            unreachable!("in thread messages are round-tripped")
+        }
+
+        fn decode<O: for<'de> Deserialize<'de>>(_input: JsValue) -> O {
+            unreachable!("in thread messages are round-tripped")
+        }
+    }
+
+    impl InThreadCodec for Counting {
+        fn round_trip<T>(value: T) -> T
+        where
+            T: Serialize + for<'de> Deserialize<'de>,
+        {
+            ROUND_TRIPS.set(ROUND_TRIPS.get() + 1);
+            Bincode::round_trip(value)
+        }
+    }
+
+    /// Waits for the spawned runs to settle.
+    async fn settle() {
+        sleep(Duration::from_millis(10)).await;
+    }
+
+    #[tokio::test]
+    async fn dropping_the_output_cancels_the_run() {
+        LocalSet::new()
+            .run_until(async {
+                let state = in_thread(None);
+                assert_eq!(state.run(2).await, 2);
+
+                let (_progress, output) = state.run_with_progress(1);
+                settle().await;
+                assert_eq!(running(), 1);
+
+                drop(output);
+                settle().await;
+                assert_eq!(running(), 0);
+            })
+            .await;
+    }
+
+    #[tokio::test]
+    async fn cancelled_runs_give_their_slot_back() {
+        LocalSet::new()
+            .run_until(async {
+                let state = in_thread(Some(1));
+
+                let (_, first) = state.run_with_progress(1);
+                let (_, second) = state.run_with_progress(3);
+                settle().await;
+                assert_eq!(running(), 1);
+                assert_eq!(state.queue_depth(), 1);
+
+                // The queued run takes over the slot.
+                drop(first);
+                settle().await;
+                assert_eq!(running(), 1);
+                assert_eq!(state.queue_depth(), 0);
+
+                drop(second);
+                settle().await;
+                assert_eq!(running(), 0);
+                assert_eq!(state.run(4).await, 4);
+            })
+            .await;
+    }
+
+    #[tokio::test]
+    async fn messages_go_through_the_codec_of_the_provider() {
+        LocalSet::new()
+            .run_until(async {
+                let state = in_thread_with::<Counting>(None);
+
+                assert_eq!(state.run(2).await, 2);
+                // The input and the output.
+                assert_eq!(ROUND_TRIPS.get(), 2);
+            })
+            .await;
+    }
+
+    #[test]
+    fn abandoned_runs_recycle_their_worker() {
+        let held_bridges = RefCell::new(vec![Some(0), Some(1)]);
+
+        drop(RecycleOnDrop {
+            held_bridges: &held_bridges,
+            index: Some(1),
+        });
+        assert_eq!(*held_bridges.borrow(), [Some(0), None]);
+
+        // A finished run keeps its worker.
+        let mut recycle = RecycleOnDrop {
+            held_bridges: &held_bridges,
+            index: Some(0),
+        };
+        recycle.index = None;
+        drop(recycle);
+        assert_eq!(*held_bridges.borrow(), [Some(0), None]);
+    }
+}
@@ -0,0 +1,230 @@
+use std::any::type_name;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::pin::pin;
+
+use futures::future::{Either, select};
+use serde::{Deserialize, Serialize};
+use yew::platform::pinned::oneshot;
+
+use super::{ProgressSink, ReportProgress};
+use crate::worker::{HandlerId, Worker, WorkerDestroyHandle, WorkerScope};
+
+/// A message sent by an [`OneshotWorker`] to a runner.
+#[derive(Debug, Serialize, Deserialize)]
+pub enum OneshotMessage<P, O> {
+    /// The agent reported its progress.
+    Progress(P),
+    /// The agent has finished.
+    Finished(O),
+}
+
+/// The message an [`OneshotWorker`] sends itself when a run is done.
+pub struct Finished<T>
+where
+    T: ReportProgress,
+{
+    handler_id: HandlerId,
+    output: Option<T::Output>,
+}
+
+impl<T> fmt::Debug for Finished<T>
+where
+    T: ReportProgress,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>())
+            .field("handler_id", &self.handler_id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The web worker running an oneshot agent.
+///
+/// Unlike the worker of the `OneshotRegistrar` of `gloo-worker`, it forwards the progress of the
+/// agent, and cancels a run when its bridge is dropped. It is registered by the
+/// [`Registrable`](crate::Registrable) implementation of the [`oneshot`](super::oneshot) macro.
+pub struct OneshotWorker<T>
+where
+    T: 'static + ReportProgress,
+{
+    running: Running<HandlerId>,
+    _marker: PhantomData<T>,
+}
+
+impl<T> fmt::Debug for OneshotWorker<T>
+where
+    T: 'static + ReportProgress,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct(type_name::<Self>()).finish_non_exhaustive()
+    }
+}
+
+/// The runs of an [`OneshotWorker`], by their bridge.
+///
+/// Dropping the sender of a run cancels it.
+struct Running<K> {
+    inner: HashMap<K, oneshot::Sender<()>>,
+}
+
+impl<K> Running<K>
+where
+    K: Hash + Eq,
+{
+    fn new() -> Self {
+        Self {
+            inner: HashMap::new(),
+        }
+    }
+
+    /// Starts a run, which is cancelled when the returned receiver resolves.
+    fn start(&mut self, key: K) -> oneshot::Receiver<()> {
+        let (cancel_tx, cancel_rx) = oneshot::channel();
+        self.inner.insert(key, cancel_tx);
+        cancel_rx
+    }
+
+    /// Removes a run, returning whether it was still running.
+    fn finish(&mut self, key: &K) -> bool {
+        self.inner.remove(key).is_some()
+    }
+
+    /// Cancels all runs.
+    fn clear(&mut self) {
+        self.inner.clear();
+    }
+}
+
+/// Runs an oneshot agent until it finishes, or returns `None` if `cancel` resolves first.
+async fn run_until_cancelled<T>(
+    input: T::Input,
+    progress: ProgressSink<T::Progress>,
+    cancel: oneshot::Receiver<()>,
+) -> Option<T::Output>
+where
+    T: ReportProgress,
+{
+    let task = pin!(T::create_with_progress(input, progress));
+    match select(task, cancel).await {
+        Either::Left((output, _)) => Some(output),
+        Either::Right(_) => None,
+    }
+}
+
+impl<T> Worker for OneshotWorker<T>
+where
+    T: 'static + ReportProgress,
+{
+    type Input = T::Input;
+    type Message = Finished<T>;
+    type Output = OneshotMessage<T::Progress, T::Output>;
+
+    fn create(_scope: &WorkerScope<Self>) -> Self {
+        Self {
+            running: Running::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    fn update(&mut self, scope: &WorkerScope<Self>, msg: Self::Message) {
+        let Finished { handler_id, output } = msg;
+
+        if self.running.finish(&handler_id) {
+            if let Some(output) = output {
+                scope.respond(handler_id, OneshotMessage::Finished(output));
+            }
+        }
+    }
+
+    fn received(&mut self, scope: &WorkerScope<Self>, input: Self::Input, handler_id: HandlerId) {
+        let cancel = self.running.start(handler_id);
+
+        let progress = {
+            let scope = scope.clone();
+            ProgressSink::new(move |m| scope.respond(handler_id, OneshotMessage::Progress(m)))
+        };
+
+        scope.send_future(async move {
+            let output = run_until_cancelled::<T>(input, progress, cancel).await;
+            Finished { handler_id, output }
+        });
+    }
+
+    fn disconnected(&mut self, _scope: &WorkerScope<Self>, id: HandlerId) {
+        self.running.finish(&id);
+    }
+
+    fn destroy(&mut self, _scope: &WorkerScope<Self>, _destruct: WorkerDestroyHandle<Self>) {
+        // Runs left when the last bridge is dropped have been abandoned, so the worker is closed
+        // right away instead of waiting for them.
+        self.running.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::pin::Pin;
+    use std::task::{Context, Poll};
+
+    use super::*;
+    use crate::oneshot::Oneshot;
+
+    /// Finishes with its input, or never if there is none.
+    struct Echo(Option<u32>);
+
+    impl Future for Echo {
+        type Output = u32;
+
+        fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<u32> {
+            self.0.map_or(Poll::Pending, Poll::Ready)
+        }
+    }
+
+    impl Oneshot for Echo {
+        type Input = Option<u32>;
+
+        fn create(input: Self::Input) -> Self {
+            Self(input)
+        }
+    }
+
+    #[tokio::test]
+    async fn finished_runs_are_responded() {
+        let mut running = Running::new();
+        let cancel = running.start(1);
+
+        let output = run_until_cancelled::<Echo>(Some(2), ProgressSink::discard(), cancel).await;
+
+        assert_eq!(output, Some(2));
+        assert!(running.finish(&1));
+    }
+
+    #[tokio::test]
+    async fn disconnecting_cancels_the_run() {
+        let mut running = Running::new();
+        let cancel = running.start(1);
+        let run = run_until_cancelled::<Echo>(None, ProgressSink::discard(), cancel);
+
+        // What `disconnected` does when the bridge of the run is dropped.
+        running.finish(&1);
+
+        assert_eq!(run.await, None);
+        // The run is not responded once it is cancelled.
+        assert!(!running.finish(&1));
+    }
+
+    #[tokio::test]
+    async fn clearing_cancels_all_runs() {
+        let mut running = Running::new();
+        let first = run_until_cancelled::<Echo>(None, ProgressSink::discard(), running.start(1));
+        let second = run_until_cancelled::<Echo>(None, ProgressSink::discard(), running.start(2));
+
+        running.clear();
+
+        assert_eq!(first.await, None);
+        assert_eq!(second.await, None);
+    }
+}
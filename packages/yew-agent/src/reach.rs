@@ -1,8 +1,42 @@
 /// The reachability of an agent.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
+#[non_exhaustive]
 pub enum Reach {
     /// Public Reachability.
     Public,
     /// Private Reachability.
     Private,
+    /// The agent runs on the current thread with [`spawn_local`](yew::platform::spawn_local)
+    /// instead of in a web worker.
+    ///
+    /// Inputs and outputs still go through the codec of the provider, so serialization bugs show
+    /// up as they would with a web worker, with
+    /// [`InThreadCodec::round_trip`](crate::InThreadCodec::round_trip). Oneshot and reactor
+    /// agents run in thread when web workers are not available, e.g. during server-side
+    /// rendering. Worker agents are bound to
+    /// the scope of a web worker and cannot run in thread, so they fall back to
+    /// [`Private`](Self::Private).
+    InThread,
+    /// The agent runs in a `SharedWorker`, shared by every tab of the origin.
+    ///
+    /// Bridges from all tabs are served by the same instance of the agent, so it can hold state
+    /// such as a websocket connection or a cache for the whole application. Only reactor agents
+    /// can be shared, registered with
+    /// [`SharedReactorRegistrar`](crate::reactor::SharedReactorRegistrar). Browsers without
+    /// `SharedWorker` fall back to [`Public`](Self::Public), and other agents to
+    /// [`Private`](Self::Private).
+    Shared,
+}
+
+impl Reach {
+    /// Returns the reachability used on the current target.
+    ///
+    /// Web workers are only available on `wasm32`, so agents run in thread elsewhere.
+    pub(crate) fn or_in_thread(self) -> Self {
+        if cfg!(target_arch = "wasm32") {
+            self
+        } else {
+            Self::InThread
+        }
+    }
 }
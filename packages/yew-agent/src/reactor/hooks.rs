@@ -4,18 +4,17 @@ use std::ops::Deref;
 use std::rc::Rc;
 
 use futures::sink::SinkExt;
-use futures::stream::{SplitSink, StreamExt};
+use futures::stream::StreamExt;
 use wasm_bindgen::UnwrapThrowExt;
 use yew::platform::pinned::RwLock;
 use yew::platform::spawn_local;
 use yew::prelude::*;
 
-use super::provider::ReactorProviderState;
-use super::{Reactor, ReactorBridge, ReactorScoped};
+use super::provider::{ReactorInputSink, ReactorProviderState};
+use super::{Reactor, ReactorScoped};
 use crate::utils::{BridgeIdState, OutputsAction, OutputsState};
 
-type ReactorTx<R> =
-    Rc<RwLock<SplitSink<ReactorBridge<R>, <<R as Reactor>::Scope as ReactorScoped>::Input>>>;
+type ReactorTx<R> = Rc<RwLock<ReactorInputSink<R>>>;
 
 /// A type that represents events from a reactor.
 pub enum ReactorEvent<R>
@@ -51,12 +50,10 @@ where
 
 impl<R> fmt::Debug for UseReactorBridgeHandle<R>
 where
-    R: 'static + Reactor<Scope: ReactorScoped<Input: fmt::Debug>>,
+    R: 'static + Reactor,
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct(type_name::<Self>())
-            .field("inner", &self.tx)
-            .finish_non_exhaustive()
+        f.debug_struct(type_name::<Self>()).finish_non_exhaustive()
     }
 }
 
@@ -135,9 +132,7 @@ where
     }
 
     let tx = use_memo((worker_state, ctr.inner), |(state, _ctr)| {
-        let bridge = state.create_bridge();
-
-        let (tx, mut rx) = bridge.split();
+        let (tx, mut rx) = state.create_bridge();
 
         spawn_local(async move {
             while let Some(m) = rx.next().await {
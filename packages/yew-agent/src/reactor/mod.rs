@@ -38,6 +38,7 @@
 
 mod hooks;
 mod provider;
+mod shared;
 
 #[doc(inline)]
 pub use gloo_worker::reactor::{
@@ -49,6 +50,7 @@ pub use hooks::{
     use_reactor_subscription,
 };
 pub use provider::ReactorProvider;
-pub(crate) use provider::ReactorProviderState;
+pub(crate) use provider::{ReactorInputSink, ReactorProviderState};
+pub use shared::SharedReactorRegistrar;
 /// A procedural macro to create reactor agents.
 pub use yew_agent_macro::reactor;
@@ -1,16 +1,33 @@
 use std::any::type_name;
-use std::cell::RefCell;
+use std::cell::{RefCell, RefMut};
+use std::convert::Infallible;
 use std::fmt;
+use std::pin::Pin;
 use std::rc::Rc;
 
-use gloo_worker::{Bincode, Codec};
+use futures::sink::{self, Sink, SinkExt};
+use futures::stream::{Stream, StreamExt};
+use gloo_worker::Bincode;
 use serde::{Deserialize, Serialize};
+use yew::platform::pinned::mpsc;
+use yew::platform::spawn_local;
 use yew::prelude::*;
 
+use super::shared::SharedReactorConnection;
 use super::{Reactor, ReactorBridge, ReactorScoped, ReactorSpawner};
-use crate::Reach;
 use crate::utils::get_next_id;
 use crate::worker::WorkerProviderProps;
+use crate::{InThreadCodec, Reach};
+
+/// The sending half of a bridge to a reactor.
+pub(crate) type ReactorInputSink<R> =
+    Pin<Box<dyn Sink<<<R as Reactor>::Scope as ReactorScoped>::Input, Error = ()>>>;
+
+/// The receiving half of a bridge to a reactor.
+pub(crate) type ReactorOutputStream<R> =
+    Pin<Box<dyn Stream<Item = <<R as Reactor>::Scope as ReactorScoped>::Output>>>;
+
+type SpawnInThreadFn<R> = dyn Fn() -> (ReactorInputSink<R>, ReactorOutputStream<R>);
 
 pub(crate) struct ReactorProviderState<T>
 where
@@ -18,8 +35,11 @@ where
 {
     id: usize,
     spawn_bridge_fn: Rc<dyn Fn() -> ReactorBridge<T>>,
+    spawn_in_thread_fn: Rc<SpawnInThreadFn<T>>,
+    connect_shared_fn: Rc<dyn Fn() -> SharedReactorConnection<T>>,
     reach: Reach,
     held_bridge: Rc<RefCell<Option<ReactorBridge<T>>>>,
+    shared_connection: Rc<RefCell<Option<SharedReactorConnection<T>>>>,
 }
 
 impl<T> fmt::Debug for ReactorProviderState<T>
@@ -48,18 +68,59 @@ where
         }
     }
 
+    fn get_shared_connection(&self) -> RefMut<'_, SharedReactorConnection<T>> {
+        RefMut::map(self.shared_connection.borrow_mut(), |m| {
+            m.get_or_insert_with(|| (self.connect_shared_fn)())
+        })
+    }
+
     /// Creates a bridge, uses "fork" for public agents.
-    pub fn create_bridge(&self) -> ReactorBridge<T> {
-        match self.reach {
+    pub fn create_bridge(&self) -> (ReactorInputSink<T>, ReactorOutputStream<T>) {
+        let bridge = match self.reach {
             Reach::Public => {
                 let held_bridge = self.get_held_bridge();
                 held_bridge.fork()
             }
             Reach::Private => (self.spawn_bridge_fn)(),
-        }
+            Reach::InThread => return (self.spawn_in_thread_fn)(),
+            Reach::Shared => return self.get_shared_connection().bridge(),
+        };
+
+        let (tx, rx) = bridge.split();
+        (Box::pin(tx.sink_map_err(|_| ())), Box::pin(rx))
     }
 }
 
+/// Spawns a reactor on the current thread, passing its inputs and outputs through `C`.
+fn spawn_in_thread<R, C>() -> (ReactorInputSink<R>, ReactorOutputStream<R>)
+where
+    R: 'static
+        + Reactor<
+            Scope: ReactorScoped<
+                Input: Serialize + for<'de> Deserialize<'de> + 'static,
+                Output: Serialize + for<'de> Deserialize<'de> + 'static,
+            >,
+        >,
+    C: InThreadCodec + 'static,
+{
+    let (input_tx, input_rx) = mpsc::unbounded();
+    let (output_tx, output_rx) = mpsc::unbounded();
+
+    let input_stream = input_rx.map(C::round_trip);
+    let output_sink = sink::unfold(output_tx, |output_tx, output| async move {
+        // Like with a web worker, outputs sent after the bridge is dropped are discarded.
+        let _ = output_tx.send_now(C::round_trip(output));
+        Ok::<_, Infallible>(output_tx)
+    });
+    spawn_local(R::create(ReactorScoped::new(input_stream, output_sink)));
+
+    let input_sink = sink::unfold(input_tx, |input_tx, input| async move {
+        input_tx.send_now(input).map_err(|_| ())?;
+        Ok(input_tx)
+    });
+    (Box::pin(input_sink), Box::pin(output_rx))
+}
+
 impl<T> Clone for ReactorProviderState<T>
 where
     T: Reactor,
@@ -68,8 +129,11 @@ where
         Self {
             id: self.id,
             spawn_bridge_fn: self.spawn_bridge_fn.clone(),
+            spawn_in_thread_fn: self.spawn_in_thread_fn.clone(),
+            connect_shared_fn: self.connect_shared_fn.clone(),
             reach: self.reach,
             held_bridge: self.held_bridge.clone(),
+            shared_connection: self.shared_connection.clone(),
         }
     }
 }
@@ -86,6 +150,11 @@ where
 /// The Reactor Agent Provider.
 ///
 /// This component provides its children access to a reactor agent.
+///
+/// The agent runs in thread with [`Reach::InThread`], or when web workers are not available.
+/// With [`Reach::Shared`], `path` is the script of a `SharedWorker` that registers the agent with
+/// [`SharedReactorRegistrar`](super::SharedReactorRegistrar). If the browser does not support
+/// `SharedWorker`, the script runs in a web worker of the tab with [`Reach::Public`] instead.
 #[component]
 pub fn ReactorProvider<R, C = Bincode>(props: &WorkerProviderProps) -> Html
 where
@@ -96,7 +165,7 @@ where
                 Output: Serialize + for<'de> Deserialize<'de> + 'static,
             >,
         >,
-    C: Codec + 'static,
+    C: InThreadCodec + 'static,
 {
     let WorkerProviderProps {
         children,
@@ -105,6 +174,10 @@ where
         module,
         reach,
     } = props.clone();
+    let reach = match reach.or_in_thread() {
+        Reach::Shared if !SharedReactorConnection::<R>::is_supported() => Reach::Public,
+        reach => reach,
+    };
 
     // Creates a spawning function so Codec is can be erased from contexts.
     let spawn_bridge_fn: Rc<dyn Fn() -> ReactorBridge<R>> = {
@@ -116,18 +189,33 @@ where
                 .spawn(&path)
         })
     };
+    let connect_shared_fn: Rc<dyn Fn() -> SharedReactorConnection<R>> = {
+        let path = path.clone();
+        Rc::new(move || SharedReactorConnection::connect::<C>(&path, module))
+    };
 
     let state = {
         use_memo((path, lazy, reach), move |(_path, lazy, reach)| {
             let state = ReactorProviderState::<R> {
                 id: get_next_id(),
                 spawn_bridge_fn,
+                spawn_in_thread_fn: Rc::new(spawn_in_thread::<R, C>),
+                connect_shared_fn,
                 reach: *reach,
                 held_bridge: Rc::default(),
+                shared_connection: Rc::default(),
             };
 
-            if *reach == Reach::Public && !*lazy {
-                state.get_held_bridge();
+            if !*lazy {
+                match reach {
+                    Reach::Public => {
+                        state.get_held_bridge();
+                    }
+                    Reach::Shared => {
+                        state.get_shared_connection();
+                    }
+                    _ => {}
+                }
             }
             state
         })
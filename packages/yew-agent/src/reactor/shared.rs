@@ -0,0 +1,434 @@
+//! Reactor agents shared by the tabs of an origin through a `SharedWorker`.
+
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::convert::Infallible;
+use std::fmt;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+use futures::sink::{self, Sink};
+use gloo_worker::{Bincode, Codec};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue, UnwrapThrowExt};
+use web_sys::{
+    MessageEvent, MessagePort, SharedWorker, SharedWorkerGlobalScope, WorkerOptions, WorkerType,
+};
+use yew::platform::pinned::mpsc::{self, UnboundedSender};
+use yew::platform::spawn_local;
+
+use super::provider::{ReactorInputSink, ReactorOutputStream};
+use super::{Reactor, ReactorRegistrar, ReactorScoped};
+use crate::utils::get_next_id;
+
+type Input<R> = <<R as Reactor>::Scope as ReactorScoped>::Input;
+type Output<R> = <<R as Reactor>::Scope as ReactorScoped>::Output;
+
+/// A message sent by a tab to the shared worker.
+///
+/// Bridges are identified by an id unique to the port of their tab.
+#[derive(Serialize, Deserialize)]
+enum ToShared<I> {
+    Connect(u32),
+    Input(u32, I),
+    Disconnect(u32),
+    /// The tab is closing, all of its bridges are disconnected.
+    Close,
+}
+
+/// A message sent by the shared worker to a tab.
+///
+/// The worker sends outputs by reference, and `Finished` with `()` as the output type, neither of
+/// which changes how the message is encoded.
+#[derive(Serialize, Deserialize)]
+enum FromShared<O> {
+    Output(u32, O),
+    /// The reactor of the bridge has exited.
+    Finished(u32),
+}
+
+fn post<C, T>(port: &MessagePort, msg: T)
+where
+    C: Codec,
+    T: Serialize,
+{
+    // The port of a closed tab silently discards messages.
+    let _ = port.post_message(&C::encode(msg));
+}
+
+/// The tabs connected to the shared worker, by the id of their port.
+type Ports = Rc<RefCell<HashMap<usize, PortSession>>>;
+
+/// A tab connected to the shared worker.
+struct PortSession {
+    port: MessagePort,
+    /// The ids of the bridges of the tab.
+    bridges: HashSet<u32>,
+    /// Dropped along with the session, once the tab is closed.
+    _onmessage: Closure<dyn FnMut(MessageEvent)>,
+}
+
+/// Registers a reactor agent in a `SharedWorker`, to be bridged with [`Reach::Shared`].
+///
+/// Every tab of the origin connects to the same worker, which runs a single instance of the
+/// reactor for all of them: the inputs of every bridge are merged into its scope, and each of its
+/// outputs is sent to every bridge. The reactor can hold state such as a websocket connection or a
+/// cache for the whole application. Once it exits, bridges are disconnected right away.
+///
+/// If the browser does not support `SharedWorker`, the provider runs the script in a web worker
+/// of the tab with [`Reach::Public`], and this registrar registers the reactor with a
+/// [`ReactorRegistrar`](super::ReactorRegistrar) instead.
+///
+/// ```no_run
+/// # use yew_agent::reactor::{ReactorScope, SharedReactorRegistrar, reactor};
+/// #[reactor(Cache)]
+/// async fn cache(scope: ReactorScope<String, String>) {
+///     // ...
+/// }
+///
+/// // In the script of the shared worker:
+/// SharedReactorRegistrar::<Cache>::new().register();
+/// ```
+///
+/// [`Reach::Shared`]: crate::Reach::Shared
+/// [`Reach::Public`]: crate::Reach::Public
+pub struct SharedReactorRegistrar<R, CODEC = Bincode>
+where
+    R: Reactor + 'static,
+    CODEC: Codec + 'static,
+{
+    _marker: PhantomData<(R, CODEC)>,
+}
+
+impl<R, CODEC> fmt::Debug for SharedReactorRegistrar<R, CODEC>
+where
+    R: Reactor + 'static,
+    CODEC: Codec + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedReactorRegistrar<_>")
+    }
+}
+
+impl<R> Default for SharedReactorRegistrar<R>
+where
+    R: Reactor + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R, CODEC> SharedReactorRegistrar<R, CODEC>
+where
+    R: Reactor + 'static,
+    CODEC: Codec + 'static,
+{
+    /// Creates a new registrar.
+    pub const fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+
+    /// Sets the encoding, which must be the same as the one of the provider.
+    pub const fn encoding<C>(&self) -> SharedReactorRegistrar<R, C>
+    where
+        C: Codec + 'static,
+    {
+        SharedReactorRegistrar {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<R, CODEC> SharedReactorRegistrar<R, CODEC>
+where
+    R: Reactor<
+            Scope: ReactorScoped<
+                Input: Serialize + for<'de> Deserialize<'de> + 'static,
+                Output: Serialize + for<'de> Deserialize<'de> + 'static,
+            >,
+        > + 'static,
+    CODEC: Codec + 'static,
+{
+    /// Serves the reactor to the tabs connecting to the current `SharedWorker`.
+    pub fn register(&self) {
+        let Ok(global) = js_sys::global().dyn_into::<SharedWorkerGlobalScope>() else {
+            // The provider fell back to a dedicated worker.
+            ReactorRegistrar::<R>::new().encoding::<CODEC>().register();
+            return;
+        };
+
+        let ports: Ports = Rc::default();
+        let finished = Rc::new(Cell::new(false));
+        let (input_tx, input_rx) = mpsc::unbounded();
+
+        let output_sink = sink::unfold(ports.clone(), |ports, output| async move {
+            for session in ports.borrow().values() {
+                for id in &session.bridges {
+                    post::<CODEC, _>(&session.port, FromShared::Output(*id, &output));
+                }
+            }
+            Ok::<_, Infallible>(ports)
+        });
+        {
+            let ports = ports.clone();
+            let finished = finished.clone();
+            spawn_local(async move {
+                R::create(ReactorScoped::new(input_rx, output_sink)).await;
+
+                finished.set(true);
+                for session in ports.borrow_mut().values_mut() {
+                    for id in session.bridges.drain() {
+                        post::<CODEC, _>(&session.port, FromShared::<()>::Finished(id));
+                    }
+                }
+            });
+        }
+
+        let onconnect = Closure::<dyn FnMut(MessageEvent)>::new(move |e: MessageEvent| {
+            let port = e.ports().get(0).unchecked_into::<MessagePort>();
+            Self::serve(port, &ports, &finished, &input_tx);
+        });
+        global.set_onconnect(Some(onconnect.as_ref().unchecked_ref()));
+        onconnect.forget();
+    }
+
+    /// Routes the messages of the tab connected to `port`.
+    fn serve(
+        port: MessagePort,
+        ports: &Ports,
+        finished: &Rc<Cell<bool>>,
+        input_tx: &UnboundedSender<Input<R>>,
+    ) {
+        let port_id = get_next_id();
+
+        let onmessage = {
+            let port = port.clone();
+            let ports = ports.clone();
+            let finished = finished.clone();
+            let input_tx = input_tx.clone();
+            Closure::<dyn FnMut(MessageEvent)>::new(move |e: MessageEvent| {
+                match CODEC::decode::<ToShared<Input<R>>>(e.data()) {
+                    ToShared::Connect(id) => {
+                        if finished.get() {
+                            post::<CODEC, _>(&port, FromShared::<()>::Finished(id));
+                        } else if let Some(session) = ports.borrow_mut().get_mut(&port_id) {
+                            session.bridges.insert(id);
+                        }
+                    }
+                    ToShared::Input(id, input) => {
+                        let connected = ports
+                            .borrow()
+                            .get(&port_id)
+                            .is_some_and(|session| session.bridges.contains(&id));
+                        if connected {
+                            let _ = input_tx.send_now(input);
+                        }
+                    }
+                    ToShared::Disconnect(id) => {
+                        if let Some(session) = ports.borrow_mut().get_mut(&port_id) {
+                            session.bridges.remove(&id);
+                        }
+                    }
+                    ToShared::Close => {
+                        port.set_onmessage(None);
+                        let session = ports.borrow_mut().remove(&port_id);
+                        // This closure is part of the session, which cannot be dropped while the
+                        // closure runs.
+                        spawn_local(async move { drop(session) });
+                    }
+                }
+            })
+        };
+        // Setting `onmessage` also starts the port.
+        port.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+        ports.borrow_mut().insert(
+            port_id,
+            PortSession {
+                port,
+                bridges: HashSet::new(),
+                _onmessage: onmessage,
+            },
+        );
+    }
+}
+
+/// The connection of a tab to the `SharedWorker` of a reactor.
+pub(crate) struct SharedReactorConnection<R>
+where
+    R: Reactor + 'static,
+{
+    port: MessagePort,
+    post: Rc<dyn Fn(ToShared<Input<R>>)>,
+    outputs: Rc<RefCell<HashMap<u32, UnboundedSender<Output<R>>>>>,
+    next_id: Cell<u32>,
+    _onmessage: Closure<dyn FnMut(MessageEvent)>,
+    onpagehide: Closure<dyn FnMut()>,
+}
+
+impl<R> SharedReactorConnection<R>
+where
+    R: Reactor<
+            Scope: ReactorScoped<
+                Input: Serialize + for<'de> Deserialize<'de> + 'static,
+                Output: Serialize + for<'de> Deserialize<'de> + 'static,
+            >,
+        > + 'static,
+{
+    /// Connects to the shared worker at `path`, spawning it if no other tab has.
+    pub fn connect<C>(path: &str, module: bool) -> Self
+    where
+        C: Codec + 'static,
+    {
+        let options = WorkerOptions::new();
+        if module {
+            options.set_type(WorkerType::Module);
+        }
+        let port = SharedWorker::new_with_worker_options(path, &options)
+            .expect_throw("failed to spawn shared worker")
+            .port();
+
+        let outputs: Rc<RefCell<HashMap<u32, UnboundedSender<Output<R>>>>> = Rc::default();
+        let onmessage = {
+            let outputs = outputs.clone();
+            Closure::<dyn FnMut(MessageEvent)>::new(move |e: MessageEvent| {
+                match C::decode::<FromShared<Output<R>>>(e.data()) {
+                    FromShared::Output(id, output) => {
+                        if let Some(tx) = outputs.borrow().get(&id) {
+                            let _ = tx.send_now(output);
+                        }
+                    }
+                    // Ends the output stream of the bridge.
+                    FromShared::Finished(id) => {
+                        outputs.borrow_mut().remove(&id);
+                    }
+                }
+            })
+        };
+        port.set_onmessage(Some(onmessage.as_ref().unchecked_ref()));
+
+        let post: Rc<dyn Fn(ToShared<Input<R>>)> = {
+            let port = port.clone();
+            Rc::new(move |msg| post::<C, _>(&port, msg))
+        };
+
+        // Ports are not closed when their tab is, so the worker is told before the tab goes away.
+        let onpagehide = {
+            let post = post.clone();
+            Closure::<dyn FnMut()>::new(move || post(ToShared::Close))
+        };
+        if let Some(window) = web_sys::window() {
+            let _ = window
+                .add_event_listener_with_callback("pagehide", onpagehide.as_ref().unchecked_ref());
+        }
+
+        Self {
+            port,
+            post,
+            outputs,
+            next_id: Cell::new(0),
+            _onmessage: onmessage,
+            onpagehide,
+        }
+    }
+
+    /// Returns whether the browser supports `SharedWorker`.
+    pub fn is_supported() -> bool {
+        js_sys::Reflect::has(&js_sys::global(), &JsValue::from_str("SharedWorker")).unwrap_or(false)
+    }
+}
+
+impl<R> SharedReactorConnection<R>
+where
+    R: Reactor + 'static,
+{
+    /// Creates a bridge to the reactor of the shared worker.
+    pub fn bridge(&self) -> (ReactorInputSink<R>, ReactorOutputStream<R>) {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+
+        let (output_tx, output_rx) = mpsc::unbounded();
+        self.outputs.borrow_mut().insert(id, output_tx);
+        (self.post)(ToShared::Connect(id));
+
+        (
+            Box::pin(input_sink(self.post.clone(), id)),
+            Box::pin(output_rx),
+        )
+    }
+}
+
+impl<R> Drop for SharedReactorConnection<R>
+where
+    R: Reactor + 'static,
+{
+    fn drop(&mut self) {
+        (self.post)(ToShared::Close);
+        if let Some(window) = web_sys::window() {
+            let _ = window.remove_event_listener_with_callback(
+                "pagehide",
+                self.onpagehide.as_ref().unchecked_ref(),
+            );
+        }
+        self.port.set_onmessage(None);
+        self.port.close();
+    }
+}
+
+/// Returns the input sink of the bridge `id`, which posts its inputs with `post` and disconnects
+/// the bridge when dropped.
+fn input_sink<I>(post: Rc<dyn Fn(ToShared<I>)>, id: u32) -> impl Sink<I, Error = ()>
+where
+    I: 'static,
+{
+    let guard = DisconnectOnDrop { post, id };
+    sink::unfold(guard, |guard, input| async move {
+        (guard.post)(ToShared::Input(guard.id, input));
+        Ok::<_, ()>(guard)
+    })
+}
+
+/// Disconnects a bridge when its input sink is dropped.
+struct DisconnectOnDrop<I> {
+    post: Rc<dyn Fn(ToShared<I>)>,
+    id: u32,
+}
+
+impl<I> Drop for DisconnectOnDrop<I> {
+    fn drop(&mut self) {
+        (self.post)(ToShared::Disconnect(self.id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::SinkExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn inputs_are_posted_until_the_bridge_is_dropped() {
+        let posted = Rc::new(RefCell::new(Vec::new()));
+        let post: Rc<dyn Fn(ToShared<u32>)> = {
+            let posted = posted.clone();
+            Rc::new(move |msg| posted.borrow_mut().push(msg))
+        };
+
+        let mut sink = Box::pin(input_sink(post, 7));
+        sink.send(1).await.unwrap();
+        sink.send(2).await.unwrap();
+        drop(sink);
+
+        assert!(matches!(
+            posted.borrow()[..],
+            [
+                ToShared::Input(7, 1),
+                ToShared::Input(7, 2),
+                ToShared::Disconnect(7)
+            ]
+        ));
+    }
+}
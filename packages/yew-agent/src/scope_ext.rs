@@ -4,7 +4,6 @@ use std::any::type_name;
 use std::fmt;
 use std::rc::Rc;
 
-use futures::stream::SplitSink;
 use futures::{SinkExt, StreamExt};
 use wasm_bindgen::UnwrapThrowExt;
 use yew::html::Scope;
@@ -12,8 +11,10 @@ use yew::platform::pinned::RwLock;
 use yew::platform::spawn_local;
 use yew::prelude::*;
 
-use crate::oneshot::{Oneshot, OneshotProviderState};
-use crate::reactor::{Reactor, ReactorBridge, ReactorEvent, ReactorProviderState, ReactorScoped};
+use crate::oneshot::{OneshotProviderState, ReportProgress};
+use crate::reactor::{
+    Reactor, ReactorEvent, ReactorInputSink, ReactorProviderState, ReactorScoped,
+};
 use crate::worker::{Worker, WorkerBridge, WorkerProviderState};
 
 /// A Worker Bridge Handle.
@@ -35,8 +36,7 @@ where
     }
 }
 
-type ReactorTx<R> =
-    Rc<RwLock<SplitSink<ReactorBridge<R>, <<R as Reactor>::Scope as ReactorScoped>::Input>>>;
+type ReactorTx<R> = Rc<RwLock<ReactorInputSink<R>>>;
 
 /// A Reactor Bridge Handle.
 pub struct ReactorBridgeHandle<R>
@@ -86,7 +86,7 @@ pub trait AgentScopeExt {
     /// Runs an oneshot in an Oneshot Agent.
     fn run_oneshot<T>(&self, input: T::Input, callback: Callback<T::Output>)
     where
-        T: Oneshot + 'static;
+        T: ReportProgress + 'static;
 }
 
 impl<COMP> AgentScopeExt for Scope<COMP>
@@ -114,8 +114,7 @@ where
             .context::<ReactorProviderState<R>>((|_| {}).into())
             .expect_throw("failed to bridge to agent.")
             .0
-            .create_bridge()
-            .split();
+            .create_bridge();
 
         spawn_local(async move {
             while let Some(m) = rx.next().await {
@@ -132,12 +131,12 @@ where
 
     fn run_oneshot<T>(&self, input: T::Input, callback: Callback<T::Output>)
     where
-        T: Oneshot + 'static,
+        T: ReportProgress + 'static,
     {
         let (inner, _) = self
             .context::<OneshotProviderState<T>>((|_| {}).into())
             .expect_throw("failed to bridge to agent.");
 
-        spawn_local(async move { callback.emit(inner.create_bridge().run(input).await) });
+        spawn_local(async move { callback.emit(inner.run(input).await) });
     }
 }
@@ -19,6 +19,9 @@ pub struct WorkerProviderProps {
 
     /// The reachability of an agent.
     ///
+    /// Worker agents cannot run in thread or be shared, [`InThread`](Reach::InThread) and
+    /// [`Shared`](Reach::Shared) fall back to [`Private`](Reach::Private).
+    ///
     /// Default: [`Public`](Reach::Public).
     #[prop_or(Reach::Public)]
     pub reach: Reach,
@@ -86,7 +89,8 @@ where
                 let held_bridge = self.get_held_bridge();
                 held_bridge.fork(Some(move |m| cb.emit(m)))
             }
-            Reach::Private => (self.spawn_bridge_fn)(),
+            // The provider replaces the other reaches with `Private`.
+            _ => (self.spawn_bridge_fn)(),
         }
     }
 }
@@ -119,6 +123,10 @@ where
         module,
         reach,
     } = props.clone();
+    let reach = match reach {
+        Reach::Public => Reach::Public,
+        _ => Reach::Private,
+    };
 
     // Creates a spawning function so Codec is can be erased from contexts.
     let spawn_bridge_fn: Rc<dyn Fn() -> WorkerBridge<W>> = {
@@ -1,7 +1,9 @@
 use proc_macro::TokenStream;
 use proc_macro2::Span;
 use quote::quote;
-use syn::{FnArg, Ident, ImplItem, ImplItemFn, ItemImpl, Pat, PatType, parse_macro_input};
+use syn::{
+    FnArg, Ident, ImplItem, ImplItemFn, ItemImpl, LitInt, LitStr, Pat, PatType, parse_macro_input,
+};
 
 /// Derive a [`LinkedState`] implementation from an impl block that declares
 /// `type Context`, `type Input`, and `async fn resolve`.
@@ -19,6 +21,30 @@ use syn::{FnArg, Ident, ImplItem, ImplItemFn, ItemImpl, Pat, PatType, parse_macr
 /// automatically. When `type Error` is present, the resolve body must return
 /// `Result<Self, Self::Error>`.
 ///
+/// ## `const STALE_AFTER` and `const TAGS` (optional)
+///
+/// Forwarded to [`LinkedState::STALE_AFTER`] and [`LinkedState::TAGS`] to
+/// control client-side cache expiry and tag-based invalidation.
+///
+/// ## `key` and `version` (optional)
+///
+/// The [`LinkedState::TYPE_KEY`] defaults to the path of the type, so moving
+/// the type to another module changes it. `#[linked_state(key = "post")]` pins
+/// the key instead; it must be unique among the states, actions and streams
+/// registered with a [`Resolver`](yew_link::Resolver).
+///
+/// `#[linked_state(version = 2)]` sets [`LinkedState::VERSION`]. Bump it when
+/// the shape of the state or its input changes: clients built against another
+/// version get a [`LinkError::VersionMismatch`](yew_link::LinkError) instead of
+/// a value they cannot decode.
+///
+/// ## Request extractor (optional)
+///
+/// `resolve` may take a third parameter whose type implements
+/// [`yew_link::FromRequestContext`]. It is extracted from the request the state
+/// is resolved for and becomes `LinkedStateResolve::Extract`. Without it,
+/// `Extract` is `()`.
+///
 /// # Example
 ///
 /// ```ignore
@@ -43,17 +69,206 @@ use syn::{FnArg, Ident, ImplItem, ImplItemFn, ItemImpl, Pat, PatType, parse_macr
 ///         ctx.get_post(*id).await.map_err(ApiError::from)
 ///     }
 /// }
+///
+/// // Only visible to the current user:
+/// #[linked_state]
+/// impl LinkedState for Drafts {
+///     type Context = DbPool;
+///     type Input = ();
+///     type Error = ApiError;
+///
+///     async fn resolve(ctx: &DbPool, _input: &(), user: CurrentUser) -> Result<Self, ApiError> {
+///         let user = user.0.ok_or(ApiError::Unauthorized)?;
+///         ctx.get_drafts(user).await.map_err(ApiError::from)
+///     }
+/// }
+///
+/// // Re-fetched in the background after a minute, invalidated with the "posts" tag:
+/// #[linked_state]
+/// impl LinkedState for Post {
+///     type Context = DbPool;
+///     type Input = u32;
+///
+///     const STALE_AFTER: Option<Duration> = Some(Duration::from_secs(60));
+///     const TAGS: &'static [&'static str] = &["posts"];
+///
+///     async fn resolve(ctx: &DbPool, id: &u32) -> Self {
+///         ctx.get_post(*id).await
+///     }
+/// }
+///
+/// // With a stable key, in its second version:
+/// #[linked_state(key = "post", version = 2)]
+/// impl LinkedState for Post {
+///     type Context = DbPool;
+///     type Input = u32;
+///
+///     async fn resolve(ctx: &DbPool, id: &u32) -> Self {
+///         ctx.get_post(*id).await
+///     }
+/// }
 /// ```
 #[proc_macro_attribute]
-pub fn linked_state(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub fn linked_state(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut attrs = LinkedAttrs::new("linked_state", true);
+    let parser = syn::meta::parser(|meta| attrs.parse(meta));
+    parse_macro_input!(attr with parser);
     let impl_block = parse_macro_input!(item as ItemImpl);
-    match expand(impl_block) {
+    match expand(impl_block, attrs) {
         Ok(tokens) => tokens.into(),
         Err(err) => err.to_compile_error().into(),
     }
 }
 
-fn expand(impl_block: ItemImpl) -> syn::Result<proc_macro2::TokenStream> {
+/// Derive a [`LinkedAction`] implementation from an impl block that declares
+/// `type Context`, `type Input`, `type Output`, and `async fn resolve`.
+///
+/// On all targets the macro emits
+/// `impl LinkedAction for T { type Input = …; type Output = …; type Error = …; }`.
+///
+/// On the server (`not(target_arch = "wasm32")`) it additionally emits
+/// `impl LinkedActionResolve for T { … }` with the user-provided resolve body.
+/// This half is stripped from WASM bundles automatically.
+///
+/// ## `type Error` (optional)
+///
+/// If `type Error` is omitted, it defaults to [`yew_link::Never`] and the resolve
+/// body is wrapped in `Ok(…)` automatically. When `type Error` is present, the
+/// resolve body must return `Result<Self::Output, Self::Error>`.
+///
+/// Like with [`macro@linked_state`], `resolve` may take a third parameter that
+/// is extracted from the request, `#[linked_action(key = "...")]` pins the
+/// `TYPE_KEY` and `#[linked_action(version = 2)]` sets [`LinkedAction::VERSION`].
+///
+/// # Example
+///
+/// ```ignore
+/// pub struct CreatePost;
+///
+/// #[linked_action]
+/// impl LinkedAction for CreatePost {
+///     type Context = DbPool;
+///     type Input = NewPost;
+///     type Output = Post;
+///     type Error = ApiError;
+///
+///     async fn resolve(ctx: &DbPool, post: &NewPost) -> Result<Post, ApiError> {
+///         ctx.insert_post(post).await.map_err(ApiError::from)
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn linked_action(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut attrs = LinkedAttrs::new("linked_action", true);
+    let parser = syn::meta::parser(|meta| attrs.parse(meta));
+    parse_macro_input!(attr with parser);
+    let impl_block = parse_macro_input!(item as ItemImpl);
+    match expand_action(impl_block, attrs) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Derive a [`LinkedStream`] implementation from an impl block that declares
+/// `type Context`, `type Input`, and `fn subscribe`.
+///
+/// On all targets the macro emits `impl LinkedStream for T { type Input = …; }`.
+///
+/// On the server (`not(target_arch = "wasm32")`) it additionally emits
+/// `impl LinkedStreamResolve for T { … }` with the user-provided subscribe body.
+/// This half is stripped from WASM bundles automatically.
+///
+/// The body of `subscribe` must evaluate to a `Stream<Item = Self> + Send + 'static`,
+/// so it cannot borrow from the context or the input.
+/// The macro boxes it into the `BoxStream` that `LinkedStreamResolve::subscribe`
+/// returns.
+///
+/// Like with [`macro@linked_state`], `subscribe` may take a third parameter that
+/// is extracted from the request, and `#[linked_stream(key = "...")]` pins the
+/// `TYPE_KEY`.
+///
+/// # Example
+///
+/// ```ignore
+/// #[linked_stream]
+/// impl LinkedStream for Price {
+///     type Context = PriceFeed;
+///     type Input = String;
+///
+///     fn subscribe(feed: &PriceFeed, symbol: &String) -> impl Stream<Item = Self> {
+///         feed.watch(symbol)
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn linked_stream(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut attrs = LinkedAttrs::new("linked_stream", false);
+    let parser = syn::meta::parser(|meta| attrs.parse(meta));
+    parse_macro_input!(attr with parser);
+    let impl_block = parse_macro_input!(item as ItemImpl);
+    match expand_stream(impl_block, attrs) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// The arguments of the `#[linked_*]` attributes.
+struct LinkedAttrs {
+    macro_name: &'static str,
+    /// Whether `version` is accepted.
+    versioned: bool,
+    key: Option<LitStr>,
+    version: Option<u32>,
+}
+
+impl LinkedAttrs {
+    fn new(macro_name: &'static str, versioned: bool) -> Self {
+        Self {
+            macro_name,
+            versioned,
+            key: None,
+            version: None,
+        }
+    }
+
+    fn parse(&mut self, meta: syn::meta::ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("key") && self.key.is_none() {
+            let key: LitStr = meta.value()?.parse()?;
+            if key.value().is_empty() {
+                return Err(syn::Error::new_spanned(key, "`key` must not be empty"));
+            }
+            self.key = Some(key);
+            Ok(())
+        } else if self.versioned && meta.path.is_ident("version") && self.version.is_none() {
+            let version: LitInt = meta.value()?.parse()?;
+            // Emitted as a plain `u32` so that suffixed literals like `2u8` are accepted.
+            self.version = Some(version.base10_parse::<u32>()?);
+            Ok(())
+        } else if self.versioned {
+            Err(meta.error(format!(
+                "#[{}] accepts only `key = \"...\"` and `version = N`, each at most once",
+                self.macro_name
+            )))
+        } else {
+            Err(meta.error(format!(
+                "#[{}] accepts only `key = \"...\"`, at most once",
+                self.macro_name
+            )))
+        }
+    }
+
+    /// Returns the `TYPE_KEY` of `self_ty`, which defaults to its path.
+    fn type_key(&self, self_ty: &syn::Type) -> proc_macro2::TokenStream {
+        match &self.key {
+            Some(key) => quote! { #key },
+            None => {
+                quote! { ::core::concat!(::core::module_path!(), "::", ::core::stringify!(#self_ty)) }
+            }
+        }
+    }
+}
+
+fn expand(impl_block: ItemImpl, attrs: LinkedAttrs) -> syn::Result<proc_macro2::TokenStream> {
     let self_ty = &impl_block.self_ty;
     let (impl_generics, ty_generics, where_clause) = impl_block.generics.split_for_impl();
 
@@ -61,18 +276,21 @@ fn expand(impl_block: ItemImpl) -> syn::Result<proc_macro2::TokenStream> {
     let mut context_ty = None;
     let mut error_ty: Option<&syn::Type> = None;
     let mut resolve_fn: Option<&ImplItemFn> = None;
+    let mut consts = Vec::new();
 
     for item in &impl_block.items {
         match item {
             ImplItem::Type(t) if t.ident == "Input" => input_ty = Some(&t.ty),
             ImplItem::Type(t) if t.ident == "Context" => context_ty = Some(&t.ty),
             ImplItem::Type(t) if t.ident == "Error" => error_ty = Some(&t.ty),
+            ImplItem::Const(c) if c.ident == "STALE_AFTER" || c.ident == "TAGS" => consts.push(c),
             ImplItem::Fn(f) if f.sig.ident == "resolve" => resolve_fn = Some(f),
             other => {
                 return Err(syn::Error::new_spanned(
                     other,
                     "#[linked_state] expects only `type Input`, `type Context`, `type Error` \
-                     (optional), and `async fn resolve`",
+                     (optional), `const STALE_AFTER` (optional), `const TAGS` (optional), and \
+                     `async fn resolve`",
                 ));
             }
         }
@@ -82,28 +300,14 @@ fn expand(impl_block: ItemImpl) -> syn::Result<proc_macro2::TokenStream> {
         input_ty.ok_or_else(|| syn::Error::new(Span::call_site(), "missing `type Input`"))?;
     let context_ty =
         context_ty.ok_or_else(|| syn::Error::new(Span::call_site(), "missing `type Context`"))?;
-    let resolve_fn = resolve_fn
-        .ok_or_else(|| syn::Error::new(Span::call_site(), "missing `async fn resolve`"))?;
-
-    if resolve_fn.sig.asyncness.is_none() {
-        return Err(syn::Error::new_spanned(
-            resolve_fn.sig.fn_token,
-            "`resolve` must be an async fn",
-        ));
-    }
-
-    let params: Vec<_> = resolve_fn.sig.inputs.iter().collect();
-    if params.len() != 2 {
-        return Err(syn::Error::new_spanned(
-            &resolve_fn.sig.inputs,
-            "`resolve` must take exactly two parameters: context and input references",
-        ));
-    }
-
-    let ctx_name = param_ident(params[0])?;
-    let input_name = param_ident(params[1])?;
-
-    let resolve_stmts = &resolve_fn.block.stmts;
+    let parts = resolve_parts(resolve_fn)?;
+    let FnParts {
+        ctx_name,
+        input_name,
+        stmts: resolve_stmts,
+        ..
+    } = parts;
+    let (extract_ty, extract_param) = parts.extract();
 
     let (error_ty_tokens, resolve_body) = match error_ty {
         Some(ty) => (quote! { #ty }, quote! { #(#resolve_stmts)* }),
@@ -112,22 +316,27 @@ fn expand(impl_block: ItemImpl) -> syn::Result<proc_macro2::TokenStream> {
             quote! { ::core::result::Result::Ok({ #(#resolve_stmts)* }) },
         ),
     };
+    let type_key = attrs.type_key(self_ty);
+    let version = attrs.version.map(|v| quote! { const VERSION: u32 = #v; });
 
     Ok(quote! {
         impl #impl_generics ::yew_link::LinkedState for #self_ty #ty_generics #where_clause {
             type Input = #input_ty;
             type Error = #error_ty_tokens;
-            const TYPE_KEY: &'static str =
-                ::core::concat!(::core::module_path!(), "::", ::core::stringify!(#self_ty));
+            const TYPE_KEY: &'static str = #type_key;
+            #version
+            #(#consts)*
         }
 
         #[cfg(not(target_arch = "wasm32"))]
         impl #impl_generics ::yew_link::LinkedStateResolve for #self_ty #ty_generics #where_clause {
             type Context = #context_ty;
+            type Extract = #extract_ty;
 
             async fn resolve<'__yew_link>(
                 #ctx_name: &'__yew_link Self::Context,
                 #input_name: &'__yew_link <Self as ::yew_link::LinkedState>::Input,
+                #extract_param,
             ) -> ::core::result::Result<Self, <Self as ::yew_link::LinkedState>::Error> {
                 #resolve_body
             }
@@ -135,6 +344,226 @@ fn expand(impl_block: ItemImpl) -> syn::Result<proc_macro2::TokenStream> {
     })
 }
 
+fn expand_action(
+    impl_block: ItemImpl,
+    attrs: LinkedAttrs,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let self_ty = &impl_block.self_ty;
+    let (impl_generics, ty_generics, where_clause) = impl_block.generics.split_for_impl();
+
+    let mut input_ty = None;
+    let mut output_ty = None;
+    let mut context_ty = None;
+    let mut error_ty: Option<&syn::Type> = None;
+    let mut resolve_fn: Option<&ImplItemFn> = None;
+
+    for item in &impl_block.items {
+        match item {
+            ImplItem::Type(t) if t.ident == "Input" => input_ty = Some(&t.ty),
+            ImplItem::Type(t) if t.ident == "Output" => output_ty = Some(&t.ty),
+            ImplItem::Type(t) if t.ident == "Context" => context_ty = Some(&t.ty),
+            ImplItem::Type(t) if t.ident == "Error" => error_ty = Some(&t.ty),
+            ImplItem::Fn(f) if f.sig.ident == "resolve" => resolve_fn = Some(f),
+            other => {
+                return Err(syn::Error::new_spanned(
+                    other,
+                    "#[linked_action] expects only `type Input`, `type Output`, `type Context`, \
+                     `type Error` (optional), and `async fn resolve`",
+                ));
+            }
+        }
+    }
+
+    let input_ty =
+        input_ty.ok_or_else(|| syn::Error::new(Span::call_site(), "missing `type Input`"))?;
+    let output_ty =
+        output_ty.ok_or_else(|| syn::Error::new(Span::call_site(), "missing `type Output`"))?;
+    let context_ty =
+        context_ty.ok_or_else(|| syn::Error::new(Span::call_site(), "missing `type Context`"))?;
+    let parts = resolve_parts(resolve_fn)?;
+    let FnParts {
+        ctx_name,
+        input_name,
+        stmts: resolve_stmts,
+        ..
+    } = parts;
+    let (extract_ty, extract_param) = parts.extract();
+
+    let (error_ty_tokens, resolve_body) = match error_ty {
+        Some(ty) => (quote! { #ty }, quote! { #(#resolve_stmts)* }),
+        None => (
+            quote! { ::yew_link::Never },
+            quote! { ::core::result::Result::Ok({ #(#resolve_stmts)* }) },
+        ),
+    };
+    let type_key = attrs.type_key(self_ty);
+    let version = attrs.version.map(|v| quote! { const VERSION: u32 = #v; });
+
+    Ok(quote! {
+        impl #impl_generics ::yew_link::LinkedAction for #self_ty #ty_generics #where_clause {
+            type Input = #input_ty;
+            type Output = #output_ty;
+            type Error = #error_ty_tokens;
+            const TYPE_KEY: &'static str = #type_key;
+            #version
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        impl #impl_generics ::yew_link::LinkedActionResolve for #self_ty #ty_generics #where_clause {
+            type Context = #context_ty;
+            type Extract = #extract_ty;
+
+            async fn resolve<'__yew_link>(
+                #ctx_name: &'__yew_link Self::Context,
+                #input_name: &'__yew_link <Self as ::yew_link::LinkedAction>::Input,
+                #extract_param,
+            ) -> ::core::result::Result<
+                <Self as ::yew_link::LinkedAction>::Output,
+                <Self as ::yew_link::LinkedAction>::Error,
+            > {
+                #resolve_body
+            }
+        }
+    })
+}
+
+fn expand_stream(
+    impl_block: ItemImpl,
+    attrs: LinkedAttrs,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let self_ty = &impl_block.self_ty;
+    let (impl_generics, ty_generics, where_clause) = impl_block.generics.split_for_impl();
+
+    let mut input_ty = None;
+    let mut context_ty = None;
+    let mut subscribe_fn: Option<&ImplItemFn> = None;
+
+    for item in &impl_block.items {
+        match item {
+            ImplItem::Type(t) if t.ident == "Input" => input_ty = Some(&t.ty),
+            ImplItem::Type(t) if t.ident == "Context" => context_ty = Some(&t.ty),
+            ImplItem::Fn(f) if f.sig.ident == "subscribe" => subscribe_fn = Some(f),
+            other => {
+                return Err(syn::Error::new_spanned(
+                    other,
+                    "#[linked_stream] expects only `type Input`, `type Context`, and `fn subscribe`",
+                ));
+            }
+        }
+    }
+
+    let input_ty =
+        input_ty.ok_or_else(|| syn::Error::new(Span::call_site(), "missing `type Input`"))?;
+    let context_ty =
+        context_ty.ok_or_else(|| syn::Error::new(Span::call_site(), "missing `type Context`"))?;
+    let subscribe_fn =
+        subscribe_fn.ok_or_else(|| syn::Error::new(Span::call_site(), "missing `fn subscribe`"))?;
+
+    if let Some(asyncness) = subscribe_fn.sig.asyncness {
+        return Err(syn::Error::new_spanned(
+            asyncness,
+            "`subscribe` must not be async; return a stream instead",
+        ));
+    }
+
+    let parts = fn_parts(subscribe_fn)?;
+    let FnParts {
+        ctx_name,
+        input_name,
+        stmts: subscribe_stmts,
+        ..
+    } = parts;
+    let (extract_ty, extract_param) = parts.extract();
+    let type_key = attrs.type_key(self_ty);
+
+    Ok(quote! {
+        impl #impl_generics ::yew_link::LinkedStream for #self_ty #ty_generics #where_clause {
+            type Input = #input_ty;
+            const TYPE_KEY: &'static str = #type_key;
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        impl #impl_generics ::yew_link::LinkedStreamResolve for #self_ty #ty_generics #where_clause {
+            type Context = #context_ty;
+            type Extract = #extract_ty;
+
+            fn subscribe(
+                #ctx_name: &Self::Context,
+                #input_name: &<Self as ::yew_link::LinkedStream>::Input,
+                #extract_param,
+            ) -> ::yew_link::__private::BoxStream<'static, Self> {
+                ::yew_link::__private::StreamExt::boxed({ #(#subscribe_stmts)* })
+            }
+        }
+    })
+}
+
+/// The parameters and body of a `resolve` or `subscribe` function.
+struct FnParts<'a> {
+    ctx_name: &'a Ident,
+    input_name: &'a Ident,
+    /// The optional third parameter, whose type becomes `type Extract`.
+    extract: Option<&'a PatType>,
+    stmts: &'a [syn::Stmt],
+}
+
+impl FnParts<'_> {
+    /// Returns the `type Extract` and the matching parameter of the generated function.
+    fn extract(&self) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+        match self.extract {
+            Some(PatType { pat, ty, .. }) => (quote! { #ty }, quote! { #pat: Self::Extract }),
+            None => (quote! { () }, quote! { _: Self::Extract }),
+        }
+    }
+}
+
+/// Validates the `resolve` function and returns its parameters and body.
+fn resolve_parts(resolve_fn: Option<&ImplItemFn>) -> syn::Result<FnParts<'_>> {
+    let resolve_fn = resolve_fn
+        .ok_or_else(|| syn::Error::new(Span::call_site(), "missing `async fn resolve`"))?;
+
+    if resolve_fn.sig.asyncness.is_none() {
+        return Err(syn::Error::new_spanned(
+            resolve_fn.sig.fn_token,
+            "`resolve` must be an async fn",
+        ));
+    }
+
+    fn_parts(resolve_fn)
+}
+
+fn fn_parts(f: &ImplItemFn) -> syn::Result<FnParts<'_>> {
+    let params: Vec<_> = f.sig.inputs.iter().collect();
+    if !matches!(params.len(), 2 | 3) {
+        return Err(syn::Error::new_spanned(
+            &f.sig.inputs,
+            format!(
+                "`{}` must take two parameters, context and input references, and optionally \
+                 a third one extracted from the request",
+                f.sig.ident
+            ),
+        ));
+    }
+
+    let extract = match params.get(2) {
+        Some(FnArg::Typed(pt)) => Some(pt),
+        Some(other) => {
+            return Err(syn::Error::new_spanned(
+                other,
+                "unexpected `self` parameter",
+            ));
+        }
+        None => None,
+    };
+
+    Ok(FnParts {
+        ctx_name: param_ident(params[0])?,
+        input_name: param_ident(params[1])?,
+        extract,
+        stmts: &f.block.stmts,
+    })
+}
+
 fn param_ident(arg: &FnArg) -> syn::Result<&Ident> {
     match arg {
         FnArg::Typed(PatType { pat, .. }) => match pat.as_ref() {
@@ -0,0 +1,229 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use yew::prelude::*;
+
+use crate::{LinkCache, LinkContextInner, LinkError};
+
+/// An operation that is executed on the server on behalf of the client.
+///
+/// Where a [`LinkedState`](crate::LinkedState) reads data, a `LinkedAction`
+/// changes it. Actions are dispatched through the same [`Resolver`](crate::Resolver)
+/// and endpoint as linked states. The actual handler is provided separately via
+/// [`Resolver::register_action`](crate::Resolver::register_action) or the
+/// [`#[linked_action]`](crate::linked_action) macro.
+pub trait LinkedAction: 'static {
+    /// The input sent by the client.
+    type Input: Serialize + DeserializeOwned + Clone + fmt::Debug + 'static;
+
+    /// The value returned by a successful run.
+    type Output: Serialize + DeserializeOwned + Clone + 'static;
+
+    /// Application-level error returned by a failed run.
+    type Error: Serialize + DeserializeOwned + Clone + fmt::Debug + fmt::Display + 'static;
+
+    /// Stable wire-format key used to route requests between client and server.
+    ///
+    /// Generated automatically by [`#[linked_action]`](crate::linked_action) as
+    /// `concat!(module_path!(), "::", stringify!(Type))`. It shares its namespace
+    /// with [`LinkedState::TYPE_KEY`](crate::LinkedState::TYPE_KEY).
+    const TYPE_KEY: &'static str;
+
+    /// The version of the wire format of this action.
+    ///
+    /// Like [`LinkedState::VERSION`](crate::LinkedState::VERSION), runs for
+    /// another version are answered with [`LinkError::VersionMismatch`] instead
+    /// of being executed. Bump it, for example with
+    /// `#[linked_action(version = 2)]`, when the input, output or error changes
+    /// in a way that clients built before cannot encode or decode. Defaults to
+    /// `0`.
+    const VERSION: u32 = 0;
+}
+
+/// Server-side extension of [`LinkedAction`] that provides the handler.
+///
+/// You normally don't implement this by hand — use the
+/// [`#[linked_action]`](crate::linked_action) attribute macro instead, which strips
+/// the server code from WASM bundles automatically.
+#[cfg(not(target_arch = "wasm32"))]
+pub trait LinkedActionResolve: LinkedAction {
+    type Context: Send + Sync + 'static;
+
+    /// Request-scoped data the handler needs, such as the current user.
+    ///
+    /// Use `()` if the handler does not depend on the request.
+    type Extract: crate::FromRequestContext;
+
+    fn resolve<'a>(
+        ctx: &'a Self::Context,
+        input: &'a Self::Input,
+        extract: Self::Extract,
+    ) -> impl Future<Output = Result<Self::Output, Self::Error>> + Send + 'a;
+}
+
+type ActionResult<A> =
+    Result<Rc<<A as LinkedAction>::Output>, LinkError<<A as LinkedAction>::Error>>;
+
+struct ActionState<A: LinkedAction> {
+    pending: usize,
+    result: Option<ActionResult<A>>,
+}
+
+/// Decrements the number of pending runs when a run completes or is dropped.
+struct PendingRun<A: LinkedAction> {
+    state: Rc<RefCell<ActionState<A>>>,
+    force_update: UseForceUpdateHandle,
+}
+
+impl<A: LinkedAction> Drop for PendingRun<A> {
+    fn drop(&mut self) {
+        self.state.borrow_mut().pending -= 1;
+        self.force_update.force_update();
+    }
+}
+
+/// Handle returned by [`use_linked_action`].
+///
+/// Runs the action with [`run`](Self::run) or [`run_with`](Self::run_with) and
+/// exposes the state of the latest run through [`is_pending`](Self::is_pending),
+/// [`data`](Self::data) and [`error`](Self::error).
+pub struct LinkedActionHandle<A: LinkedAction> {
+    link: LinkContextInner,
+    state: Rc<RefCell<ActionState<A>>>,
+    force_update: UseForceUpdateHandle,
+    pending: bool,
+    result: Option<ActionResult<A>>,
+}
+
+impl<A: LinkedAction> Clone for LinkedActionHandle<A> {
+    fn clone(&self) -> Self {
+        Self {
+            link: self.link.clone(),
+            state: self.state.clone(),
+            force_update: self.force_update.clone(),
+            pending: self.pending,
+            result: self.result.clone(),
+        }
+    }
+}
+
+impl<A: LinkedAction> LinkedActionHandle<A> {
+    /// Runs the action and returns its result.
+    ///
+    /// The component is re-rendered when the run starts and when it completes.
+    pub async fn run(&self, input: A::Input) -> ActionResult<A> {
+        self.run_with(input, |_, _| {}).await
+    }
+
+    /// Runs the action and calls `on_success` with its output if it succeeds.
+    ///
+    /// Use the provided [`LinkCache`] to invalidate or replace the linked states
+    /// the action changed:
+    ///
+    /// ```ignore
+    /// let create_post = use_linked_action::<CreatePost>();
+    ///
+    /// create_post
+    ///     .run_with(new_post, |post, cache| {
+    ///         cache.update::<Post>(post.id, post.clone());
+    ///         cache.invalidate::<PostList>(&());
+    ///     })
+    ///     .await;
+    /// ```
+    pub async fn run_with<F>(&self, input: A::Input, on_success: F) -> ActionResult<A>
+    where
+        F: FnOnce(&A::Output, &LinkCache),
+    {
+        self.state.borrow_mut().pending += 1;
+        self.force_update.force_update();
+        // Ends the run even if the future is dropped before it completes.
+        let _pending = PendingRun {
+            state: self.state.clone(),
+            force_update: self.force_update.clone(),
+        };
+
+        let result = self
+            .link
+            .request::<A::Output, A::Error>(A::TYPE_KEY, A::VERSION, &input)
+            .await
+            .map(Rc::new);
+
+        if let Ok(output) = &result {
+            on_success(
+                output,
+                &LinkCache {
+                    inner: self.link.clone(),
+                },
+            );
+        }
+
+        self.state.borrow_mut().result = Some(result.clone());
+
+        result
+    }
+
+    /// Returns `true` while a run started from this component is in progress.
+    pub fn is_pending(&self) -> bool {
+        self.pending
+    }
+
+    /// Returns the output of the latest completed run, if it succeeded.
+    pub fn data(&self) -> Option<Rc<A::Output>> {
+        self.result.as_ref()?.as_ref().ok().cloned()
+    }
+
+    /// Returns the error of the latest completed run, if it failed.
+    pub fn error(&self) -> Option<&LinkError<A::Error>> {
+        self.result.as_ref()?.as_ref().err()
+    }
+    /// Returns the number of runs in progress, which [`is_pending`](Self::is_pending) only
+    /// reflects once the component re-renders.
+    #[cfg(all(
+        test,
+        feature = "client",
+        not(feature = "ssr"),
+        not(target_arch = "wasm32")
+    ))]
+    pub(crate) fn pending_runs(&self) -> usize {
+        self.state.borrow().pending
+    }
+}
+
+/// Run a [`LinkedAction`] on the server.
+///
+/// On the client the action is sent to the provider's `endpoint` URL. During SSR
+/// it is executed by the [`Resolver`](crate::Resolver) of the ancestor
+/// [`LinkProvider`](crate::LinkProvider).
+///
+/// # Panics
+///
+/// Panics if there is no ancestor [`LinkProvider`](crate::LinkProvider) in the
+/// component tree.
+#[hook]
+pub fn use_linked_action<A: LinkedAction>() -> LinkedActionHandle<A> {
+    let link =
+        use_context::<LinkContextInner>().expect("use_linked_action requires a LinkProvider");
+    let state = use_ref(|| {
+        RefCell::new(ActionState::<A> {
+            pending: 0,
+            result: None,
+        })
+    });
+    let force_update = use_force_update();
+
+    let (pending, result) = {
+        let state = state.borrow();
+        (state.pending > 0, state.result.clone())
+    };
+
+    LinkedActionHandle {
+        link,
+        state,
+        force_update,
+        pending,
+        result,
+    }
+}
@@ -0,0 +1,214 @@
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::LinkError;
+
+/// The format linked states and actions are encoded in on the wire.
+///
+/// JSON is always available. The binary codecs produce smaller bodies and are
+/// faster to encode for large values such as tables or time series. They are
+/// enabled by the `bincode` and `postcard` features.
+///
+/// The client chooses a codec with the `codec` prop of
+/// [`LinkProvider`](crate::LinkProvider). The handlers accept every enabled
+/// codec and answer in the one named by the `Content-Type` of the request.
+/// Linked streams are always sent as JSON.
+///
+/// Binary codecs are not self-describing, so values sent with them must not
+/// use `#[serde(untagged)]`, `#[serde(flatten)]` or `serde_json::Value`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum LinkCodec {
+    /// `application/json`.
+    #[default]
+    Json,
+    /// `application/x-bincode`, using the standard bincode configuration.
+    #[cfg(feature = "bincode")]
+    Bincode,
+    /// `application/x-postcard`.
+    #[cfg(feature = "postcard")]
+    Postcard,
+}
+
+impl LinkCodec {
+    /// Returns the media type of bodies encoded with this codec.
+    pub const fn content_type(self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            #[cfg(feature = "bincode")]
+            Self::Bincode => "application/x-bincode",
+            #[cfg(feature = "postcard")]
+            Self::Postcard => "application/x-postcard",
+        }
+    }
+
+    /// Returns the enabled codec named by a `Content-Type` header value.
+    ///
+    /// Parameters such as `charset` are ignored, and every `+json` media type
+    /// is treated as JSON.
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let essence = content_type.split(';').next().unwrap_or_default().trim();
+        if essence.eq_ignore_ascii_case("application/json")
+            || essence
+                .rsplit_once('+')
+                .is_some_and(|(_, suffix)| suffix.eq_ignore_ascii_case("json"))
+        {
+            return Some(Self::Json);
+        }
+
+        #[cfg(feature = "bincode")]
+        if essence.eq_ignore_ascii_case(Self::Bincode.content_type()) {
+            return Some(Self::Bincode);
+        }
+
+        #[cfg(feature = "postcard")]
+        if essence.eq_ignore_ascii_case(Self::Postcard.content_type()) {
+            return Some(Self::Postcard);
+        }
+
+        None
+    }
+
+    pub(crate) fn encode_to_vec<T: Serialize + ?Sized>(self, value: &T) -> Result<Vec<u8>, String> {
+        match self {
+            Self::Json => serde_json::to_vec(value).map_err(|e| e.to_string()),
+            #[cfg(feature = "bincode")]
+            Self::Bincode => bincode::serde::encode_to_vec(value, bincode::config::standard())
+                .map_err(|e| e.to_string()),
+            #[cfg(feature = "postcard")]
+            Self::Postcard => postcard::to_allocvec(value).map_err(|e| e.to_string()),
+        }
+    }
+
+    pub(crate) fn decode_from_slice<T: DeserializeOwned>(self, bytes: &[u8]) -> Result<T, String> {
+        match self {
+            Self::Json => serde_json::from_slice(bytes).map_err(|e| e.to_string()),
+            #[cfg(feature = "bincode")]
+            Self::Bincode => bincode::serde::decode_from_slice(bytes, bincode::config::standard())
+                .map(|(value, _)| value)
+                .map_err(|e| e.to_string()),
+            #[cfg(feature = "postcard")]
+            Self::Postcard => postcard::from_bytes(bytes).map_err(|e| e.to_string()),
+        }
+    }
+
+    /// Encodes `value` for a resolver.
+    ///
+    /// JSON values are kept as a [`serde_json::Value`] so that they can be
+    /// embedded in a [`LinkRequest`](crate::LinkRequest) as they are.
+    pub(crate) fn encode<T: Serialize + ?Sized>(self, value: &T) -> Result<Encoded, String> {
+        if self == Self::Json {
+            serde_json::to_value(value)
+                .map(Encoded::Json)
+                .map_err(|e| e.to_string())
+        } else {
+            self.encode_to_vec(value).map(Encoded::Binary)
+        }
+    }
+
+    pub(crate) fn decode<T: DeserializeOwned>(self, encoded: Encoded) -> Result<T, String> {
+        match encoded {
+            Encoded::Json(val) => serde_json::from_value(val).map_err(|e| e.to_string()),
+            Encoded::Binary(bytes) => self.decode_from_slice(&bytes),
+        }
+    }
+
+    /// Decodes the reply to a request into the output or the error of the
+    /// resolve function.
+    pub(crate) fn decode_result<O, E>(self, reply: Reply) -> Result<O, LinkError<E>>
+    where
+        O: DeserializeOwned,
+        E: DeserializeOwned,
+    {
+        match reply {
+            Reply::Ok(val) => self.decode(val).map_err(LinkError::Internal),
+            Reply::Err(err_val) => {
+                let e: E = self.decode(err_val).map_err(LinkError::Internal)?;
+                Err(LinkError::Resolve(e))
+            }
+            Reply::VersionMismatch { client, server } => {
+                Err(LinkError::VersionMismatch { client, server })
+            }
+        }
+    }
+
+    /// Encodes an error message produced by the resolver itself rather than by
+    /// a resolve function.
+    pub(crate) fn message(self, message: String) -> Encoded {
+        self.encode(&message)
+            .expect("strings are always serializable")
+    }
+
+    /// Encodes an error message to the bytes of a response body.
+    pub(crate) fn message_bytes(self, message: String) -> Vec<u8> {
+        self.message(message)
+            .into_bytes()
+            .expect("strings are always serializable")
+    }
+}
+
+/// An input, output or error encoded with a [`LinkCodec`].
+#[derive(Clone, Debug)]
+pub(crate) enum Encoded {
+    Json(serde_json::Value),
+    Binary(Vec<u8>),
+}
+
+impl Encoded {
+    pub(crate) fn into_json(self) -> Result<serde_json::Value, String> {
+        match self {
+            Self::Json(val) => Ok(val),
+            Self::Binary(bytes) => serde_json::from_slice(&bytes).map_err(|e| e.to_string()),
+        }
+    }
+
+    pub(crate) fn into_bytes(self) -> Result<Vec<u8>, String> {
+        match self {
+            Self::Json(val) => serde_json::to_vec(&val).map_err(|e| e.to_string()),
+            Self::Binary(bytes) => Ok(bytes),
+        }
+    }
+}
+
+/// The reply of the endpoint to a single request.
+pub(crate) enum Reply {
+    Ok(Encoded),
+    Err(Encoded),
+    /// The request was made for another version of the linked state than the
+    /// one the server has.
+    VersionMismatch {
+        client: u32,
+        server: u32,
+    },
+}
+
+impl From<Result<Encoded, Encoded>> for Reply {
+    fn from(result: Result<Encoded, Encoded>) -> Self {
+        match result {
+            Ok(val) => Self::Ok(val),
+            Err(err_val) => Self::Err(err_val),
+        }
+    }
+}
+
+/// A request sent with a binary [`LinkCodec`].
+///
+/// Binary bodies are always a list of requests, as binary codecs cannot tell a
+/// single request from a batch. The input is encoded with the same codec as the
+/// body.
+#[doc(hidden)]
+#[derive(Serialize, serde::Deserialize)]
+pub struct BinaryLinkRequest {
+    pub(crate) type_key: String,
+    pub(crate) version: u32,
+    pub(crate) input: Vec<u8>,
+}
+
+/// The response to a [`BinaryLinkRequest`].
+#[doc(hidden)]
+#[derive(Serialize, serde::Deserialize)]
+pub enum BinaryLinkResponse {
+    Ok(Vec<u8>),
+    Error(Vec<u8>),
+    VersionMismatch { client: u32, server: u32 },
+}
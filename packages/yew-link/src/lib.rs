@@ -1,25 +1,30 @@
 use std::any::{Any, TypeId};
+#[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+use std::cell::Cell;
 use std::cell::RefCell;
 use std::collections::HashMap;
-#[cfg(target_arch = "wasm32")]
+#[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
 use std::collections::HashSet;
 use std::fmt;
 use std::hash::Hash;
-#[cfg(target_arch = "wasm32")]
+#[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
 use std::num::NonZeroUsize;
 use std::pin::Pin;
 use std::rc::Rc;
 use std::sync::Arc;
+use std::time::Duration;
 
-#[cfg(target_arch = "wasm32")]
+use futures::future;
+use futures::stream::{BoxStream, Stream, StreamExt};
+#[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
 use lru::LruCache;
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 use yew::prelude::*;
-#[cfg(target_arch = "wasm32")]
+#[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
 use yew::suspense::Suspension;
 use yew::suspense::SuspensionResult;
-pub use yew_link_macro::linked_state;
+pub use yew_link_macro::{linked_action, linked_state, linked_stream};
 
 /// A type that can be resolved on the server and transferred to the client.
 ///
@@ -36,10 +41,34 @@ pub trait LinkedState: Serialize + DeserializeOwned + Clone + 'static {
     /// Stable wire-format key used to route requests between client and server.
     ///
     /// Generated automatically by [`#[linked_state]`](linked_state) as
-    /// `concat!(module_path!(), "::", stringify!(Type))`. If you implement
-    /// `LinkedState` manually (e.g. for generic types), set this to a
+    /// `concat!(module_path!(), "::", stringify!(Type))`, unless set with
+    /// `#[linked_state(key = "...")]`. Pinning the key lets the type move
+    /// between modules without breaking clients built before the move. If you
+    /// implement `LinkedState` manually (e.g. for generic types), set this to a
     /// string that is identical across server and client builds.
     const TYPE_KEY: &'static str;
+
+    /// The version of the wire format of this state.
+    ///
+    /// The client sends it with every request, and the endpoint answers
+    /// requests for another version with [`LinkError::VersionMismatch`]
+    /// instead of resolving them. Bump it, for example with
+    /// `#[linked_state(version = 2)]`, when the state or its input changes in a
+    /// way that clients built before cannot decode. Defaults to `0`.
+    const VERSION: u32 = 0;
+
+    /// How long a fetched value is considered fresh on the client.
+    ///
+    /// When a component using this state mounts and the cached value is older
+    /// than this, the cached value is displayed while a fresh one is fetched in
+    /// the background. Defaults to `None`, meaning values never go stale on
+    /// their own.
+    const STALE_AFTER: Option<Duration> = None;
+
+    /// Tags used to invalidate cached values of this type together.
+    ///
+    /// See [`LinkCache::invalidate_tag`].
+    const TAGS: &'static [&'static str] = &[];
 }
 
 /// Server-side extension of [`LinkedState`] that provides a resolve function.
@@ -52,9 +81,15 @@ pub trait LinkedState: Serialize + DeserializeOwned + Clone + 'static {
 pub trait LinkedStateResolve: LinkedState {
     type Context: Send + Sync + 'static;
 
+    /// Request-scoped data the resolver needs, such as the current user.
+    ///
+    /// Use `()` if the resolver does not depend on the request.
+    type Extract: FromRequestContext;
+
     fn resolve<'a>(
         ctx: &'a Self::Context,
         input: &'a Self::Input,
+        extract: Self::Extract,
     ) -> impl Future<Output = Result<Self, Self::Error>> + Send + 'a;
 }
 
@@ -82,6 +117,10 @@ pub enum LinkError<E> {
     Resolve(E),
     /// Infrastructure failure (network, serialization, missing resolver).
     Internal(String),
+    /// The server has another [`LinkedState::VERSION`] of the state, or
+    /// [`LinkedAction::VERSION`] of the action, than the client. The client is outdated (or ahead of a server that has not been
+    /// deployed yet) and should usually reload the page.
+    VersionMismatch { client: u32, server: u32 },
 }
 
 impl<E: fmt::Display> fmt::Display for LinkError<E> {
@@ -89,20 +128,30 @@ impl<E: fmt::Display> fmt::Display for LinkError<E> {
         match self {
             Self::Resolve(e) => fmt::Display::fmt(e, f),
             Self::Internal(s) => f.write_str(s),
+            Self::VersionMismatch { client, server } => write!(
+                f,
+                "the client has version {client} of this state, but the server has version \
+                 {server}"
+            ),
         }
     }
 }
 
+/// Applies an optimistic change to the cached value of a linked state.
+type MutateCallback<T> = Callback<Box<dyn FnOnce(&mut T)>, OptimisticUpdate>;
+
 /// Handle returned by [`use_linked_state`].
 ///
 /// Provides access to the resolved data, a [`refresh`](Self::refresh)
 /// method that triggers a background re-fetch, and an
 /// [`is_refreshing`](Self::is_refreshing) method to check whether a refresh
-/// is in progress (stale-while-revalidate).
+/// is in progress (stale-while-revalidate), and a [`mutate`](Self::mutate)
+/// method for optimistic updates.
 #[derive(Clone)]
 pub struct LinkedStateHandle<T: LinkedState> {
     result: Result<Rc<T>, LinkError<T::Error>>,
     refresh: Callback<()>,
+    mutate: MutateCallback<T>,
     refreshing: bool,
 }
 
@@ -141,12 +190,58 @@ impl<T: LinkedState> LinkedStateHandle<T> {
     pub fn is_refreshing(&self) -> bool {
         self.refreshing
     }
+
+    /// Applies `f` to the cached value and re-renders every component using it,
+    /// without waiting for the server.
+    ///
+    /// The server remains the source of truth: the next refresh or
+    /// [`LinkCache::update`] replaces the optimistic value. If that refresh
+    /// fails to reach the server, the last value it confirmed is restored.
+    /// Call [`OptimisticUpdate::rollback`] to restore it by hand, for example
+    /// when the action that was supposed to make the change fails:
+    ///
+    /// ```ignore
+    /// let update = todos.mutate(|todos| todos.push(todo.clone()));
+    /// match add_todo.run(todo).await {
+    ///     Ok(_) => todos.refresh(),
+    ///     Err(_) => update.rollback(),
+    /// }
+    /// ```
+    ///
+    /// Does nothing if the cached value is an error, and on the server.
+    pub fn mutate(&self, f: impl FnOnce(&mut T) + 'static) -> OptimisticUpdate {
+        self.mutate.emit(Box::new(f))
+    }
+}
+
+/// An optimistic change made with [`LinkedStateHandle::mutate`].
+#[derive(Clone)]
+pub struct OptimisticUpdate {
+    rollback: Callback<()>,
+}
+
+impl OptimisticUpdate {
+    fn noop() -> Self {
+        Self {
+            rollback: Callback::from(|()| {}),
+        }
+    }
+
+    /// Restores the last value the server confirmed, undoing this and every
+    /// later optimistic change to the same linked state.
+    ///
+    /// Does nothing if a fresh value has been fetched since.
+    pub fn rollback(&self) {
+        self.rollback.emit(());
+    }
 }
 
 #[doc(hidden)]
 #[derive(Serialize, serde::Deserialize)]
 pub struct LinkRequest {
     type_key: String,
+    #[serde(default)]
+    version: u32,
     input: serde_json::Value,
 }
 
@@ -157,24 +252,117 @@ pub struct LinkResponse {
     ok: Option<serde_json::Value>,
     #[serde(default)]
     error: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version_mismatch: Option<VersionMismatch>,
+}
+
+impl From<Result<serde_json::Value, serde_json::Value>> for LinkResponse {
+    fn from(result: Result<serde_json::Value, serde_json::Value>) -> Self {
+        match result {
+            Ok(val) => Self {
+                ok: Some(val),
+                error: None,
+                version_mismatch: None,
+            },
+            Err(err_val) => Self {
+                ok: None,
+                error: Some(err_val),
+                version_mismatch: None,
+            },
+        }
+    }
+}
+
+impl From<VersionMismatch> for LinkResponse {
+    fn from(mismatch: VersionMismatch) -> Self {
+        Self {
+            ok: None,
+            error: None,
+            version_mismatch: Some(mismatch),
+        }
+    }
+}
+
+/// The versions of a linked state on both ends of a request that was not
+/// resolved because they differ.
+#[derive(Clone, Copy, Serialize, serde::Deserialize)]
+struct VersionMismatch {
+    client: u32,
+    server: u32,
+}
+
+/// Query string of a request to the linked-stream endpoint.
+///
+/// `EventSource` can only issue `GET` requests, so the input is sent as a JSON
+/// string in the query rather than in the body.
+#[doc(hidden)]
+#[derive(Serialize, serde::Deserialize)]
+pub struct LinkStreamQuery {
+    type_key: String,
+    input: String,
+}
+
+impl TryFrom<LinkStreamQuery> for LinkRequest {
+    type Error = String;
+
+    fn try_from(query: LinkStreamQuery) -> Result<Self, Self::Error> {
+        Ok(Self {
+            type_key: query.type_key,
+            version: 0,
+            input: serde_json::from_str(&query.input)
+                .map_err(|e| format!("failed to deserialize input: {e}"))?,
+        })
+    }
 }
 
-type ResolveBoxFuture =
-    Pin<Box<dyn Future<Output = Result<serde_json::Value, serde_json::Value>> + Send>>;
-type ResolverFn = Box<dyn Fn(serde_json::Value) -> ResolveBoxFuture + Send + Sync>;
+/// Body of a request to the linked-state endpoint.
+///
+/// Clients send a single [`LinkRequest`] or, when several states are requested
+/// in the same tick, an array of them. A batch is answered with an array of
+/// [`LinkResponse`]s in the same order.
+#[doc(hidden)]
+#[derive(Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum LinkRequestBody {
+    Batch(Vec<LinkRequest>),
+    Single(LinkRequest),
+}
 
-/// Registry of resolve functions, keyed by [`std::any::type_name`].
+type ResolveBoxFuture = Pin<Box<dyn Future<Output = Result<Encoded, Encoded>> + Send>>;
+type ResolverFn =
+    Box<dyn Fn(LinkCodec, Encoded, &RequestContext) -> ResolveBoxFuture + Send + Sync>;
+type SubscribeFn = Box<
+    dyn Fn(
+            serde_json::Value,
+            &RequestContext,
+        ) -> Result<BoxStream<'static, serde_json::Value>, String>
+        + Send
+        + Sync,
+>;
+
+struct Handler {
+    version: u32,
+    resolve: ResolverFn,
+}
+
+/// Registry of resolve functions, keyed by [`LinkedState::TYPE_KEY`].
 ///
 /// Constructed on the server and passed to [`LinkProvider`]. Also used by the
 /// axum handler when the `axum` feature is enabled.
+///
+/// Linked states, actions and streams share one namespace of keys. Registering
+/// two of them with the same key panics, as one would silently shadow the
+/// other.
 pub struct Resolver {
-    handlers: HashMap<&'static str, ResolverFn>,
+    handlers: HashMap<&'static str, Handler>,
+    streams: HashMap<&'static str, SubscribeFn>,
 }
 
 impl fmt::Debug for Resolver {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Resolver")
             .field("types", &self.handlers.keys().collect::<Vec<_>>())
+            .field("streams", &self.streams.keys().collect::<Vec<_>>())
             .finish()
     }
 }
@@ -183,57 +371,366 @@ impl Resolver {
     pub fn new() -> Self {
         Self {
             handlers: HashMap::new(),
+            streams: HashMap::new(),
         }
     }
 
     /// Register a resolver for `T`. The closure receives `T::Input` and returns
     /// a future that produces `Result<T, T::Error>`.
-    pub fn register<T, F, Fut>(mut self, f: F) -> Self
+    pub fn register<T, F, Fut>(self, f: F) -> Self
     where
         T: LinkedState + Send,
         T::Error: Send,
         F: Fn(T::Input) -> Fut + Send + Sync + 'static,
         Fut: Future<Output = Result<T, T::Error>> + Send + 'static,
     {
-        self.handlers.insert(
+        self.register_with::<T, (), _, _>(move |input, ()| f(input))
+    }
+
+    /// Register a resolver for `T` that depends on the request. The closure
+    /// additionally receives an `X` extracted from the [`RequestContext`].
+    pub fn register_with<T, X, F, Fut>(self, f: F) -> Self
+    where
+        T: LinkedState + Send,
+        T::Error: Send,
+        X: FromRequestContext,
+        F: Fn(T::Input, X) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, T::Error>> + Send + 'static,
+    {
+        self.insert_handler(T::TYPE_KEY, T::VERSION, f)
+    }
+
+    /// Register a handler for the action `A`. The closure receives `A::Input` and
+    /// returns a future that produces `Result<A::Output, A::Error>`.
+    pub fn register_action<A, F, Fut>(self, f: F) -> Self
+    where
+        A: LinkedAction,
+        A::Output: Send,
+        A::Error: Send,
+        F: Fn(A::Input) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<A::Output, A::Error>> + Send + 'static,
+    {
+        self.register_action_with::<A, (), _, _>(move |input, ()| f(input))
+    }
+
+    /// Register a handler for the action `A` that depends on the request. The
+    /// closure additionally receives an `X` extracted from the [`RequestContext`].
+    pub fn register_action_with<A, X, F, Fut>(self, f: F) -> Self
+    where
+        A: LinkedAction,
+        A::Output: Send,
+        A::Error: Send,
+        X: FromRequestContext,
+        F: Fn(A::Input, X) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<A::Output, A::Error>> + Send + 'static,
+    {
+        self.insert_handler(A::TYPE_KEY, A::VERSION, f)
+    }
+
+    /// Register a subscription for the stream `T`. The closure receives
+    /// `T::Input` and returns a stream of values that are pushed to the client.
+    pub fn register_stream<T, F, S>(self, f: F) -> Self
+    where
+        T: LinkedStream,
+        F: Fn(T::Input) -> S + Send + Sync + 'static,
+        S: Stream<Item = T> + Send + 'static,
+    {
+        self.register_stream_with::<T, (), _, _>(move |input, ()| f(input))
+    }
+
+    /// Register a subscription for the stream `T` that depends on the request.
+    /// The closure additionally receives an `X` extracted from the
+    /// [`RequestContext`].
+    pub fn register_stream_with<T, X, F, S>(mut self, f: F) -> Self
+    where
+        T: LinkedStream,
+        X: FromRequestContext,
+        F: Fn(T::Input, X) -> S + Send + Sync + 'static,
+        S: Stream<Item = T> + Send + 'static,
+    {
+        self.assert_unregistered(T::TYPE_KEY);
+        self.streams.insert(
             T::TYPE_KEY,
-            Box::new(move |input_json: serde_json::Value| {
-                let input: T::Input = match serde_json::from_value(input_json) {
+            Box::new(
+                move |input_json: serde_json::Value, request: &RequestContext| {
+                    let input: T::Input = serde_json::from_value(input_json)
+                        .map_err(|e| format!("failed to deserialize input: {e}"))?;
+                    Ok(f(input, X::from_request_context(request))
+                        .filter_map(|item| future::ready(serde_json::to_value(&item).ok()))
+                        .boxed())
+                },
+            ),
+        );
+        self
+    }
+
+    /// Panics if `type_key` is already taken.
+    fn assert_unregistered(&self, type_key: &str) {
+        assert!(
+            !self.handlers.contains_key(type_key) && !self.streams.contains_key(type_key),
+            "`{type_key}` is registered twice with the Resolver; give one of the types \
+             another key with `#[linked_state(key = \"...\")]`"
+        );
+    }
+
+    fn insert_handler<I, X, O, E, F, Fut>(
+        mut self,
+        type_key: &'static str,
+        version: u32,
+        f: F,
+    ) -> Self
+    where
+        I: DeserializeOwned,
+        X: FromRequestContext,
+        O: Serialize,
+        E: Serialize + fmt::Display,
+        F: Fn(I, X) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<O, E>> + Send + 'static,
+    {
+        self.assert_unregistered(type_key);
+        let resolve: ResolverFn = Box::new(
+            move |codec: LinkCodec, input: Encoded, request: &RequestContext| {
+                let input: I = match codec.decode(input) {
                     Ok(v) => v,
                     Err(e) => {
                         return Box::pin(async move {
-                            Err(serde_json::Value::String(format!(
-                                "failed to deserialize input: {e}"
-                            )))
+                            Err(codec.message(format!("failed to deserialize input: {e}")))
                         });
                     }
                 };
-                let fut = f(input);
+                let fut = f(input, X::from_request_context(request));
                 Box::pin(async move {
                     match fut.await {
-                        Ok(val) => serde_json::to_value(&val)
-                            .map_err(|e| serde_json::Value::String(e.to_string())),
-                        Err(e) => Err(serde_json::to_value(&e).unwrap_or_else(|ser_err| {
-                            serde_json::Value::String(format!(
-                                "{e}: (serialization failed: {ser_err})"
-                            ))
+                        Ok(val) => codec.encode(&val).map_err(|e| codec.message(e)),
+                        Err(e) => Err(codec.encode(&e).unwrap_or_else(|ser_err| {
+                            codec.message(format!("{e}: (serialization failed: {ser_err})"))
                         })),
                     }
                 })
-            }),
+            },
         );
+        self.handlers.insert(type_key, Handler { version, resolve });
         self
     }
 
-    /// Resolve a [`LinkRequest`].
+    /// Returns the version mismatch of a request made for `version` of
+    /// `type_key`, if any.
+    fn check_version(&self, type_key: &str, version: u32) -> Result<(), VersionMismatch> {
+        match self.handlers.get(type_key) {
+            Some(handler) if handler.version != version => Err(VersionMismatch {
+                client: version,
+                server: handler.version,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Resolve `input` for `type_key`, both encoded with `codec`.
+    async fn resolve_encoded(
+        &self,
+        codec: LinkCodec,
+        type_key: &str,
+        input: Encoded,
+        request: &RequestContext,
+    ) -> Result<Encoded, Encoded> {
+        let handler = self
+            .handlers
+            .get(type_key)
+            .ok_or_else(|| codec.message(format!("no resolver registered for {type_key}")))?;
+        (handler.resolve)(codec, input, request).await
+    }
+
+    /// Resolve a [`LinkRequest`] with an empty [`RequestContext`].
     pub async fn resolve_request(
         &self,
         req: &LinkRequest,
     ) -> Result<serde_json::Value, serde_json::Value> {
-        let handler = self.handlers.get(req.type_key.as_str()).ok_or_else(|| {
-            serde_json::Value::String(format!("no resolver registered for {}", req.type_key))
-        })?;
-        handler(req.input.clone()).await
+        self.resolve_request_with(req, &RequestContext::default())
+            .await
+    }
+
+    /// Resolve a [`LinkRequest`] made as part of `request`.
+    pub async fn resolve_request_with(
+        &self,
+        req: &LinkRequest,
+        request: &RequestContext,
+    ) -> Result<serde_json::Value, serde_json::Value> {
+        match self
+            .resolve_encoded(
+                LinkCodec::Json,
+                &req.type_key,
+                Encoded::Json(req.input.clone()),
+                request,
+            )
+            .await
+        {
+            Ok(val) => val.into_json().map_err(serde_json::Value::String),
+            Err(err_val) => Err(err_val
+                .into_json()
+                .unwrap_or_else(serde_json::Value::String)),
+        }
+    }
+
+    /// Resolve a [`BinaryLinkRequest`] encoded with `codec` and made as part of
+    /// `request`.
+    ///
+    /// The input and the result never pass through [`serde_json::Value`].
+    pub async fn resolve_binary_with(
+        &self,
+        codec: LinkCodec,
+        req: BinaryLinkRequest,
+        request: &RequestContext,
+    ) -> BinaryLinkResponse {
+        if let Err(VersionMismatch { client, server }) =
+            self.check_version(&req.type_key, req.version)
+        {
+            return BinaryLinkResponse::VersionMismatch { client, server };
+        }
+
+        match self
+            .resolve_encoded(codec, &req.type_key, Encoded::Binary(req.input), request)
+            .await
+        {
+            Ok(val) => match val.into_bytes() {
+                Ok(bytes) => BinaryLinkResponse::Ok(bytes),
+                Err(e) => BinaryLinkResponse::Error(codec.message_bytes(e)),
+            },
+            Err(err_val) => match err_val.into_bytes() {
+                Ok(bytes) => BinaryLinkResponse::Error(bytes),
+                Err(e) => BinaryLinkResponse::Error(codec.message_bytes(e)),
+            },
+        }
+    }
+
+    /// Resolve the body of a request to the linked-state endpoint, encoded with
+    /// `codec` and made as part of `request`.
+    ///
+    /// This is what the bundled handlers do after checking the HTTP request.
+    /// Requests made for another [`LinkedState::VERSION`] than the registered
+    /// one are not resolved, and answered with a version mismatch instead.
+    ///
+    /// Returns the status code and the body of the response, encoded with the
+    /// same codec: `400` if the body is malformed, `409` if a single JSON
+    /// request has a version mismatch, `422` if it failed and `200` otherwise.
+    /// Batches and binary bodies are always answered with `200`.
+    pub async fn resolve_body_with(
+        &self,
+        codec: LinkCodec,
+        body: &[u8],
+        request: &RequestContext,
+    ) -> (u16, Vec<u8>) {
+        if codec != LinkCodec::Json {
+            return match codec.decode_from_slice::<Vec<BinaryLinkRequest>>(body) {
+                Ok(reqs) => {
+                    let resps: Vec<BinaryLinkResponse> = future::join_all(
+                        reqs.into_iter()
+                            .map(|req| self.resolve_binary_with(codec, req, request)),
+                    )
+                    .await;
+                    (
+                        200,
+                        codec
+                            .encode_to_vec(&resps)
+                            .expect("link responses are always serializable"),
+                    )
+                }
+                Err(e) => (
+                    400,
+                    codec.message_bytes(format!("failed to deserialize request: {e}")),
+                ),
+            };
+        }
+
+        let (status, resp) = match serde_json::from_slice::<LinkRequestBody>(body) {
+            Ok(LinkRequestBody::Single(req)) => {
+                let resp = self.respond(&req, request).await;
+                let status = match (&resp.ok, &resp.version_mismatch) {
+                    (Some(_), _) => 200,
+                    (None, Some(_)) => 409,
+                    (None, None) => 422,
+                };
+                (status, serde_json::to_vec(&resp))
+            }
+            Ok(LinkRequestBody::Batch(reqs)) => {
+                let resps: Vec<LinkResponse> =
+                    future::join_all(reqs.iter().map(|req| self.respond(req, request))).await;
+                (200, serde_json::to_vec(&resps))
+            }
+            Err(e) => {
+                let result = Err(serde_json::Value::String(format!(
+                    "failed to deserialize request: {e}"
+                )));
+                (400, serde_json::to_vec(&LinkResponse::from(result)))
+            }
+        };
+
+        (
+            status,
+            resp.expect("link responses are always serializable"),
+        )
+    }
+
+    /// Resolve a [`LinkRequest`] made as part of `request` unless its version
+    /// does not match.
+    async fn respond(&self, req: &LinkRequest, request: &RequestContext) -> LinkResponse {
+        match self.check_version(&req.type_key, req.version) {
+            Ok(()) => self.resolve_request_with(req, request).await.into(),
+            Err(mismatch) => mismatch.into(),
+        }
+    }
+
+    /// Resolve a batch of [`LinkRequest`]s concurrently with an empty
+    /// [`RequestContext`].
+    ///
+    /// The results are returned in the same order as the requests.
+    pub async fn resolve_batch(
+        &self,
+        reqs: &[LinkRequest],
+    ) -> Vec<Result<serde_json::Value, serde_json::Value>> {
+        self.resolve_batch_with(reqs, &RequestContext::default())
+            .await
+    }
+
+    /// Resolve a batch of [`LinkRequest`]s made as part of `request` concurrently.
+    ///
+    /// The results are returned in the same order as the requests.
+    pub async fn resolve_batch_with(
+        &self,
+        reqs: &[LinkRequest],
+        request: &RequestContext,
+    ) -> Vec<Result<serde_json::Value, serde_json::Value>> {
+        futures::future::join_all(
+            reqs.iter()
+                .map(|req| self.resolve_request_with(req, request)),
+        )
+        .await
+    }
+
+    /// Subscribe to the stream requested by a [`LinkRequest`] with an empty
+    /// [`RequestContext`].
+    ///
+    /// Each item is serialized to JSON. Items that fail to serialize are skipped.
+    pub fn subscribe(
+        &self,
+        req: &LinkRequest,
+    ) -> Result<BoxStream<'static, serde_json::Value>, String> {
+        self.subscribe_with(req, &RequestContext::default())
+    }
+
+    /// Subscribe to the stream requested by a [`LinkRequest`] made as part of
+    /// `request`.
+    ///
+    /// Each item is serialized to JSON. Items that fail to serialize are skipped.
+    pub fn subscribe_with(
+        &self,
+        req: &LinkRequest,
+        request: &RequestContext,
+    ) -> Result<BoxStream<'static, serde_json::Value>, String> {
+        let subscribe = self
+            .streams
+            .get(req.type_key.as_str())
+            .ok_or_else(|| format!("no stream registered for {}", req.type_key))?;
+        subscribe(req.input.clone(), request)
     }
 }
 
@@ -255,9 +752,39 @@ impl Resolver {
         T::Error: Send,
     {
         let ctx = Arc::new(ctx);
-        self.register::<T, _, _>(move |input| {
+        self.register_with::<T, T::Extract, _, _>(move |input, extract| {
             let ctx = ctx.clone();
-            async move { T::resolve(&*ctx, &input).await }
+            async move { T::resolve(&*ctx, &input, extract).await }
+        })
+    }
+
+    /// Register a handler for the action `A` using its [`LinkedActionResolve`] impl.
+    ///
+    /// The `ctx` is wrapped in an [`Arc`] internally so clones are cheap.
+    pub fn register_linked_action<A>(self, ctx: A::Context) -> Self
+    where
+        A: LinkedActionResolve,
+        A::Input: Send,
+        A::Output: Send,
+        A::Error: Send,
+    {
+        let ctx = Arc::new(ctx);
+        self.register_action_with::<A, A::Extract, _, _>(move |input, extract| {
+            let ctx = ctx.clone();
+            async move { A::resolve(&*ctx, &input, extract).await }
+        })
+    }
+
+    /// Register a subscription for the stream `T` using its [`LinkedStreamResolve`] impl.
+    ///
+    /// The `ctx` is wrapped in an [`Arc`] internally so clones are cheap.
+    pub fn register_linked_stream<T>(self, ctx: T::Context) -> Self
+    where
+        T: LinkedStreamResolve,
+    {
+        let ctx = Arc::new(ctx);
+        self.register_stream_with::<T, T::Extract, _, _>(move |input, extract| {
+            T::subscribe(&ctx, &input, extract)
         })
     }
 }
@@ -285,14 +812,14 @@ impl PartialEq for CacheKey {
 
 impl Eq for CacheKey {}
 
-#[cfg(all(not(feature = "ssr"), target_arch = "wasm32"))]
+#[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
 fn eq_inputs<I: PartialEq + 'static>(a: &dyn Any, b: &dyn Any) -> bool {
     a.downcast_ref::<I>()
         .zip(b.downcast_ref::<I>())
         .is_some_and(|(a, b)| a == b)
 }
 
-#[cfg(all(not(feature = "ssr"), target_arch = "wasm32"))]
+#[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
 fn cache_key<T: LinkedState>(input: &T::Input) -> CacheKey {
     use std::hash::Hasher;
     let mut hasher = std::collections::hash_map::DefaultHasher::new();
@@ -305,81 +832,239 @@ fn cache_key<T: LinkedState>(input: &T::Input) -> CacheKey {
     }
 }
 
-#[cfg(target_arch = "wasm32")]
-type Cache = Rc<RefCell<LruCache<CacheKey, serde_json::Value>>>;
-#[cfg(not(target_arch = "wasm32"))]
+#[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+#[derive(Clone)]
+struct CacheEntry {
+    value: serde_json::Value,
+    /// [`now_ms`] at the time the value was fetched.
+    fetched_at: f64,
+    tags: &'static [&'static str],
+}
+
+#[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+impl CacheEntry {
+    fn new<T: LinkedState>(value: serde_json::Value) -> Self {
+        Self {
+            value,
+            fetched_at: now_ms(),
+            tags: T::TAGS,
+        }
+    }
+
+    fn is_older_than(&self, age: Duration) -> bool {
+        now_ms() - self.fetched_at > age.as_secs_f64() * 1000.0
+    }
+}
+
+/// Milliseconds since the Unix epoch.
+#[cfg(all(not(feature = "ssr"), target_arch = "wasm32"))]
+fn now_ms() -> f64 {
+    js_sys::Date::now()
+}
+
+#[cfg(all(not(feature = "ssr"), not(target_arch = "wasm32"), feature = "client"))]
+fn now_ms() -> f64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0.0, |elapsed| elapsed.as_secs_f64() * 1000.0)
+}
+
+#[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+type Cache = Rc<RefCell<LruCache<CacheKey, CacheEntry>>>;
+#[cfg(not(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client"))))]
 type Cache = Rc<RefCell<HashMap<CacheKey, serde_json::Value>>>;
 
-#[cfg(target_arch = "wasm32")]
+#[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
 type InFlight = Rc<RefCell<HashMap<CacheKey, Suspension>>>;
 
-#[cfg(target_arch = "wasm32")]
+#[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
 type Refreshing = Rc<RefCell<HashSet<CacheKey>>>;
 
+#[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+type Stale = Rc<RefCell<HashSet<CacheKey>>>;
+
+/// The last values confirmed by the server for keys that were changed
+/// optimistically.
+#[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+type Confirmed = Rc<RefCell<HashMap<CacheKey, CacheEntry>>>;
+
+#[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+type Pages = Rc<RefCell<HashMap<CacheKey, pages::PagesState>>>;
+
+/// The serialized failures of fetches that are not cached, kept until the
+/// component that waited for them renders.
+#[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+type Failed = Rc<RefCell<HashMap<CacheKey, serde_json::Value>>>;
+
+#[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+type BatchSender = futures::channel::oneshot::Sender<Result<Reply, String>>;
+
+#[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+type Batch = Rc<RefCell<Vec<(String, u32, Encoded, BatchSender)>>>;
+
 #[derive(Clone)]
 struct LinkContextInner {
     cache: Cache,
-    #[cfg(target_arch = "wasm32")]
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
     in_flight: InFlight,
-    #[cfg(target_arch = "wasm32")]
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
     refreshing: Refreshing,
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    stale: Stale,
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    confirmed: Confirmed,
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    pages: Pages,
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    failed: Failed,
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    persistence: Option<LinkPersistence>,
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    batch: Batch,
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    transport: Option<Rc<dyn LinkTransport>>,
+    batching: bool,
+    codec: LinkCodec,
     endpoint: AttrValue,
+    stream_endpoint: AttrValue,
     #[cfg(feature = "ssr")]
     resolver: Option<Arc<Resolver>>,
+    #[cfg(feature = "ssr")]
+    request: RequestContext,
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    generation: Rc<Cell<u64>>,
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    version: u64,
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    provider_update: UseForceUpdateHandle,
 }
 
 impl PartialEq for LinkContextInner {
     fn eq(&self, other: &Self) -> bool {
-        Rc::ptr_eq(&self.cache, &other.cache) && self.endpoint == other.endpoint && {
-            #[cfg(target_arch = "wasm32")]
-            {
-                Rc::ptr_eq(&self.in_flight, &other.in_flight)
-                    && Rc::ptr_eq(&self.refreshing, &other.refreshing)
-            }
-            #[cfg(not(target_arch = "wasm32"))]
-            {
-                true
+        Rc::ptr_eq(&self.cache, &other.cache)
+            && self.endpoint == other.endpoint
+            && self.stream_endpoint == other.stream_endpoint
+            && self.batching == other.batching
+            && self.codec == other.codec
+            && {
+                #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+                {
+                    self.version == other.version
+                        && Rc::ptr_eq(&self.in_flight, &other.in_flight)
+                        && Rc::ptr_eq(&self.refreshing, &other.refreshing)
+                        && Rc::ptr_eq(&self.stale, &other.stale)
+                        && Rc::ptr_eq(&self.confirmed, &other.confirmed)
+                        && Rc::ptr_eq(&self.pages, &other.pages)
+                        && Rc::ptr_eq(&self.failed, &other.failed)
+                        && self.persistence == other.persistence
+                }
+                #[cfg(not(all(
+                    not(feature = "ssr"),
+                    any(target_arch = "wasm32", feature = "client")
+                )))]
+                {
+                    true
+                }
             }
-        }
     }
 }
 
 impl LinkContextInner {
-    #[cfg(all(not(feature = "ssr"), target_arch = "wasm32"))]
+    /// Re-renders every component that consumes this context.
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    fn notify(&self) {
+        self.generation.set(self.generation.get().wrapping_add(1));
+        self.provider_update.force_update();
+    }
+
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
     async fn fetch_remote<T: LinkedState>(
         &self,
         input: &T::Input,
     ) -> Result<T, LinkError<T::Error>> {
-        use gloo_net::http::Request;
-
-        let req_body = LinkRequest {
-            type_key: T::TYPE_KEY.to_string(),
-            input: serde_json::to_value(input).map_err(|e| LinkError::Internal(e.to_string()))?,
-        };
-        let resp = Request::post(self.endpoint.as_ref())
-            .json(&req_body)
-            .map_err(|e| LinkError::Internal(e.to_string()))?
-            .send()
+        self.request_remote::<T, T::Error>(T::TYPE_KEY, T::VERSION, input)
             .await
-            .map_err(|e| LinkError::Internal(e.to_string()))?;
+    }
 
-        let link_resp: LinkResponse = resp
-            .json()
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    async fn request_remote<O, E>(
+        &self,
+        type_key: &str,
+        version: u32,
+        input: &impl Serialize,
+    ) -> Result<O, LinkError<E>>
+    where
+        O: DeserializeOwned,
+        E: DeserializeOwned,
+    {
+        let input = self.codec.encode(input).map_err(LinkError::Internal)?;
+        let transport = self.transport.as_deref().ok_or_else(|| {
+            LinkError::Internal("the LinkProvider has no transport on this target".into())
+        })?;
+
+        let result = if self.batching {
+            let (tx, rx) = futures::channel::oneshot::channel();
+            let schedule_flush = {
+                let mut batch = self.batch.borrow_mut();
+                batch.push((type_key.to_string(), version, input, tx));
+                batch.len() == 1
+            };
+
+            if schedule_flush {
+                let link_ctx = self.clone();
+                yew::platform::spawn_local(async move {
+                    // Let the current tick finish so that every request issued in it
+                    // ends up in the same batch.
+                    yew::platform::time::sleep(std::time::Duration::ZERO).await;
+                    link_ctx.flush_batch().await;
+                });
+            }
+
+            rx.await
+                .map_err(|_| LinkError::Internal("batched request was cancelled".into()))?
+                .map_err(LinkError::Internal)?
+        } else {
+            transport::send(
+                transport,
+                &self.endpoint,
+                self.codec,
+                vec![(type_key.to_string(), version, input)],
+            )
             .await
-            .map_err(|e| LinkError::Internal(e.to_string()))?;
+            .map_err(LinkError::Internal)?
+            .remove(0)
+        };
+
+        self.codec.decode_result(result)
+    }
+
+    /// Sends every queued request in one round trip and hands each result to
+    /// its waiting request.
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    async fn flush_batch(&self) {
+        let (reqs, senders): (Vec<_>, Vec<_>) = std::mem::take(&mut *self.batch.borrow_mut())
+            .into_iter()
+            .map(|(type_key, version, input, tx)| ((type_key, version, input), tx))
+            .unzip();
+
+        if reqs.is_empty() {
+            return;
+        }
+        let Some(transport) = self.transport.as_deref() else {
+            return;
+        };
 
-        match link_resp.ok {
-            Some(val) => {
-                serde_json::from_value(val).map_err(|e| LinkError::Internal(e.to_string()))
+        match transport::send(transport, &self.endpoint, self.codec, reqs).await {
+            Ok(results) => {
+                for (tx, result) in senders.into_iter().zip(results) {
+                    let _ = tx.send(Ok(result));
+                }
             }
-            None => match link_resp.error {
-                Some(err_val) => {
-                    let e: T::Error = serde_json::from_value(err_val)
-                        .map_err(|e| LinkError::Internal(e.to_string()))?;
-                    Err(LinkError::Resolve(e))
+            Err(e) => {
+                for tx in senders {
+                    let _ = tx.send(Err(e.clone()));
                 }
-                None => Err(LinkError::Internal("unknown error".into())),
-            },
+            }
         }
     }
 
@@ -388,23 +1073,383 @@ impl LinkContextInner {
         &self,
         input: &T::Input,
     ) -> Result<T, LinkError<T::Error>> {
+        self.request_local::<T, T::Error>(T::TYPE_KEY, input).await
+    }
+
+    #[cfg(feature = "ssr")]
+    async fn request_local<O, E>(
+        &self,
+        type_key: &str,
+        input: &impl Serialize,
+    ) -> Result<O, LinkError<E>>
+    where
+        O: DeserializeOwned,
+        E: DeserializeOwned,
+    {
         let resolver = self
             .resolver
             .as_ref()
             .expect("resolver not set on server-side LinkProvider");
-        let req = LinkRequest {
-            type_key: T::TYPE_KEY.to_string(),
-            input: serde_json::to_value(input).map_err(|e| LinkError::Internal(e.to_string()))?,
+        let input = self.codec.encode(input).map_err(LinkError::Internal)?;
+        let result = resolver
+            .resolve_encoded(self.codec, type_key, input, &self.request)
+            .await;
+        self.codec.decode_result(result.into())
+    }
+
+    /// Sends a request for `version` of `type_key` to the server, or resolves it locally
+    /// during SSR.
+    async fn request<O, E>(
+        &self,
+        type_key: &str,
+        version: u32,
+        input: &impl Serialize,
+    ) -> Result<O, LinkError<E>>
+    where
+        O: DeserializeOwned,
+        E: DeserializeOwned,
+    {
+        #[cfg(feature = "ssr")]
+        {
+            // The server always has the version it resolves.
+            let _ = version;
+            self.request_local(type_key, input).await
+        }
+
+        #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+        {
+            self.request_remote(type_key, version, input).await
+        }
+
+        #[cfg(all(
+            not(feature = "ssr"),
+            not(any(target_arch = "wasm32", feature = "client"))
+        ))]
+        {
+            let _ = (type_key, version, input);
+            Err(LinkError::Internal(
+                "yew-link requires the `ssr` feature (server), or a wasm32 target or the `client` feature (client)".into(),
+            ))
+        }
+    }
+
+    /// Fetches `key` and caches the result.
+    ///
+    /// The returned suspension is shared with every component that requests
+    /// the same state before the fetch completes.
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    fn spawn_fetch<T: LinkedState>(&self, key: CacheKey, input: T::Input) -> Suspension {
+        let sus = Suspension::from_future({
+            let link_ctx = self.clone();
+            let key = key.clone();
+            async move {
+                let result: Result<T, LinkError<T::Error>> =
+                    link_ctx.fetch_remote::<T>(&input).await;
+
+                link_ctx.in_flight.borrow_mut().remove(&key);
+                link_ctx.store::<T>(key, &result);
+            }
+        });
+
+        self.in_flight.borrow_mut().insert(key, sus.clone());
+        sus
+    }
+
+    /// Caches the result of a fetch.
+    ///
+    /// Results that did not come from the server are not cached, and restore
+    /// the value it last confirmed if `key` was changed optimistically. If
+    /// nothing is cached for `key`, they are kept in [`Failed`] for the
+    /// component waiting for the fetch, which would otherwise fetch again.
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    fn store<T: LinkedState>(&self, key: CacheKey, result: &Result<T, LinkError<T::Error>>) {
+        let confirmed = self.confirmed.borrow_mut().remove(&key);
+        match result {
+            Err(LinkError::Internal(_) | LinkError::VersionMismatch { .. }) => {
+                if let Some(entry) = confirmed {
+                    self.cache.borrow_mut().put(key, entry);
+                } else if !self.cache.borrow().contains(&key) {
+                    if let Ok(json_val) = serde_json::to_value(result) {
+                        self.failed.borrow_mut().insert(key, json_val);
+                    }
+                }
+            }
+            Ok(_) | Err(LinkError::Resolve(_)) => {
+                self.failed.borrow_mut().remove(&key);
+                if let Ok(json_val) = serde_json::to_value(result) {
+                    let entry = CacheEntry::new::<T>(json_val);
+                    if let Some(input) = key.input.downcast_ref::<T::Input>() {
+                        self.persist::<T>(input, &entry);
+                    }
+                    self.cache.borrow_mut().put(key, entry);
+                }
+            }
+        }
+    }
+
+    /// Writes `entry` to the persistent storage, if there is one.
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    fn persist<T: LinkedState>(&self, input: &T::Input, entry: &CacheEntry) {
+        if let Some(persistence) = &self.persistence {
+            let capacity = self.cache.borrow().cap().get();
+            persistence.save::<T>(input, &entry.value, entry.fetched_at, capacity);
+        }
+    }
+
+    /// Seeds the cache with the value of `key` persisted by an earlier session,
+    /// if it is not cached yet. The value is marked as stale so that it is
+    /// revalidated against the server.
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    fn restore<T: LinkedState>(&self, key: &CacheKey, input: &T::Input) {
+        let Some(persistence) = &self.persistence else {
+            return;
+        };
+        if self.cache.borrow().contains(key) {
+            return;
+        }
+        let Some((value, fetched_at)) = persistence.load::<T>(input) else {
+            return;
+        };
+
+        self.cache.borrow_mut().put(
+            key.clone(),
+            CacheEntry {
+                value,
+                fetched_at,
+                tags: T::TAGS,
+            },
+        );
+        self.stale.borrow_mut().insert(key.clone());
+    }
+
+    /// Applies `f` to the cached value of `key`, remembering the value it
+    /// replaces until the server confirms or rejects the change.
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    fn mutate<T: LinkedState>(
+        &self,
+        key: CacheKey,
+        f: Box<dyn FnOnce(&mut T)>,
+    ) -> OptimisticUpdate {
+        let Some(entry) = self.cache.borrow_mut().get(&key).cloned() else {
+            return OptimisticUpdate::noop();
         };
-        match resolver.resolve_request(&req).await {
-            Ok(val) => serde_json::from_value(val).map_err(|e| LinkError::Internal(e.to_string())),
-            Err(err_val) => {
-                let e: T::Error = serde_json::from_value(err_val)
-                    .map_err(|e| LinkError::Internal(e.to_string()))?;
-                Err(LinkError::Resolve(e))
+        let Ok(Ok(mut value)) =
+            serde_json::from_value::<Result<T, LinkError<T::Error>>>(entry.value.clone())
+        else {
+            return OptimisticUpdate::noop();
+        };
+
+        f(&mut value);
+
+        let result: Result<T, LinkError<T::Error>> = Ok(value);
+        let Ok(json_val) = serde_json::to_value(&result) else {
+            return OptimisticUpdate::noop();
+        };
+        self.confirmed
+            .borrow_mut()
+            .entry(key.clone())
+            .or_insert_with(|| entry.clone());
+        self.cache.borrow_mut().put(
+            key.clone(),
+            CacheEntry {
+                value: json_val,
+                ..entry
+            },
+        );
+        self.notify();
+
+        let link_ctx = self.clone();
+        OptimisticUpdate {
+            rollback: Callback::from(move |()| {
+                let confirmed = link_ctx.confirmed.borrow_mut().remove(&key);
+                if let Some(entry) = confirmed {
+                    link_ctx.cache.borrow_mut().put(key.clone(), entry);
+                    link_ctx.notify();
+                }
+            }),
+        }
+    }
+
+    /// Warms the cache for `input` unless it already holds a fresh value or a
+    /// request for it is in flight.
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    fn prefetch<T: LinkedState>(&self, input: T::Input) {
+        let key = cache_key::<T>(&input);
+        self.restore::<T>(&key, &input);
+
+        let in_flight = self
+            .in_flight
+            .borrow()
+            .get(&key)
+            .is_some_and(|sus| !sus.resumed());
+        if in_flight || self.refreshing.borrow().contains(&key) {
+            return;
+        }
+
+        let expired = self
+            .cache
+            .borrow()
+            .peek(&key)
+            .map(|entry| T::STALE_AFTER.is_some_and(|age| entry.is_older_than(age)));
+        match expired {
+            None => {
+                self.spawn_fetch::<T>(key, input);
+            }
+            Some(expired) => {
+                if expired || self.stale.borrow().contains(&key) {
+                    self.spawn_refresh::<T>(key, input);
+                }
             }
         }
     }
+
+    /// Re-fetches `key` in the background while consumers keep displaying the cached value.
+    ///
+    /// All consumers are re-rendered once when the refresh starts and once when it completes.
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    fn spawn_refresh<T: LinkedState>(&self, key: CacheKey, input: T::Input) {
+        self.stale.borrow_mut().remove(&key);
+        if !self.refreshing.borrow_mut().insert(key.clone()) {
+            return;
+        }
+
+        let link_ctx = self.clone();
+        yew::platform::spawn_local(async move {
+            let result: Result<T, LinkError<T::Error>> = link_ctx.fetch_remote::<T>(&input).await;
+
+            link_ctx.refreshing.borrow_mut().remove(&key);
+            link_ctx.store::<T>(key, &result);
+
+            link_ctx.notify();
+        });
+    }
+}
+
+/// Handle to the linked-state cache of the nearest [`LinkProvider`].
+///
+/// Passed to the `on_success` closure of
+/// [`LinkedActionHandle::run_with`] so that an action can invalidate or replace the linked
+/// states it changed. All methods are no-ops on the server.
+#[derive(Clone)]
+pub struct LinkCache {
+    inner: LinkContextInner,
+}
+
+impl fmt::Debug for LinkCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LinkCache")
+            .field("endpoint", &self.inner.endpoint)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for LinkCache {
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl LinkCache {
+    /// Marks the cached `T` for `input` as stale.
+    ///
+    /// Mounted components using this state keep displaying the stale value and re-fetch it in
+    /// the background (see [`LinkedStateHandle::is_refreshing`]). Components mounted later
+    /// re-fetch it as well.
+    pub fn invalidate<T: LinkedState>(&self, input: &T::Input) {
+        #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+        {
+            let key = cache_key::<T>(input);
+            if self.inner.cache.borrow().contains(&key) {
+                self.inner.stale.borrow_mut().insert(key);
+                self.inner.notify();
+            }
+        }
+
+        #[cfg(not(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client"))))]
+        {
+            let _ = input;
+        }
+    }
+
+    /// Replaces the cached `T` for `input` with `value` and re-renders every component using it.
+    pub fn update<T: LinkedState>(&self, input: T::Input, value: T) {
+        #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+        {
+            let key = cache_key::<T>(&input);
+            let result: Result<T, LinkError<T::Error>> = Ok(value);
+            if let Ok(json_val) = serde_json::to_value(&result) {
+                self.inner.stale.borrow_mut().remove(&key);
+                self.inner.confirmed.borrow_mut().remove(&key);
+                let entry = CacheEntry::new::<T>(json_val);
+                self.inner.persist::<T>(&input, &entry);
+                self.inner.cache.borrow_mut().put(key, entry);
+                self.inner.notify();
+            }
+        }
+
+        #[cfg(not(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client"))))]
+        {
+            let _ = (input, value);
+        }
+    }
+
+    /// Marks every cached value whose type lists `tag` in
+    /// [`LinkedState::TAGS`] as stale.
+    ///
+    /// See [`invalidate`](Self::invalidate).
+    pub fn invalidate_tag(&self, tag: &str) {
+        #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+        self.invalidate_where(|entry| entry.tags.contains(&tag));
+
+        #[cfg(not(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client"))))]
+        {
+            let _ = tag;
+        }
+    }
+
+    /// Marks every cached value as stale.
+    ///
+    /// See [`invalidate`](Self::invalidate).
+    pub fn invalidate_all(&self) {
+        #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+        self.invalidate_where(|_| true);
+    }
+
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    fn invalidate_where(&self, predicate: impl Fn(&CacheEntry) -> bool) {
+        let keys: Vec<CacheKey> = self
+            .inner
+            .cache
+            .borrow()
+            .iter()
+            .filter(|(_, entry)| predicate(entry))
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        if !keys.is_empty() {
+            self.inner.stale.borrow_mut().extend(keys);
+            self.inner.notify();
+        }
+    }
+}
+
+/// Returns a [`LinkCache`] that invalidates linked states of the nearest
+/// [`LinkProvider`].
+///
+/// ```ignore
+/// let invalidator = use_link_invalidator();
+/// let onclick = Callback::from(move |_| invalidator.invalidate_tag("posts"));
+/// ```
+///
+/// # Panics
+///
+/// Panics if there is no ancestor [`LinkProvider`] in the component tree.
+#[hook]
+pub fn use_link_invalidator() -> LinkCache {
+    let inner =
+        use_context::<LinkContextInner>().expect("use_link_invalidator requires a LinkProvider");
+
+    LinkCache { inner }
 }
 
 /// Wrapper so [`Resolver`] can be passed as a component prop.
@@ -439,43 +1484,166 @@ pub struct LinkProviderProps {
     /// Remote endpoint URL used by the client to fetch linked states.
     #[prop_or_default]
     pub endpoint: AttrValue,
+    /// Remote endpoint URL used by the client to subscribe to linked streams.
+    ///
+    /// Defaults to `{endpoint}/stream`.
+    #[prop_or_default]
+    pub stream_endpoint: AttrValue,
+    /// How the client sends requests to the `endpoint`. Defaults to the
+    /// browser's `fetch` on wasm32 targets, and must be set by native clients.
+    /// Ignored on the server.
+    #[prop_or_default]
+    pub transport: Option<TransportProp>,
     /// Server-side resolver. Ignored on wasm32 targets.
     #[prop_or_default]
     pub resolver: Option<ResolverProp>,
+    /// The request being rendered, passed to the resolvers during SSR. Ignored
+    /// on wasm32 targets.
+    #[prop_or_default]
+    pub request: RequestContext,
     /// Maximum number of entries in the linked-state cache. Defaults to 64.
     #[prop_or(64)]
     pub cache_capacity: usize,
+    /// Whether requests issued in the same tick are sent to the `endpoint` in a
+    /// single round trip. Defaults to `true`.
+    ///
+    /// Disable this if the endpoint is served by a handler that only accepts
+    /// single requests.
+    #[prop_or(true)]
+    pub batching: bool,
+    /// The format requests to the `endpoint` are encoded in. Defaults to
+    /// [`LinkCodec::Json`].
+    ///
+    /// During SSR the resolvers are called with the same codec, so a state that
+    /// cannot be encoded with it fails on both paths.
+    #[prop_or_default]
+    pub codec: LinkCodec,
+    /// Where linked states are persisted across reloads. Defaults to `None`,
+    /// meaning the cache only lives in memory. Ignored on the server.
+    #[prop_or_default]
+    pub persistence: Option<LinkPersistence>,
+    /// Whether cached linked states are re-fetched in the background when the
+    /// window regains focus. Defaults to `false`.
+    #[prop_or_default]
+    pub revalidate_on_focus: bool,
+    /// Whether cached linked states are re-fetched in the background when the
+    /// browser goes back online. Defaults to `false`.
+    #[prop_or_default]
+    pub revalidate_on_reconnect: bool,
 }
 
 /// Provides linked-state resolution context to descendant components.
 ///
 /// On the server, pass a [`ResolverProp`] so that [`use_linked_state`] can
 /// resolve states locally. On the client, pass an `endpoint` URL.
+///
+/// With the `client` feature, the client side also works on native targets,
+/// for example in tests rendered with yew's `ServerRenderer`. Native clients
+/// are never hydrated and must pass a [`LinkTransport`], such as a
+/// [`ResolverTransport`]. Linked streams are only received on wasm32 targets.
 #[component]
 pub fn LinkProvider(props: &LinkProviderProps) -> Html {
-    #[cfg(target_arch = "wasm32")]
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
     let cache: Cache = {
         let cap = NonZeroUsize::new(props.cache_capacity).unwrap_or(NonZeroUsize::MIN);
         (*use_ref(|| Rc::new(RefCell::new(LruCache::new(cap))))).clone()
     };
-    #[cfg(not(target_arch = "wasm32"))]
+    #[cfg(not(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client"))))]
     let cache: Cache = (*use_ref(|| Rc::new(RefCell::new(HashMap::new())))).clone();
-    #[cfg(target_arch = "wasm32")]
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
     let in_flight: InFlight = (*use_ref(|| Rc::new(RefCell::new(HashMap::new())))).clone();
-    #[cfg(target_arch = "wasm32")]
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
     let refreshing: Refreshing = (*use_ref(|| Rc::new(RefCell::new(HashSet::new())))).clone();
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    let stale: Stale = (*use_ref(|| Rc::new(RefCell::new(HashSet::new())))).clone();
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    let confirmed: Confirmed = (*use_ref(|| Rc::new(RefCell::new(HashMap::new())))).clone();
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    let pages: Pages = (*use_ref(|| Rc::new(RefCell::new(HashMap::new())))).clone();
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    let failed: Failed = (*use_ref(|| Rc::new(RefCell::new(HashMap::new())))).clone();
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    let batch: Batch = (*use_ref(|| Rc::new(RefCell::new(Vec::new())))).clone();
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    let generation: Rc<Cell<u64>> = (*use_ref(|| Rc::new(Cell::new(0)))).clone();
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    let provider_update = use_force_update();
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    let _ = use_memo(props.persistence.clone(), |persistence| {
+        if let Some(persistence) = persistence {
+            persistence.migrate();
+        }
+    });
 
     let ctx = LinkContextInner {
         cache,
-        #[cfg(target_arch = "wasm32")]
+        #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
         in_flight,
-        #[cfg(target_arch = "wasm32")]
+        #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
         refreshing,
+        #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+        stale,
+        #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+        confirmed,
+        #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+        pages,
+        #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+        failed,
+        #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+        persistence: props.persistence.clone(),
+        #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+        batch,
+        #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+        transport: props
+            .transport
+            .as_ref()
+            .map(|transport| Rc::clone(&transport.0))
+            .or_else(transport::default_transport),
+        batching: props.batching,
+        codec: props.codec,
         endpoint: props.endpoint.clone(),
+        stream_endpoint: if props.stream_endpoint.is_empty() {
+            format!("{}/stream", props.endpoint.trim_end_matches('/')).into()
+        } else {
+            props.stream_endpoint.clone()
+        },
         #[cfg(feature = "ssr")]
         resolver: props.resolver.as_ref().map(|r| Arc::clone(&r.0)),
+        #[cfg(feature = "ssr")]
+        request: props.request.clone(),
+        #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+        version: generation.get(),
+        #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+        generation,
+        #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+        provider_update,
     };
 
+    #[cfg(all(not(feature = "ssr"), target_arch = "wasm32"))]
+    {
+        let link_cache = LinkCache { inner: ctx.clone() };
+        use_effect_with(
+            (props.revalidate_on_focus, props.revalidate_on_reconnect),
+            move |&(on_focus, on_reconnect)| {
+                use gloo_events::EventListener;
+
+                let window = web_sys::window().expect("no window available");
+                let listeners: Vec<EventListener> = [(on_focus, "focus"), (on_reconnect, "online")]
+                    .into_iter()
+                    .filter(|(enabled, _)| *enabled)
+                    .map(|(_, event_type)| {
+                        let link_cache = link_cache.clone();
+                        EventListener::new(&window, event_type, move |_| {
+                            link_cache.invalidate_all()
+                        })
+                    })
+                    .collect();
+
+                move || drop(listeners)
+            },
+        );
+    }
+
     html! {
         <ContextProvider<LinkContextInner> context={ctx}>
             { for props.children.iter() }
@@ -497,7 +1665,9 @@ pub fn LinkProvider(props: &LinkProviderProps) -> Html {
 /// time. On [`refresh`](LinkedStateHandle::refresh), the previous value is
 /// kept visible (stale-while-revalidate) and
 /// [`is_refreshing`](LinkedStateHandle::is_refreshing) returns `true` until
-/// the fresh data arrives.
+/// the fresh data arrives. The same happens when the cached value is
+/// invalidated through a [`LinkCache`], or when it is older than
+/// [`LinkedState::STALE_AFTER`] once a component starts using it.
 ///
 /// Multiple components requesting the same `(T, Input)` concurrently share a
 /// single in-flight request.
@@ -507,11 +1677,11 @@ pub fn LinkProvider(props: &LinkProviderProps) -> Html {
 /// Panics if there is no ancestor [`LinkProvider`] in the component tree.
 #[hook]
 pub fn use_linked_state<T: LinkedState>(input: T::Input) -> SuspensionResult<LinkedStateHandle<T>> {
-    #[cfg(any(feature = "ssr", target_arch = "wasm32"))]
+    #[cfg(any(feature = "ssr", target_arch = "wasm32", feature = "client"))]
     let link_ctx =
         use_context::<LinkContextInner>().expect("use_linked_state requires a LinkProvider");
 
-    #[cfg(any(feature = "ssr", target_arch = "wasm32"))]
+    #[cfg(any(feature = "ssr", target_arch = "wasm32", feature = "client"))]
     type Prepared<T, E> = Result<T, LinkError<E>>;
 
     #[cfg(feature = "ssr")]
@@ -535,26 +1705,35 @@ pub fn use_linked_state<T: LinkedState>(input: T::Input) -> SuspensionResult<Lin
                 Err(e) => Err(e.clone()),
             },
             refresh: Callback::from(|_: ()| {}),
+            mutate: Callback::from(|_| OptimisticUpdate::noop()),
             refreshing: false,
         })
     }
 
-    #[cfg(all(not(feature = "ssr"), not(target_arch = "wasm32")))]
+    #[cfg(all(
+        not(feature = "ssr"),
+        not(any(target_arch = "wasm32", feature = "client"))
+    ))]
     {
         let _ = input;
         Ok(LinkedStateHandle {
             result: Err(LinkError::Internal(
-                "yew-link requires the `ssr` feature (server) or a wasm32 target (client)".into(),
+                "yew-link requires the `ssr` feature (server), or a wasm32 target or the `client` feature (client)".into(),
             )),
             refresh: Callback::from(|_: ()| {}),
+            mutate: Callback::from(|_| OptimisticUpdate::noop()),
             refreshing: false,
         })
     }
 
-    #[cfg(all(not(feature = "ssr"), target_arch = "wasm32"))]
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
     {
+        #[cfg(target_arch = "wasm32")]
         let prepared =
             yew::functional::use_prepared_state::<Prepared<T, T::Error>, T::Input>(input.clone())?;
+        // Native clients are never hydrated.
+        #[cfg(not(target_arch = "wasm32"))]
+        let prepared: Option<Rc<Prepared<T, T::Error>>> = None;
 
         let key = cache_key::<T>(&input);
 
@@ -562,54 +1741,68 @@ pub fn use_linked_state<T: LinkedState>(input: T::Input) -> SuspensionResult<Lin
             if let Ok(json_val) = serde_json::to_value(result.as_ref()) {
                 let mut cache = link_ctx.cache.borrow_mut();
                 if cache.peek(&key).is_none() {
-                    cache.put(key.clone(), json_val);
+                    cache.put(key.clone(), CacheEntry::new::<T>(json_val));
                 }
             }
         }
 
-        let force_update = use_force_update();
+        link_ctx.restore::<T>(&key, &input);
+
+        let mutate = {
+            let key = key.clone();
+            let link_ctx = link_ctx.clone();
+            Callback::from(move |f| link_ctx.mutate::<T>(key.clone(), f))
+        };
+
         let has_refreshed = use_ref(|| std::cell::Cell::new(false));
+        let mounted_key = use_ref(|| RefCell::new(None::<CacheKey>));
 
         let refresh = {
-            let cache = link_ctx.cache.clone();
-            let refreshing = link_ctx.refreshing.clone();
             let key = key.clone();
             let link_ctx = link_ctx.clone();
             let input = input.clone();
-            let force_update = force_update.clone();
             let has_refreshed = has_refreshed.clone();
             Callback::from(move |()| {
-                refreshing.borrow_mut().insert(key.clone());
                 has_refreshed.set(true);
+                link_ctx.spawn_refresh::<T>(key.clone(), input.clone());
+                link_ctx.notify();
+            })
+        };
 
-                let link_ctx = link_ctx.clone();
-                let key = key.clone();
-                let cache = cache.clone();
-                let refreshing = refreshing.clone();
-                let inner_force_update = force_update.clone();
-                let input = input.clone();
-                wasm_bindgen_futures::spawn_local(async move {
-                    let result: Result<T, LinkError<T::Error>> =
-                        link_ctx.fetch_remote::<T>(&input).await;
-
-                    refreshing.borrow_mut().remove(&key);
-
-                    let should_cache = match &result {
-                        Ok(_) | Err(LinkError::Resolve(_)) => true,
-                        Err(LinkError::Internal(_)) => false,
-                    };
-                    if should_cache {
-                        if let Ok(json_val) = serde_json::to_value(&result) {
-                            cache.borrow_mut().put(key, json_val);
-                        }
-                    }
+        // Values that expired while no component was using them are revalidated
+        // once, when a component starts using them.
+        let expired = mounted_key.borrow().as_ref() != Some(&key)
+            && T::STALE_AFTER.is_some_and(|stale_after| {
+                link_ctx
+                    .cache
+                    .borrow()
+                    .peek(&key)
+                    .is_some_and(|entry| entry.is_older_than(stale_after))
+            });
+        *mounted_key.borrow_mut() = Some(key.clone());
+
+        if expired || link_ctx.stale.borrow().contains(&key) {
+            has_refreshed.set(true);
+            link_ctx.spawn_refresh::<T>(key.clone(), input.clone());
+        }
 
-                    inner_force_update.force_update();
-                });
+        let is_refreshing = link_ctx.refreshing.borrow().contains(&key);
 
-                force_update.force_update();
-            })
-        };
+        let cached_val = link_ctx
+            .cache
+            .borrow_mut()
+            .get(&key)
+            .map(|entry| entry.value.clone());
+        if let Some(cached_val) = cached_val {
+            if let Ok(result) = serde_json::from_value::<Prepared<T, T::Error>>(cached_val) {
+                return Ok(LinkedStateHandle {
+                    result: result.map(Rc::new),
+                    refresh,
+                    mutate,
+                    refreshing: is_refreshing,
+                });
+            }
+        }
 
         if !has_refreshed.get() {
             if let Some(result) = prepared {
@@ -619,66 +1812,121 @@ pub fn use_linked_state<T: LinkedState>(input: T::Input) -> SuspensionResult<Lin
                         Err(e) => Err(e.clone()),
                     },
                     refresh,
+                    mutate,
                     refreshing: false,
                 });
             }
         }
 
-        let is_refreshing = link_ctx.refreshing.borrow().contains(&key);
-
-        if let Some(cached_val) = link_ctx.cache.borrow_mut().get(&key).cloned() {
-            if let Ok(result) = serde_json::from_value::<Prepared<T, T::Error>>(cached_val) {
-                return Ok(LinkedStateHandle {
-                    result: result.map(Rc::new),
-                    refresh,
-                    refreshing: is_refreshing,
-                });
-            }
-        }
-
         if let Some(sus) = link_ctx.in_flight.borrow().get(&key).cloned() {
             if !sus.resumed() {
                 return Err(sus);
             }
-            if let Some(cached_val) = link_ctx.cache.borrow_mut().get(&key).cloned() {
+            let cached_val = link_ctx
+                .cache
+                .borrow_mut()
+                .get(&key)
+                .map(|entry| entry.value.clone());
+            if let Some(cached_val) = cached_val {
                 if let Ok(result) = serde_json::from_value::<Prepared<T, T::Error>>(cached_val) {
                     return Ok(LinkedStateHandle {
                         result: result.map(Rc::new),
                         refresh,
+                        mutate,
                         refreshing: false,
                     });
                 }
             }
         }
 
-        let sus = Suspension::from_future({
-            let link_ctx = link_ctx.clone();
-            let key = key.clone();
-            async move {
-                let result: Result<T, LinkError<T::Error>> =
-                    link_ctx.fetch_remote::<T>(&input).await;
-
-                link_ctx.in_flight.borrow_mut().remove(&key);
-
-                let should_cache = match &result {
-                    Ok(_) | Err(LinkError::Resolve(_)) => true,
-                    Err(LinkError::Internal(_)) => false,
-                };
-                if should_cache {
-                    if let Ok(json_val) = serde_json::to_value(&result) {
-                        link_ctx.cache.borrow_mut().put(key, json_val);
-                    }
-                }
+        // A failure that is not cached is shown once, the next render fetches
+        // the state again.
+        let failed = link_ctx.failed.borrow_mut().remove(&key);
+        if let Some(err_val) = failed {
+            if let Ok(result) = serde_json::from_value::<Prepared<T, T::Error>>(err_val) {
+                return Ok(LinkedStateHandle {
+                    result: result.map(Rc::new),
+                    refresh,
+                    mutate,
+                    refreshing: false,
+                });
             }
-        });
+        }
 
-        link_ctx.in_flight.borrow_mut().insert(key, sus.clone());
-        Err(sus)
+        Err(link_ctx.spawn_fetch::<T>(key, input))
     }
 }
 
-#[cfg(all(not(target_arch = "wasm32"), any(feature = "axum", feature = "actix")))]
+/// Returns a callback that loads the [`LinkedState`] `T` for an input into the
+/// cache of the nearest [`LinkProvider`] without suspending.
+///
+/// Components that use the state afterwards render from the cache right away,
+/// or suspend on the prefetch if it is still in flight. Inputs that are cached
+/// and fresh are not fetched again, and stale ones are re-fetched in the
+/// background. The callback does nothing on the server.
+///
+/// Pairs with the `prefetch` prop of yew-router's `Link`:
+///
+/// ```ignore
+/// let prefetch = use_link_prefetch::<Post>();
+///
+/// html! {
+///     <Link<Route> to={Route::Post { id }} prefetch={prefetch.reform(move |_| id)}>
+///         { "Read more" }
+///     </Link<Route>>
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Panics if there is no ancestor [`LinkProvider`] in the component tree.
+#[hook]
+pub fn use_link_prefetch<T: LinkedState>() -> Callback<T::Input> {
+    let link_ctx =
+        use_context::<LinkContextInner>().expect("use_link_prefetch requires a LinkProvider");
+
+    use_callback(link_ctx, |input: T::Input, link_ctx| {
+        #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+        link_ctx.prefetch::<T>(input);
+
+        #[cfg(not(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client"))))]
+        {
+            let _ = (input, link_ctx);
+        }
+    })
+}
+
+mod action;
+mod codec;
+mod pages;
+mod request;
+mod storage;
+mod stream;
+mod transport;
+pub use action::*;
+pub use codec::*;
+pub use pages::*;
+pub use request::*;
+pub use storage::*;
+pub use stream::*;
+pub use transport::*;
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use futures::stream::{BoxStream, StreamExt};
+}
+
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    any(feature = "axum", feature = "actix", feature = "tower")
+))]
 mod services;
 
-#[cfg(all(not(target_arch = "wasm32"), any(feature = "axum", feature = "actix")))]
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    any(feature = "axum", feature = "actix", feature = "tower")
+))]
 pub use services::*;
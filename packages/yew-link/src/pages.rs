@@ -0,0 +1,274 @@
+use std::rc::Rc;
+
+use yew::prelude::*;
+use yew::suspense::SuspensionResult;
+
+#[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+use crate::{LinkContextInner, cache_key};
+use crate::{LinkError, LinkedState, use_linked_state};
+
+/// A [`LinkedState`] that is one page of a longer list, such as a feed.
+///
+/// The input of a page names the page, typically with a cursor or an offset.
+/// Each page is resolved and cached like any other linked state;
+/// [`use_linked_pages`] chains them together.
+///
+/// ```ignore
+/// #[derive(Clone, Serialize, Deserialize)]
+/// pub struct Feed {
+///     pub posts: Vec<Post>,
+///     pub next_cursor: Option<u64>,
+/// }
+///
+/// impl LinkedPage for Feed {
+///     fn next_page(&self, _input: &Option<u64>) -> Option<Option<u64>> {
+///         self.next_cursor.map(Some)
+///     }
+/// }
+/// ```
+pub trait LinkedPage: LinkedState {
+    /// Returns the input of the page after this one, or `None` if this is the
+    /// last page.
+    ///
+    /// `input` is the input this page was resolved for.
+    fn next_page(&self, input: &Self::Input) -> Option<Self::Input>;
+}
+
+/// How far a list of pages has been loaded, keyed by its first page.
+#[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+#[derive(Clone, Default)]
+pub(crate) struct PagesState {
+    /// The number of pages loaded, including the first one.
+    loaded: usize,
+    loading: bool,
+    /// The serialized [`LinkError`] of the latest failed load.
+    error: Option<serde_json::Value>,
+}
+
+/// Handle returned by [`use_linked_pages`].
+pub struct LinkedPagesHandle<T: LinkedPage> {
+    pages: Vec<Rc<T>>,
+    error: Option<LinkError<T::Error>>,
+    loading: bool,
+    has_more: bool,
+    load_more: Callback<()>,
+    refresh: Callback<()>,
+}
+
+impl<T: LinkedPage> Clone for LinkedPagesHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            pages: self.pages.clone(),
+            error: self.error.clone(),
+            loading: self.loading,
+            has_more: self.has_more,
+            load_more: self.load_more.clone(),
+            refresh: self.refresh.clone(),
+        }
+    }
+}
+
+impl<T: LinkedPage> LinkedPagesHandle<T> {
+    /// Returns the pages loaded so far, in order.
+    pub fn pages(&self) -> &[Rc<T>] {
+        &self.pages
+    }
+
+    /// Returns `true` if the last loaded page is followed by another one.
+    pub fn has_more(&self) -> bool {
+        self.has_more
+    }
+
+    /// Fetches the next page in the background.
+    ///
+    /// The loaded pages stay on screen while it is fetched; see
+    /// [`is_loading`](Self::is_loading). Does nothing if there are no more
+    /// pages, if a page is already being loaded, and on the server.
+    pub fn load_more(&self) {
+        self.load_more.emit(());
+    }
+
+    /// Returns `true` while [`load_more`](Self::load_more) is fetching a page.
+    pub fn is_loading(&self) -> bool {
+        self.loading
+    }
+
+    /// Returns the error of the first page, or of the latest failed
+    /// [`load_more`](Self::load_more).
+    pub fn error(&self) -> Option<&LinkError<T::Error>> {
+        self.error.as_ref()
+    }
+
+    /// Drops every page but the first one and re-fetches it in the
+    /// background.
+    pub fn refresh(&self) {
+        self.refresh.emit(());
+    }
+}
+
+/// Fetch a list of [`LinkedPage`]s, starting with the page for `input`.
+///
+/// The first page is fetched like [`use_linked_state`] does: the hook suspends
+/// until it is available, it is resolved and embedded in the HTML during SSR,
+/// and hydration reads it without a request. Further pages are fetched with
+/// [`load_more`](LinkedPagesHandle::load_more) and kept in the cache of the
+/// [`LinkProvider`](crate::LinkProvider), so a component that mounts again
+/// shows every page loaded before.
+///
+/// ```ignore
+/// let feed = use_linked_pages::<Feed>(None)?;
+/// let onclick = {
+///     let feed = feed.clone();
+///     Callback::from(move |_| feed.load_more())
+/// };
+///
+/// html! {
+///     <>
+///         { for feed.pages().iter().flat_map(|page| page.posts.iter()).map(render_post) }
+///         if feed.has_more() {
+///             <button {onclick} disabled={feed.is_loading()}>{ "Load more" }</button>
+///         }
+///     </>
+/// }
+/// ```
+///
+/// # Panics
+///
+/// Panics if there is no ancestor [`LinkProvider`](crate::LinkProvider) in the
+/// component tree.
+#[hook]
+pub fn use_linked_pages<T: LinkedPage>(input: T::Input) -> SuspensionResult<LinkedPagesHandle<T>> {
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    let link_ctx =
+        use_context::<LinkContextInner>().expect("use_linked_pages requires a LinkProvider");
+
+    let first = use_linked_state::<T>(input.clone())?;
+
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    let key = cache_key::<T>(&input);
+
+    let refresh = {
+        let first = first.clone();
+        #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+        let (link_ctx, key) = (link_ctx.clone(), key.clone());
+        Callback::from(move |()| {
+            #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+            link_ctx.pages.borrow_mut().remove(&key);
+            first.refresh();
+        })
+    };
+
+    let first_page = match first.as_result() {
+        Ok(page) => Rc::clone(page),
+        Err(e) => {
+            return Ok(LinkedPagesHandle {
+                pages: Vec::new(),
+                error: Some(e.clone()),
+                loading: false,
+                has_more: false,
+                load_more: Callback::from(|()| {}),
+                refresh,
+            });
+        }
+    };
+
+    let next = first_page.next_page(&input);
+    let pages = vec![first_page];
+
+    #[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+    let (pages, next, loading, error, load_more) = {
+        let (mut pages, mut next) = (pages, next);
+        let state = link_ctx
+            .pages
+            .borrow()
+            .get(&key)
+            .cloned()
+            .unwrap_or_default();
+
+        // Pages evicted from the cache end the list; `load_more` fetches them again.
+        while pages.len() < state.loaded {
+            let Some(page_input) = next.clone() else {
+                break;
+            };
+            let page_key = cache_key::<T>(&page_input);
+            if link_ctx.stale.borrow().contains(&page_key) {
+                link_ctx.spawn_refresh::<T>(page_key.clone(), page_input.clone());
+            }
+
+            let cached_val = link_ctx
+                .cache
+                .borrow_mut()
+                .get(&page_key)
+                .map(|entry| entry.value.clone());
+            let Some(Ok(Ok(page))) =
+                cached_val.map(serde_json::from_value::<Result<T, LinkError<T::Error>>>)
+            else {
+                break;
+            };
+
+            next = page.next_page(&page_input);
+            pages.push(Rc::new(page));
+        }
+
+        let error = state
+            .error
+            .and_then(|err_val| serde_json::from_value::<LinkError<T::Error>>(err_val).ok());
+
+        let load_more = {
+            let link_ctx = link_ctx.clone();
+            let next = next.clone();
+            let loaded = pages.len();
+            Callback::from(move |()| {
+                let Some(page_input) = next.clone() else {
+                    return;
+                };
+                {
+                    let mut all = link_ctx.pages.borrow_mut();
+                    let state = all.entry(key.clone()).or_default();
+                    if state.loading {
+                        return;
+                    }
+                    state.loading = true;
+                    state.error = None;
+                }
+                link_ctx.notify();
+
+                let link_ctx = link_ctx.clone();
+                let key = key.clone();
+                yew::platform::spawn_local(async move {
+                    let result: Result<T, LinkError<T::Error>> =
+                        link_ctx.fetch_remote::<T>(&page_input).await;
+
+                    {
+                        let mut all = link_ctx.pages.borrow_mut();
+                        let state = all.entry(key).or_default();
+                        state.loading = false;
+                        match &result {
+                            Ok(_) => state.loaded = loaded + 1,
+                            Err(e) => state.error = serde_json::to_value(e).ok(),
+                        }
+                    }
+                    if result.is_ok() {
+                        link_ctx.store::<T>(cache_key::<T>(&page_input), &result);
+                    }
+
+                    link_ctx.notify();
+                });
+            })
+        };
+
+        (pages, next, state.loading, error, load_more)
+    };
+
+    #[cfg(not(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client"))))]
+    let (loading, error, load_more) = (false, None, Callback::from(|()| {}));
+
+    Ok(LinkedPagesHandle {
+        pages,
+        error,
+        loading,
+        has_more: next.is_some(),
+        load_more,
+        refresh,
+    })
+}
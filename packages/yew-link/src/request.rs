@@ -0,0 +1,155 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// The incoming HTTP request a linked state is resolved for.
+///
+/// The axum and actix handlers fill it in from the request they serve. During
+/// SSR it is passed to the [`LinkProvider`](crate::LinkProvider) through the
+/// `request` prop, so that resolvers see the same request on both paths.
+///
+/// Resolvers don't read it directly but declare a [`FromRequestContext`]
+/// extractor. Cloning is cheap; two contexts are equal if they are clones of
+/// each other.
+#[derive(Clone, Default)]
+pub struct RequestContext {
+    inner: Arc<RequestContextInner>,
+}
+
+#[derive(Clone, Default)]
+struct RequestContextInner {
+    uri: String,
+    headers: Vec<(String, String)>,
+    extensions: HashMap<TypeId, Arc<dyn Any + Send + Sync>>,
+}
+
+impl RequestContext {
+    /// Creates an empty context.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the request URI.
+    pub fn with_uri(mut self, uri: impl Into<String>) -> Self {
+        Arc::make_mut(&mut self.inner).uri = uri.into();
+        self
+    }
+
+    /// Appends a header. Header names are case-insensitive.
+    pub fn with_header(mut self, name: impl AsRef<str>, value: impl Into<String>) -> Self {
+        Arc::make_mut(&mut self.inner)
+            .headers
+            .push((name.as_ref().to_ascii_lowercase(), value.into()));
+        self
+    }
+
+    /// Attaches a value of type `T`, replacing any previous value of that type.
+    ///
+    /// Use this to pass data that was already extracted by a middleware, such as
+    /// an authenticated session.
+    pub fn with_extension<T: Send + Sync + 'static>(mut self, value: T) -> Self {
+        Arc::make_mut(&mut self.inner)
+            .extensions
+            .insert(TypeId::of::<T>(), Arc::new(value));
+        self
+    }
+
+    /// Returns the request URI, or an empty string if it was not set.
+    pub fn uri(&self) -> &str {
+        &self.inner.uri
+    }
+
+    /// Returns the first value of the header `name`.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.inner
+            .headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Returns all values of the header `name`.
+    pub fn headers<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.inner
+            .headers
+            .iter()
+            .filter(move |(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Returns the value of the cookie `name`.
+    pub fn cookie(&self, name: &str) -> Option<&str> {
+        self.headers("cookie")
+            .flat_map(|header| header.split(';'))
+            .filter_map(|pair| pair.trim().split_once('='))
+            .find(|(n, _)| *n == name)
+            .map(|(_, v)| v)
+    }
+
+    /// Returns the value of type `T` attached with [`with_extension`](Self::with_extension).
+    pub fn extension<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.inner
+            .extensions
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref())
+    }
+}
+
+impl fmt::Debug for RequestContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Header values are left out as they may contain credentials.
+        f.debug_struct("RequestContext")
+            .field("uri", &self.inner.uri)
+            .field(
+                "headers",
+                &self
+                    .inner
+                    .headers
+                    .iter()
+                    .map(|(n, _)| n)
+                    .collect::<Vec<_>>(),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for RequestContext {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+/// A value a resolver extracts from the [`RequestContext`] it runs in.
+///
+/// Declared as the `Extract` type of [`LinkedStateResolve`](crate::LinkedStateResolve),
+/// [`LinkedActionResolve`](crate::LinkedActionResolve) and
+/// [`LinkedStreamResolve`](crate::LinkedStreamResolve). Extraction cannot fail;
+/// use an `Option` or an enum if the data may be missing and return an
+/// application-level error from the resolver instead.
+///
+/// ```
+/// use yew_link::{FromRequestContext, RequestContext};
+///
+/// pub struct Tenant(pub Option<String>);
+///
+/// impl FromRequestContext for Tenant {
+///     fn from_request_context(req: &RequestContext) -> Self {
+///         Self(req.header("x-tenant").map(str::to_owned))
+///     }
+/// }
+/// ```
+pub trait FromRequestContext: Send + 'static {
+    /// Extracts the value from `req`.
+    fn from_request_context(req: &RequestContext) -> Self;
+}
+
+impl FromRequestContext for () {
+    fn from_request_context(_req: &RequestContext) -> Self {}
+}
+
+impl FromRequestContext for RequestContext {
+    fn from_request_context(req: &RequestContext) -> Self {
+        req.clone()
+    }
+}
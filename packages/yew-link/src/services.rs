@@ -1,15 +1,233 @@
+#[cfg(feature = "tower")]
+pub mod tower {
+    use std::convert::Infallible;
+    use std::fmt;
+    use std::pin::Pin;
+    use std::sync::Arc;
+    use std::task::{Context, Poll};
+
+    use bytes::Bytes;
+    use http::request::Parts;
+    use http::{Method, Request, Response, StatusCode, header};
+    use http_body::Body;
+    use http_body_util::{BodyExt, Full, LengthLimitError, Limited};
+    use tower_service::Service;
+
+    use crate::{LinkCodec, RequestContext, Resolver};
+
+    type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+    /// The default maximum size of a request body accepted by [`LinkService`], 2 MiB.
+    pub const DEFAULT_BODY_LIMIT: usize = 2 * 1024 * 1024;
+
+    /// Builds the [`RequestContext`] resolvers see from the request parts.
+    ///
+    /// Headers whose value is not valid UTF-8 are skipped.
+    pub fn request_context(parts: &Parts) -> RequestContext {
+        parts
+            .headers
+            .iter()
+            .filter_map(|(name, value)| Some((name, value.to_str().ok()?)))
+            .fold(
+                RequestContext::new().with_uri(parts.uri.to_string()),
+                |request, (name, value)| request.with_header(name, value),
+            )
+    }
+
+    /// A [`tower_service::Service`] that resolves [`LinkRequest`](crate::LinkRequest)s.
+    ///
+    /// Works with any server built on `http` 1.x and tower, such as hyper or
+    /// axum. Accepts a single request or a batch of requests, which are resolved
+    /// concurrently, encoded with any of the accepted [codecs](Self::codecs).
+    /// The response is encoded with the codec of the request. Responds with
+    ///
+    /// - `405 Method Not Allowed` if the request is not a `POST`,
+    /// - `415 Unsupported Media Type` if the `Content-Type` of the body does not
+    ///   name an accepted codec,
+    /// - `413 Payload Too Large` if the body exceeds the [body limit](Self::body_limit),
+    /// - `400 Bad Request` if the body cannot be read or decoded,
+    /// - `422 Unprocessable Entity` if a single request failed to resolve,
+    /// - `200 OK` otherwise.
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use yew_link::Resolver;
+    /// use yew_link::tower::LinkService;
+    ///
+    /// let service = LinkService::new(Arc::new(Resolver::new())).body_limit(64 * 1024);
+    /// # let _ = service;
+    /// ```
+    #[derive(Clone)]
+    pub struct LinkService {
+        resolver: Arc<Resolver>,
+        body_limit: usize,
+        check_content_type: bool,
+        codecs: Option<Vec<LinkCodec>>,
+    }
+
+    impl fmt::Debug for LinkService {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("LinkService")
+                .field("resolver", &self.resolver)
+                .field("body_limit", &self.body_limit)
+                .field("check_content_type", &self.check_content_type)
+                .field("codecs", &self.codecs)
+                .finish()
+        }
+    }
+
+    impl LinkService {
+        /// Creates a service that resolves requests with `resolver`.
+        pub fn new(resolver: Arc<Resolver>) -> Self {
+            Self {
+                resolver,
+                body_limit: DEFAULT_BODY_LIMIT,
+                check_content_type: true,
+                codecs: None,
+            }
+        }
+
+        /// Sets the maximum size of a request body in bytes. Defaults to
+        /// [`DEFAULT_BODY_LIMIT`].
+        pub fn body_limit(mut self, limit: usize) -> Self {
+            self.body_limit = limit;
+            self
+        }
+
+        /// Sets whether requests must declare the `Content-Type` of an accepted
+        /// codec. Defaults to `true`.
+        ///
+        /// When disabled, bodies without a known `Content-Type` are decoded as
+        /// JSON.
+        pub fn check_content_type(mut self, check: bool) -> Self {
+            self.check_content_type = check;
+            self
+        }
+
+        /// Restricts the codecs requests may be encoded with. Defaults to every
+        /// [`LinkCodec`] enabled by the crate features.
+        pub fn codecs(mut self, codecs: impl IntoIterator<Item = LinkCodec>) -> Self {
+            self.codecs = Some(codecs.into_iter().collect());
+            self
+        }
+
+        fn accepts(&self, codec: LinkCodec) -> bool {
+            self.codecs
+                .as_ref()
+                .is_none_or(|codecs| codecs.contains(&codec))
+        }
+
+        /// Resolves a single HTTP request.
+        pub async fn handle<B>(&self, req: Request<B>) -> Response<Full<Bytes>>
+        where
+            B: Body,
+            B::Error: Into<BoxError>,
+        {
+            let (parts, body) = req.into_parts();
+
+            if parts.method != Method::POST {
+                let mut resp = text_response(
+                    StatusCode::METHOD_NOT_ALLOWED,
+                    "linked states must be requested with POST",
+                );
+                resp.headers_mut()
+                    .insert(header::ALLOW, header::HeaderValue::from_static("POST"));
+                return resp;
+            }
+
+            let codec = parts
+                .headers
+                .get(header::CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .and_then(LinkCodec::from_content_type)
+                .filter(|codec| self.accepts(*codec));
+            let codec = match codec {
+                Some(codec) => codec,
+                None if !self.check_content_type => LinkCodec::Json,
+                None => {
+                    return text_response(
+                        StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                        "expected a request with the `Content-Type` of an accepted codec",
+                    );
+                }
+            };
+
+            let body = match Limited::new(body, self.body_limit).collect().await {
+                Ok(collected) => collected.to_bytes(),
+                Err(e) if e.is::<LengthLimitError>() => {
+                    return text_response(StatusCode::PAYLOAD_TOO_LARGE, e.to_string());
+                }
+                Err(e) => return text_response(StatusCode::BAD_REQUEST, e.to_string()),
+            };
+
+            let (status, body) = self
+                .resolver
+                .resolve_body_with(codec, &body, &request_context(&parts))
+                .await;
+
+            Response::builder()
+                .status(status)
+                .header(header::CONTENT_TYPE, codec.content_type())
+                .body(Full::new(Bytes::from(body)))
+                .expect("status and headers are valid")
+        }
+    }
+
+    fn text_response(status: StatusCode, body: impl Into<String>) -> Response<Full<Bytes>> {
+        Response::builder()
+            .status(status)
+            .header(header::CONTENT_TYPE, "text/plain; charset=utf-8")
+            .body(Full::new(Bytes::from(body.into())))
+            .expect("status and headers are valid")
+    }
+
+    impl<B> Service<Request<B>> for LinkService
+    where
+        B: Body + Send + 'static,
+        B::Data: Send,
+        B::Error: Into<BoxError>,
+    {
+        type Error = Infallible;
+        type Future =
+            Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send + 'static>>;
+        type Response = Response<Full<Bytes>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: Request<B>) -> Self::Future {
+            let service = self.clone();
+            Box::pin(async move { Ok(service.handle(req).await) })
+        }
+    }
+}
+
 #[cfg(feature = "axum")]
 pub mod axum {
+    use std::convert::Infallible;
     use std::sync::Arc;
 
-    use axum::Json;
-    use axum::extract::State;
+    use axum::extract::{Query, Request, State};
     use axum::http::StatusCode;
-    use axum::response::IntoResponse;
+    use axum::http::request::Parts;
+    use axum::response::sse::{Event, KeepAlive, Sse};
+    use axum::response::{IntoResponse, Response};
+    use futures::stream::StreamExt;
 
-    use crate::{LinkRequest, LinkResponse, Resolver};
+    use crate::tower::LinkService;
+    pub use crate::tower::request_context;
+    use crate::{LinkRequest, LinkStreamQuery, Resolver};
 
-    /// Axum handler that resolves [`LinkRequest`]s.
+    /// Axum handler that resolves [`LinkRequest`](crate::LinkRequest)s.
+    ///
+    /// Accepts a single request or a batch of requests, which are resolved
+    /// concurrently. Resolvers see the incoming request through their
+    /// [`FromRequestContext`](crate::FromRequestContext) extractor.
+    ///
+    /// This is a thin wrapper over [`LinkService`] with its default settings.
+    /// Use `axum::Router::route_service` with a [`LinkService`] to change them.
     ///
     /// ```
     /// use std::sync::Arc;
@@ -46,35 +264,87 @@ pub mod axum {
     /// ```
     pub async fn linked_state_handler(
         State(resolver): State<Arc<Resolver>>,
-        Json(req): Json<LinkRequest>,
-    ) -> impl IntoResponse {
-        match resolver.resolve_request(&req).await {
-            Ok(val) => (
-                StatusCode::OK,
-                Json(LinkResponse {
-                    ok: Some(val),
-                    error: None,
-                }),
-            ),
-            Err(err_val) => (
-                StatusCode::UNPROCESSABLE_ENTITY,
-                Json(LinkResponse {
-                    ok: None,
-                    error: Some(err_val),
-                }),
-            ),
+        request: Request,
+    ) -> Response {
+        LinkService::new(resolver)
+            .handle(request)
+            .await
+            .into_response()
+    }
+
+    /// Axum handler that streams [`LinkedStream`](crate::LinkedStream) values
+    /// as server-sent events.
+    ///
+    /// Serve it at the `stream_endpoint` of the
+    /// [`LinkProvider`](crate::LinkProvider), which defaults to `{endpoint}/stream`.
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use yew_link::Resolver;
+    /// use yew_link::axum::{linked_state_handler, linked_stream_handler};
+    ///
+    /// let resolver = Arc::new(Resolver::new());
+    ///
+    /// let app: axum::Router = axum::Router::new()
+    ///     .route("/api/link", axum::routing::post(linked_state_handler))
+    ///     .route("/api/link/stream", axum::routing::get(linked_stream_handler))
+    ///     .with_state(resolver);
+    /// # let _ = app;
+    /// ```
+    pub async fn linked_stream_handler(
+        State(resolver): State<Arc<Resolver>>,
+        parts: Parts,
+        Query(query): Query<LinkStreamQuery>,
+    ) -> Response {
+        let request = request_context(&parts);
+        match LinkRequest::try_from(query).and_then(|req| resolver.subscribe_with(&req, &request)) {
+            Ok(stream) => {
+                let events =
+                    stream.map(|val| Ok::<_, Infallible>(Event::default().data(val.to_string())));
+                Sse::new(events)
+                    .keep_alive(KeepAlive::default())
+                    .into_response()
+            }
+            Err(e) => (StatusCode::UNPROCESSABLE_ENTITY, e).into_response(),
         }
     }
 }
 
 #[cfg(feature = "actix")]
 pub mod actix {
-    use actix_web::HttpResponse;
-    use actix_web::web::{Data, Json};
+    use actix_web::http::{StatusCode, header};
+    use actix_web::web::{Bytes, Data, Payload, Query};
+    use actix_web::{HttpRequest, HttpResponse};
+    use futures::stream::{StreamExt, TryStreamExt};
+    use http_body::Frame;
+    use http_body_util::{BodyExt, StreamBody};
 
-    use crate::{LinkRequest, LinkResponse, Resolver};
+    use crate::tower::LinkService;
+    use crate::{LinkRequest, LinkStreamQuery, RequestContext, Resolver};
 
-    /// Actix handler that resolves [`LinkRequest`]s.
+    /// Builds the [`RequestContext`] resolvers see from the request.
+    ///
+    /// Headers whose value is not valid UTF-8 are skipped.
+    pub fn request_context(req: &HttpRequest) -> RequestContext {
+        req.headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name, value.to_str().ok()?)))
+            .fold(
+                RequestContext::new().with_uri(req.uri().to_string()),
+                |request, (name, value)| request.with_header(name, value),
+            )
+    }
+
+    /// Actix handler that resolves [`LinkRequest`](crate::LinkRequest)s.
+    ///
+    /// Accepts a single request or a batch of requests, which are resolved
+    /// concurrently. Resolvers see the incoming request through their
+    /// [`FromRequestContext`](crate::FromRequestContext) extractor.
+    ///
+    /// This is a thin wrapper over [`serve`] with the default settings of
+    /// [`LinkService`], so it checks the codec and the body size and responds
+    /// with the same status codes.
     ///
     /// ```no_run
     /// use actix_web::web::{Data, post};
@@ -117,18 +387,102 @@ pub mod actix {
     /// ```
     pub async fn linked_state_handler(
         resolver: Data<Resolver>,
-        Json(req): Json<LinkRequest>,
+        req: HttpRequest,
+        payload: Payload,
+    ) -> HttpResponse {
+        serve(&LinkService::new(resolver.into_inner()), req, payload).await
+    }
+
+    /// Resolves a request with `service`, to change its settings in an actix
+    /// handler.
+    ///
+    /// ```no_run
+    /// use actix_web::web::{Data, Payload};
+    /// use actix_web::{HttpRequest, HttpResponse};
+    /// use yew_link::actix::serve;
+    /// use yew_link::tower::LinkService;
+    ///
+    /// async fn handler(
+    ///     service: Data<LinkService>,
+    ///     req: HttpRequest,
+    ///     payload: Payload,
+    /// ) -> HttpResponse {
+    ///     serve(&service, req, payload).await
+    /// }
+    /// ```
+    pub async fn serve(service: &LinkService, req: HttpRequest, payload: Payload) -> HttpResponse {
+        // actix is built on `http` 0.2, the request is converted to the 1.x
+        // types of `LinkService` by value.
+        let request = req
+            .headers()
+            .iter()
+            .fold(
+                http::Request::builder()
+                    .method(req.method().as_str())
+                    .uri(req.uri().to_string()),
+                |builder, (name, value)| builder.header(name.as_str(), value.as_bytes()),
+            )
+            .body(StreamBody::new(payload.map_ok(Frame::data)))
+            .expect("the request of actix is valid");
+
+        let (parts, body) = service.handle(request).await.into_parts();
+        let body = match body.collect().await {
+            Ok(collected) => collected.to_bytes(),
+            Err(never) => match never {},
+        };
+
+        let mut resp = HttpResponse::build(
+            StatusCode::from_u16(parts.status.as_u16()).expect("status is valid"),
+        );
+        for (name, value) in &parts.headers {
+            resp.insert_header((name.as_str(), value.as_bytes()));
+        }
+        resp.body(body)
+    }
+
+    /// Actix handler that streams [`LinkedStream`](crate::LinkedStream) values
+    /// as server-sent events.
+    ///
+    /// Serve it at the `stream_endpoint` of the
+    /// [`LinkProvider`](crate::LinkProvider), which defaults to `{endpoint}/stream`.
+    ///
+    /// ```no_run
+    /// use actix_web::web::{Data, get, post};
+    /// use actix_web::{App, HttpServer};
+    /// use yew_link::Resolver;
+    /// use yew_link::actix::{linked_state_handler, linked_stream_handler};
+    ///
+    /// #[actix_web::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///     let resolver = Data::new(Resolver::new());
+    ///
+    ///     HttpServer::new(move || {
+    ///         App::new()
+    ///             .app_data(resolver.clone())
+    ///             .route("/api/link", post().to(linked_state_handler))
+    ///             .route("/api/link/stream", get().to(linked_stream_handler))
+    ///     })
+    ///     .bind(("0.0.0.0", 8080))?
+    ///     .run()
+    ///     .await
+    /// }
+    /// ```
+    pub async fn linked_stream_handler(
+        resolver: Data<Resolver>,
+        http_req: HttpRequest,
+        Query(query): Query<LinkStreamQuery>,
     ) -> HttpResponse {
-        match resolver.resolve_request(&req).await {
-            Ok(val) => HttpResponse::Ok().json(LinkResponse {
-                ok: Some(val),
-                error: None,
-            }),
-
-            Err(err_val) => HttpResponse::UnprocessableEntity().json(LinkResponse {
-                ok: None,
-                error: Some(err_val),
-            }),
+        let request = request_context(&http_req);
+        match LinkRequest::try_from(query).and_then(|req| resolver.subscribe_with(&req, &request)) {
+            Ok(stream) => {
+                HttpResponse::Ok()
+                    .content_type("text/event-stream")
+                    .insert_header((header::CACHE_CONTROL, "no-cache"))
+                    .streaming(stream.map(|val| {
+                        Ok::<_, actix_web::Error>(Bytes::from(format!("data: {val}\n\n")))
+                    }))
+            }
+            Err(e) => HttpResponse::UnprocessableEntity().body(e),
         }
     }
 }
@@ -0,0 +1,253 @@
+use std::fmt;
+use std::rc::Rc;
+
+#[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+use crate::LinkedState;
+
+/// Synchronous key-value storage that outlives the page, such as the browser's
+/// `localStorage`.
+///
+/// Used by [`LinkPersistence`] to keep linked states across reloads. Writes
+/// are best-effort: implementations should ignore failures such as an
+/// exceeded quota.
+pub trait LinkStorage: 'static {
+    /// Returns the value stored under `key`.
+    fn get(&self, key: &str) -> Option<String>;
+
+    /// Stores `value` under `key`, replacing the previous value.
+    fn set(&self, key: &str, value: &str);
+
+    /// Removes the value stored under `key`.
+    fn remove(&self, key: &str);
+
+    /// Returns every key in the storage.
+    fn keys(&self) -> Vec<String>;
+}
+
+/// A [`LinkStorage`] backed by the browser's `localStorage`.
+#[cfg(target_arch = "wasm32")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LocalStorage;
+
+#[cfg(target_arch = "wasm32")]
+impl LocalStorage {
+    fn storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok()?
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl LinkStorage for LocalStorage {
+    fn get(&self, key: &str) -> Option<String> {
+        Self::storage()?.get_item(key).ok()?
+    }
+
+    fn set(&self, key: &str, value: &str) {
+        if let Some(storage) = Self::storage() {
+            let _ = storage.set_item(key, value);
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        if let Some(storage) = Self::storage() {
+            let _ = storage.remove_item(key);
+        }
+    }
+
+    fn keys(&self) -> Vec<String> {
+        let Some(storage) = Self::storage() else {
+            return Vec::new();
+        };
+        (0..storage.length().unwrap_or(0))
+            .filter_map(|index| storage.key(index).ok().flatten())
+            .collect()
+    }
+}
+
+/// Mirrors the linked-state cache of a [`LinkProvider`](crate::LinkProvider)
+/// into a [`LinkStorage`], for the `persistence` prop.
+///
+/// Values fetched from the server are written to the storage under the
+/// [`TYPE_KEY`](crate::LinkedState::TYPE_KEY), the
+/// [`VERSION`](crate::LinkedState::VERSION) and the input of the state. When
+/// a component uses a state that is not cached yet, the stored value is shown
+/// right away and revalidated against the server in the background; if the
+/// server cannot be reached, the stored value stays. This keeps the app usable
+/// offline.
+///
+/// At most as many values as the `cache_capacity` of the provider are kept:
+/// the least recently fetched ones are removed when a new value is stored.
+///
+/// Values stored for another version of a state are ignored. To drop every
+/// stored value, for example after a change the versions of the states do not
+/// cover, bump the [`version`](Self::version) of the persistence: values
+/// stored with another one are dropped when the provider mounts.
+///
+/// `LocalStorage` is the only storage provided, on wasm32 targets. There is
+/// no IndexedDB storage: [`LinkStorage`] is synchronous, so values are limited
+/// to what `localStorage` can hold. Other storages can implement the trait.
+///
+/// ```ignore
+/// let persistence = LinkPersistence::new(LocalStorage).version(2);
+///
+/// html! {
+///     <LinkProvider endpoint="/api/link" {persistence}>
+///         <App />
+///     </LinkProvider>
+/// }
+/// ```
+#[derive(Clone)]
+pub struct LinkPersistence {
+    storage: Rc<dyn LinkStorage>,
+    prefix: Rc<str>,
+    version: u32,
+}
+
+impl fmt::Debug for LinkPersistence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LinkPersistence")
+            .field("prefix", &self.prefix)
+            .field("version", &self.version)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for LinkPersistence {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.storage, &other.storage)
+            && self.prefix == other.prefix
+            && self.version == other.version
+    }
+}
+
+impl LinkPersistence {
+    /// Persists linked states in `storage`.
+    pub fn new(storage: impl LinkStorage) -> Self {
+        Self {
+            storage: Rc::new(storage),
+            prefix: "yew-link:".into(),
+            version: 0,
+        }
+    }
+
+    /// Sets the version of the persisted values. Defaults to `0`.
+    pub fn version(mut self, version: u32) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Sets the prefix of every key written to the storage. Defaults to
+    /// `yew-link:`.
+    ///
+    /// Keys that start with the prefix belong to yew-link, and are removed
+    /// when the version changes.
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into().into();
+        self
+    }
+}
+
+#[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Persisted {
+    fetched_at: f64,
+    value: serde_json::Value,
+}
+
+#[cfg(all(not(feature = "ssr"), any(target_arch = "wasm32", feature = "client")))]
+impl LinkPersistence {
+    fn version_key(&self) -> String {
+        format!("{}version", self.prefix)
+    }
+
+    /// Drops the stored values if they were written with another version.
+    pub(crate) fn migrate(&self) {
+        let version_key = self.version_key();
+        let version = self.version.to_string();
+        if self.storage.get(&version_key).as_deref() == Some(version.as_str()) {
+            return;
+        }
+
+        for key in self.storage.keys() {
+            if key.starts_with(&*self.prefix) {
+                self.storage.remove(&key);
+            }
+        }
+        self.storage.set(&version_key, &version);
+    }
+
+    fn key<T: LinkedState>(&self, input: &T::Input) -> Option<String> {
+        let input = serde_json::to_string(input).ok()?;
+        Some(format!(
+            "{}{}@{}:{input}",
+            self.prefix,
+            T::TYPE_KEY,
+            T::VERSION
+        ))
+    }
+
+    /// Returns the stored value of `T` for `input` and the time it was fetched.
+    pub(crate) fn load<T: LinkedState>(
+        &self,
+        input: &T::Input,
+    ) -> Option<(serde_json::Value, f64)> {
+        let persisted = self.storage.get(&self.key::<T>(input)?)?;
+        let Persisted { fetched_at, value } = serde_json::from_str(&persisted).ok()?;
+        Some((value, fetched_at))
+    }
+
+    /// Stores the value of `T` for `input`, then evicts the least recently
+    /// fetched values beyond `capacity`, the capacity of the cache.
+    pub(crate) fn save<T: LinkedState>(
+        &self,
+        input: &T::Input,
+        value: &serde_json::Value,
+        fetched_at: f64,
+        capacity: usize,
+    ) {
+        let Some(key) = self.key::<T>(input) else {
+            return;
+        };
+        let persisted = Persisted {
+            fetched_at,
+            value: value.clone(),
+        };
+        if let Ok(persisted) = serde_json::to_string(&persisted) {
+            self.storage.set(&key, &persisted);
+        }
+        self.evict(capacity);
+    }
+
+    /// Removes the least recently fetched values until at most `capacity` are
+    /// stored. Values that cannot be read are removed first.
+    fn evict(&self, capacity: usize) {
+        let version_key = self.version_key();
+        let keys: Vec<String> = self
+            .storage
+            .keys()
+            .into_iter()
+            .filter(|key| key.starts_with(&*self.prefix) && *key != version_key)
+            .collect();
+        if keys.len() <= capacity {
+            return;
+        }
+
+        let mut entries: Vec<(f64, String)> = keys
+            .into_iter()
+            .map(|key| {
+                let fetched_at = self
+                    .storage
+                    .get(&key)
+                    .and_then(|persisted| serde_json::from_str::<Persisted>(&persisted).ok())
+                    .map_or(f64::NEG_INFINITY, |persisted| persisted.fetched_at);
+                (fetched_at, key)
+            })
+            .collect();
+        entries.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+        let excess = entries.len() - capacity;
+        for (_, key) in entries.into_iter().take(excess) {
+            self.storage.remove(&key);
+        }
+    }
+}
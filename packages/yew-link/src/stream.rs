@@ -0,0 +1,334 @@
+#[cfg(all(not(feature = "ssr"), target_arch = "wasm32"))]
+use std::cell::RefCell;
+use std::fmt;
+use std::hash::Hash;
+use std::rc::Rc;
+#[cfg(all(not(feature = "ssr"), target_arch = "wasm32"))]
+use std::rc::Weak;
+#[cfg(all(not(feature = "ssr"), target_arch = "wasm32"))]
+use std::time::Duration;
+
+#[cfg(not(target_arch = "wasm32"))]
+use futures::stream::BoxStream;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use yew::prelude::*;
+use yew::suspense::SuspensionResult;
+#[cfg(all(not(feature = "ssr"), target_arch = "wasm32"))]
+use yew::suspense::{Suspension, SuspensionHandle};
+
+#[cfg(any(feature = "ssr", target_arch = "wasm32"))]
+use crate::LinkContextInner;
+use crate::{LinkError, Never};
+
+/// A value that is pushed from the server to the client as it changes.
+///
+/// Where a [`LinkedState`](crate::LinkedState) is fetched once and then cached, a
+/// `LinkedStream` keeps a connection open and delivers every new value. The
+/// subscription is provided separately via [`Resolver::register_stream`](crate::Resolver::register_stream)
+/// or the [`#[linked_stream]`](crate::linked_stream) macro.
+pub trait LinkedStream: Serialize + DeserializeOwned + Clone + 'static {
+    /// The input/deps used to subscribe to this stream.
+    type Input: Serialize + DeserializeOwned + PartialEq + Eq + Hash + Clone + fmt::Debug + 'static;
+
+    /// Stable wire-format key used to route subscriptions between client and server.
+    ///
+    /// Generated automatically by [`#[linked_stream]`](crate::linked_stream) as
+    /// `concat!(module_path!(), "::", stringify!(Type))`. It shares its namespace
+    /// with [`LinkedState::TYPE_KEY`](crate::LinkedState::TYPE_KEY).
+    const TYPE_KEY: &'static str;
+}
+
+/// Server-side extension of [`LinkedStream`] that provides the subscription.
+///
+/// You normally don't implement this by hand — use the
+/// [`#[linked_stream]`](crate::linked_stream) attribute macro instead, which strips
+/// the server code from WASM bundles automatically.
+#[cfg(not(target_arch = "wasm32"))]
+pub trait LinkedStreamResolve: LinkedStream {
+    type Context: Send + Sync + 'static;
+
+    /// Request-scoped data the subscription needs, such as the current user.
+    ///
+    /// Use `()` if the subscription does not depend on the request.
+    type Extract: crate::FromRequestContext;
+
+    /// The stream cannot borrow from `ctx` or `input`, which is why it is
+    /// boxed rather than an `impl Stream`.
+    fn subscribe(
+        ctx: &Self::Context,
+        input: &Self::Input,
+        extract: Self::Extract,
+    ) -> BoxStream<'static, Self>;
+}
+
+/// Handle returned by [`use_linked_stream`].
+///
+/// Holds the latest value received from the server.
+#[derive(Clone)]
+pub struct LinkedStreamHandle<T: LinkedStream> {
+    result: Result<Rc<T>, LinkError<Never>>,
+}
+
+impl<T: LinkedStream> LinkedStreamHandle<T> {
+    /// Returns the latest value, panicking if it could not be received.
+    ///
+    /// This clones the inner [`Rc`], which is cheap.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the subscription failed. Use [`as_result`](Self::as_result) for
+    /// non-panicking access.
+    pub fn data(&self) -> Rc<T> {
+        self.result.as_ref().unwrap().clone()
+    }
+
+    /// Returns a reference to the underlying result.
+    pub fn as_result(&self) -> &Result<Rc<T>, LinkError<Never>> {
+        &self.result
+    }
+}
+
+#[cfg(feature = "ssr")]
+impl LinkContextInner {
+    /// Subscribes to `T` locally and waits for the first value.
+    async fn first_streamed<T: LinkedStream>(
+        &self,
+        input: &T::Input,
+    ) -> Result<T, LinkError<Never>> {
+        use futures::stream::StreamExt;
+
+        let resolver = self
+            .resolver
+            .as_ref()
+            .expect("resolver not set on server-side LinkProvider");
+        let req = crate::LinkRequest {
+            type_key: T::TYPE_KEY.to_string(),
+            version: 0,
+            input: serde_json::to_value(input).map_err(|e| LinkError::Internal(e.to_string()))?,
+        };
+        let val = resolver
+            .subscribe_with(&req, &self.request)
+            .map_err(LinkError::Internal)?
+            .next()
+            .await
+            .ok_or_else(|| {
+                LinkError::Internal(format!(
+                    "stream {} ended before yielding a value",
+                    T::TYPE_KEY
+                ))
+            })?;
+
+        serde_json::from_value(val).map_err(|e| LinkError::Internal(e.to_string()))
+    }
+}
+
+/// How long to wait before reconnecting after the connection is closed or fails.
+#[cfg(all(not(feature = "ssr"), target_arch = "wasm32"))]
+const RECONNECT_DELAY: Duration = Duration::from_secs(3);
+
+/// Aborts the connection task when dropped.
+#[cfg(all(not(feature = "ssr"), target_arch = "wasm32"))]
+struct StreamTask(futures::future::AbortHandle);
+
+#[cfg(all(not(feature = "ssr"), target_arch = "wasm32"))]
+impl Drop for StreamTask {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+#[cfg(all(not(feature = "ssr"), target_arch = "wasm32"))]
+struct StreamSlot<T: LinkedStream> {
+    input: Option<T::Input>,
+    latest: Option<Result<Rc<T>, LinkError<Never>>>,
+    waiting: Option<(Suspension, SuspensionHandle)>,
+    task: Option<StreamTask>,
+}
+
+#[cfg(all(not(feature = "ssr"), target_arch = "wasm32"))]
+impl<T: LinkedStream> StreamSlot<T> {
+    fn new() -> Self {
+        Self {
+            input: None,
+            latest: None,
+            waiting: None,
+            task: None,
+        }
+    }
+}
+
+#[cfg(all(not(feature = "ssr"), target_arch = "wasm32"))]
+impl LinkContextInner {
+    fn stream_url<T: LinkedStream>(&self, input: &T::Input) -> Result<String, LinkError<Never>> {
+        let input = serde_json::to_string(input).map_err(|e| LinkError::Internal(e.to_string()))?;
+        let separator = if self.stream_endpoint.contains('?') {
+            '&'
+        } else {
+            '?'
+        };
+
+        Ok(format!(
+            "{}{separator}type_key={}&input={}",
+            self.stream_endpoint,
+            String::from(js_sys::encode_uri_component(T::TYPE_KEY)),
+            String::from(js_sys::encode_uri_component(&input)),
+        ))
+    }
+}
+
+/// Receives values from `url` into `slot` until the task is aborted.
+///
+/// The first error, whether the server closed the connection or the network
+/// failed, ends the `EventSource`, and a new one is opened after
+/// [`RECONNECT_DELAY`].
+#[cfg(all(not(feature = "ssr"), target_arch = "wasm32"))]
+async fn listen<T: LinkedStream>(
+    url: String,
+    slot: Weak<RefCell<StreamSlot<T>>>,
+    force_update: UseForceUpdateHandle,
+) {
+    use futures::stream::StreamExt;
+    use gloo_net::eventsource::futures::EventSource;
+
+    loop {
+        if let Ok(mut source) = EventSource::new(&url) {
+            if let Ok(mut messages) = source.subscribe("message") {
+                while let Some(Ok((_, event))) = messages.next().await {
+                    let Some(slot) = slot.upgrade() else {
+                        return;
+                    };
+
+                    let result = event
+                        .data()
+                        .as_string()
+                        .ok_or_else(|| LinkError::Internal("expected a text message".into()))
+                        .and_then(|data| {
+                            serde_json::from_str::<T>(&data)
+                                .map_err(|e| LinkError::Internal(e.to_string()))
+                        })
+                        .map(Rc::new);
+
+                    let waiting = {
+                        let mut slot = slot.borrow_mut();
+                        slot.latest = Some(result);
+                        slot.waiting.take()
+                    };
+                    match waiting {
+                        Some((_, handle)) => handle.resume(),
+                        None => force_update.force_update(),
+                    }
+                }
+            }
+            source.close();
+        }
+
+        yew::platform::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+/// Subscribe to a [`LinkedStream`].
+///
+/// On the server the stream is subscribed to locally via the
+/// [`Resolver`](crate::Resolver) of the ancestor [`LinkProvider`](crate::LinkProvider).
+/// The hook suspends until the first value arrives, which is embedded in the
+/// SSR HTML like a [`LinkedState`](crate::LinkedState).
+///
+/// On the client during hydration, the SSR-embedded value is displayed
+/// immediately. The hook then connects to the provider's `stream_endpoint` with
+/// server-sent events and re-renders the component with every new value. The
+/// connection is re-established automatically if it drops, and closed when the
+/// component is destroyed or `input` changes. Without an embedded value, the
+/// hook suspends until the first value arrives.
+///
+/// Native clients, enabled by the `client` feature, cannot receive streams
+/// yet: instead of suspending forever, the hook returns a
+/// [`LinkError::Internal`] right away.
+///
+/// # Panics
+///
+/// Panics if there is no ancestor [`LinkProvider`](crate::LinkProvider) in the
+/// component tree.
+#[hook]
+pub fn use_linked_stream<T: LinkedStream>(
+    input: T::Input,
+) -> SuspensionResult<LinkedStreamHandle<T>> {
+    #[cfg(any(feature = "ssr", target_arch = "wasm32"))]
+    let link_ctx =
+        use_context::<LinkContextInner>().expect("use_linked_stream requires a LinkProvider");
+
+    #[cfg(any(feature = "ssr", target_arch = "wasm32"))]
+    type Prepared<T> = Result<T, LinkError<Never>>;
+
+    #[cfg(feature = "ssr")]
+    {
+        let prepared = {
+            let link_ctx = link_ctx.clone();
+            yew::functional::use_prepared_state_with_suspension(
+                input,
+                move |input: Rc<T::Input>| {
+                    let link_ctx = link_ctx.clone();
+                    async move { link_ctx.first_streamed::<T>(&input).await }
+                },
+            )
+        }?;
+
+        let result: Rc<Prepared<T>> =
+            prepared.expect("prepared state should always be Some on SSR");
+        Ok(LinkedStreamHandle {
+            result: match result.as_ref() {
+                Ok(val) => Ok(Rc::new(val.clone())),
+                Err(e) => Err(e.clone()),
+            },
+        })
+    }
+
+    #[cfg(all(not(feature = "ssr"), not(target_arch = "wasm32")))]
+    {
+        let _ = input;
+        Ok(LinkedStreamHandle {
+            result: Err(LinkError::Internal(
+                "linked streams require the `ssr` feature (server) or a wasm32 target (client)"
+                    .into(),
+            )),
+        })
+    }
+
+    #[cfg(all(not(feature = "ssr"), target_arch = "wasm32"))]
+    {
+        let prepared = yew::functional::use_prepared_state::<Prepared<T>, T::Input>(input.clone())?;
+        let slot = use_ref(|| RefCell::new(StreamSlot::<T>::new()));
+        let force_update = use_force_update();
+
+        let mut state = slot.borrow_mut();
+
+        if state.input.as_ref() != Some(&input) {
+            state.latest = prepared.map(|result| match result.as_ref() {
+                Ok(val) => Ok(Rc::new(val.clone())),
+                Err(e) => Err(e.clone()),
+            });
+            state.waiting = None;
+            state.task = None;
+            state.input = Some(input.clone());
+
+            match link_ctx.stream_url::<T>(&input) {
+                Ok(url) => {
+                    let (abort_handle, registration) = futures::future::AbortHandle::new_pair();
+                    let listener = listen::<T>(url, Rc::downgrade(&slot), force_update);
+                    wasm_bindgen_futures::spawn_local(async move {
+                        let _ = futures::future::Abortable::new(listener, registration).await;
+                    });
+                    state.task = Some(StreamTask(abort_handle));
+                }
+                Err(e) => state.latest = Some(Err(e)),
+            }
+        }
+
+        if let Some(result) = state.latest.clone() {
+            return Ok(LinkedStreamHandle { result });
+        }
+
+        let (sus, _) = state.waiting.get_or_insert_with(Suspension::new);
+        Err(sus.clone())
+    }
+}
@@ -0,0 +1,153 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use futures::FutureExt;
+use tokio::test;
+use yew::prelude::*;
+
+use super::client::{local, render};
+use super::{Post, Recording, post};
+use crate::{
+    LinkContextInner, LinkError, LinkedAction, LinkedActionHandle, Resolver, cache_key,
+    use_linked_action,
+};
+
+/// Likes a post, which fails for the post `0`.
+struct Like;
+
+impl LinkedAction for Like {
+    type Error = String;
+    type Input = u32;
+    type Output = u32;
+
+    const TYPE_KEY: &'static str = "like";
+}
+
+fn likes() -> Recording {
+    Recording::new(
+        Resolver::new()
+            .register::<Post, _, _>(|id| async move { Ok(post(id)) })
+            .register_action::<Like, _, _>(|id| async move {
+                match id {
+                    0 => Err("no such post".to_string()),
+                    id => Ok(id + 1),
+                }
+            }),
+    )
+}
+
+type Slot = Rc<RefCell<Option<(LinkContextInner, LinkedActionHandle<Like>)>>>;
+
+#[derive(Properties)]
+struct CaptureProps {
+    slot: Slot,
+}
+
+impl PartialEq for CaptureProps {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.slot, &other.slot)
+    }
+}
+
+/// Stores the context of the provider and a handle to [`Like`] in `slot`.
+#[component]
+fn Capture(props: &CaptureProps) -> Html {
+    let ctx = use_context::<LinkContextInner>().unwrap();
+    let like = use_linked_action::<Like>();
+    *props.slot.borrow_mut() = Some((ctx, like));
+    Html::default()
+}
+
+async fn capture(transport: &Recording) -> (LinkContextInner, LinkedActionHandle<Like>) {
+    let slot = Slot::default();
+    render(transport, html! { <Capture slot={slot.clone()} /> }).await;
+    slot.take().expect("the provider renders its children")
+}
+
+#[test]
+async fn runs_are_pending_until_they_complete() {
+    let transport = likes();
+
+    local(async {
+        let (_, like) = capture(&transport).await;
+        assert_eq!(like.pending_runs(), 0);
+
+        let mut run = like.run(1).boxed_local();
+        assert!((&mut run).now_or_never().is_none());
+        assert_eq!(like.pending_runs(), 1);
+
+        assert_eq!(run.await.ok().as_deref(), Some(&2));
+        assert_eq!(like.pending_runs(), 0);
+    })
+    .await;
+}
+
+#[test]
+async fn dropped_runs_are_not_pending() {
+    let transport = likes();
+
+    local(async {
+        let (_, like) = capture(&transport).await;
+
+        let mut run = like.run(1).boxed_local();
+        assert!((&mut run).now_or_never().is_none());
+        drop(run);
+
+        assert_eq!(like.pending_runs(), 0);
+    })
+    .await;
+}
+
+#[test]
+async fn successful_runs_update_the_cache() {
+    let transport = likes();
+
+    local(async {
+        let (ctx, like) = capture(&transport).await;
+        ctx.spawn_fetch::<Post>(cache_key::<Post>(&1), 1).await;
+
+        let failed = like
+            .run_with(0, |_, _| panic!("failed runs do not update the cache"))
+            .await;
+        assert!(matches!(failed, Err(LinkError::Resolve(e)) if e == "no such post"));
+
+        like.run_with(1, |_, cache| cache.invalidate::<Post>(&1))
+            .await
+            .unwrap();
+        assert!(ctx.stale.borrow().contains(&cache_key::<Post>(&1)));
+    })
+    .await;
+}
+
+/// [`Like`] in the version the server has.
+struct LikeV2;
+
+impl LinkedAction for LikeV2 {
+    type Error = String;
+    type Input = u32;
+    type Output = u32;
+
+    const TYPE_KEY: &'static str = Like::TYPE_KEY;
+    const VERSION: u32 = 2;
+}
+
+#[test]
+async fn version_mismatches_are_not_run() {
+    let transport = Recording::new(
+        Resolver::new()
+            .register_action::<LikeV2, _, _>(|_| async { panic!("mismatched runs are not run") }),
+    );
+
+    local(async {
+        let (_, like) = capture(&transport).await;
+
+        assert!(matches!(
+            like.run(1).await,
+            Err(LinkError::VersionMismatch {
+                client: 0,
+                server: 2
+            })
+        ));
+    })
+    .await;
+}
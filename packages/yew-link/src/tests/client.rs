@@ -0,0 +1,373 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::task::LocalSet;
+use tokio::test;
+use yew::LocalServerRenderer;
+use yew::platform::time::sleep;
+use yew::prelude::*;
+use yew::suspense::{Suspension, use_future};
+
+use super::{Post, Recording, post};
+use crate::{
+    LinkCache, LinkContextInner, LinkError, LinkProvider, LinkedPage, LinkedState, Never, Resolver,
+    TransportProp, cache_key, use_link_prefetch, use_linked_pages, use_linked_state,
+};
+
+/// Runs `f` on a [`LocalSet`], which the tasks spawned by the provider need.
+pub(super) async fn local<F: Future>(f: F) -> F::Output {
+    LocalSet::new().run_until(f).await
+}
+
+/// Waits for the tasks spawned by the provider to settle.
+async fn settle() {
+    sleep(Duration::from_millis(10)).await;
+}
+
+#[derive(Properties, PartialEq)]
+struct AppProps {
+    transport: TransportProp,
+    children: Html,
+}
+
+#[component]
+fn App(props: &AppProps) -> Html {
+    html! {
+        <LinkProvider endpoint="/api/link" transport={props.transport.clone()}>
+            <Suspense fallback={html! { "loading" }}>
+                { props.children.clone() }
+            </Suspense>
+        </LinkProvider>
+    }
+}
+
+/// Renders `children` in a [`LinkProvider`] that sends its requests to `transport`.
+pub(super) async fn render(transport: &Recording, children: Html) -> String {
+    let props = AppProps {
+        transport: TransportProp(Rc::new(transport.clone())),
+        children,
+    };
+
+    LocalServerRenderer::<App>::with_props(props)
+        .hydratable(false)
+        .render()
+        .await
+}
+
+type Slot = Rc<RefCell<Option<LinkContextInner>>>;
+
+#[derive(Properties)]
+struct CaptureProps {
+    slot: Slot,
+}
+
+impl PartialEq for CaptureProps {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.slot, &other.slot)
+    }
+}
+
+/// Stores the context of the provider in `slot`.
+#[component]
+fn Capture(props: &CaptureProps) -> Html {
+    *props.slot.borrow_mut() = use_context::<LinkContextInner>();
+    Html::default()
+}
+
+/// Renders a provider that sends its requests to `transport` and returns its
+/// context.
+async fn capture(transport: &Recording) -> LinkContextInner {
+    let slot = Slot::default();
+    render(transport, html! { <Capture slot={slot.clone()} /> }).await;
+    slot.take().expect("the provider renders its children")
+}
+
+/// Fetches `T` for `input` into the cache of `ctx`.
+async fn fetch<T: LinkedState>(ctx: &LinkContextInner, input: T::Input) {
+    ctx.spawn_fetch::<T>(cache_key::<T>(&input), input).await;
+}
+
+/// Returns the value of `T` for `input` in the cache of `ctx`.
+fn cached<T: LinkedState>(ctx: &LinkContextInner, input: &T::Input) -> Option<T> {
+    let entry = ctx.cache.borrow().peek(&cache_key::<T>(input))?.clone();
+    serde_json::from_value::<Result<T, LinkError<T::Error>>>(entry.value)
+        .ok()?
+        .ok()
+}
+
+#[derive(Properties, PartialEq)]
+struct PostViewProps {
+    id: u32,
+}
+
+#[component]
+fn PostView(props: &PostViewProps) -> HtmlResult {
+    let post = use_linked_state::<Post>(props.id)?;
+
+    Ok(match post.as_result() {
+        Ok(post) => html! { { post.title.clone() } },
+        Err(e) => html! { { e.to_string() } },
+    })
+}
+
+fn posts() -> Recording {
+    Recording::new(Resolver::new().register::<Post, _, _>(|id| async move { Ok(post(id)) }))
+}
+
+#[test]
+async fn requests_of_a_render_are_batched() {
+    let transport = posts();
+
+    let s = local(render(
+        &transport,
+        html! { <PostView id={1} /><PostView id={2} /> },
+    ))
+    .await;
+
+    assert_eq!(s, "post 1post 2");
+    let bodies = transport.bodies();
+    assert_eq!(bodies.len(), 1);
+    let batch: Vec<serde_json::Value> = serde_json::from_slice(&bodies[0]).unwrap();
+    assert_eq!(batch.len(), 2);
+}
+
+#[derive(Properties, PartialEq)]
+struct PrefetchProps {
+    id: u32,
+}
+
+/// Prefetches the post `id` while it renders.
+#[component]
+fn Prefetch(props: &PrefetchProps) -> Html {
+    use_link_prefetch::<Post>().emit(props.id);
+    Html::default()
+}
+
+#[test]
+async fn prefetched_values_are_cached() {
+    let transport = posts();
+    let slot = Slot::default();
+
+    local(async {
+        render(
+            &transport,
+            html! {
+                <Capture slot={slot.clone()} />
+                <Prefetch id={1} />
+            },
+        )
+        .await;
+        settle().await;
+    })
+    .await;
+
+    let ctx = slot.take().unwrap();
+    assert_eq!(cached::<Post>(&ctx, &1), Some(post(1)));
+    assert_eq!(transport.bodies().len(), 1);
+}
+
+#[derive(Properties, PartialEq)]
+struct LaterProps {
+    children: Html,
+}
+
+/// Renders its children once the tasks spawned so far have settled.
+#[component]
+fn Later(props: &LaterProps) -> HtmlResult {
+    use_future(settle)?;
+    Ok(props.children.clone())
+}
+
+#[test]
+async fn prefetched_values_are_read_without_refetching() {
+    let transport = posts();
+
+    let s = local(render(
+        &transport,
+        html! {
+            <Prefetch id={1} />
+            <Later><PostView id={1} /></Later>
+        },
+    ))
+    .await;
+
+    assert_eq!(s, "post 1");
+    assert_eq!(transport.bodies().len(), 1);
+}
+
+/// [`Post`] in the version the server has.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct PostV2 {
+    title: String,
+}
+
+impl LinkedState for PostV2 {
+    type Error = Never;
+    type Input = u32;
+
+    const TYPE_KEY: &'static str = Post::TYPE_KEY;
+    const VERSION: u32 = 2;
+}
+
+#[test]
+async fn version_mismatch_is_returned_without_refetching() {
+    let transport = Recording::new(Resolver::new().register::<PostV2, _, _>(|id| async move {
+        Ok(PostV2 {
+            title: post(id).title,
+        })
+    }));
+
+    let s = local(render(&transport, html! { <PostView id={1} /> })).await;
+
+    assert_eq!(
+        s,
+        "the client has version 0 of this state, but the server has version 2"
+    );
+    assert_eq!(transport.bodies().len(), 1);
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Price(u32);
+
+impl LinkedState for Price {
+    type Error = Never;
+    type Input = u32;
+
+    const STALE_AFTER: Option<Duration> = Some(Duration::from_millis(50));
+    const TAGS: &'static [&'static str] = &["prices"];
+    const TYPE_KEY: &'static str = "price";
+}
+
+fn prices() -> Recording {
+    Recording::new(
+        Resolver::new()
+            .register::<Post, _, _>(|id| async move { Ok(post(id)) })
+            .register::<Price, _, _>(|id| async move { Ok(Price(id * 100)) }),
+    )
+}
+
+#[test]
+async fn values_are_refetched_once_they_are_stale() {
+    let transport = prices();
+
+    local(async {
+        let ctx = capture(&transport).await;
+        fetch::<Price>(&ctx, 1).await;
+        assert_eq!(transport.bodies().len(), 1);
+
+        ctx.prefetch::<Price>(1);
+        settle().await;
+        assert_eq!(transport.bodies().len(), 1);
+
+        sleep(Duration::from_millis(60)).await;
+        ctx.prefetch::<Price>(1);
+        settle().await;
+        assert_eq!(transport.bodies().len(), 2);
+    })
+    .await;
+}
+
+#[test]
+async fn invalidating_a_tag_marks_its_values_as_stale() {
+    let transport = prices();
+
+    local(async {
+        let ctx = capture(&transport).await;
+        fetch::<Price>(&ctx, 1).await;
+        fetch::<Post>(&ctx, 1).await;
+
+        LinkCache { inner: ctx.clone() }.invalidate_tag("prices");
+
+        let stale = ctx.stale.borrow();
+        assert!(stale.contains(&cache_key::<Price>(&1)));
+        assert!(!stale.contains(&cache_key::<Post>(&1)));
+    })
+    .await;
+}
+
+#[test]
+async fn optimistic_updates_are_rolled_back() {
+    let transport = posts();
+
+    local(async {
+        let ctx = capture(&transport).await;
+        fetch::<Post>(&ctx, 1).await;
+
+        let update = ctx.mutate::<Post>(
+            cache_key::<Post>(&1),
+            Box::new(|post| post.title = "draft".into()),
+        );
+        assert_eq!(cached::<Post>(&ctx, &1).unwrap().title, "draft");
+        update.rollback();
+        assert_eq!(cached::<Post>(&ctx, &1), Some(post(1)));
+
+        // A refresh that does not reach the server rolls back as well.
+        ctx.mutate::<Post>(
+            cache_key::<Post>(&1),
+            Box::new(|post| post.title = "draft".into()),
+        );
+        ctx.store::<Post>(
+            cache_key::<Post>(&1),
+            &Err(LinkError::Internal("offline".into())),
+        );
+        assert_eq!(cached::<Post>(&ctx, &1), Some(post(1)));
+    })
+    .await;
+}
+
+/// The page of the numbers `[2n, 2n + 1]` for the input `n`, up to 5.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Page {
+    items: Vec<u32>,
+    next: Option<u32>,
+}
+
+impl LinkedState for Page {
+    type Error = Never;
+    type Input = u32;
+
+    const TYPE_KEY: &'static str = "page";
+}
+
+impl LinkedPage for Page {
+    fn next_page(&self, _input: &u32) -> Option<u32> {
+        self.next
+    }
+}
+
+/// Loads a second page on its first render.
+#[component]
+fn Feed() -> HtmlResult {
+    let feed = use_linked_pages::<Page>(0)?;
+    let loaded_more = use_mut_ref(|| false);
+
+    if !loaded_more.replace(true) {
+        feed.load_more();
+        return Err(Suspension::from_future(settle()).into());
+    }
+
+    let items: Vec<String> = feed
+        .pages()
+        .iter()
+        .flat_map(|page| &page.items)
+        .map(u32::to_string)
+        .collect();
+    Ok(html! { { items.join(",") } })
+}
+
+#[test]
+async fn pages_are_merged() {
+    let transport = Recording::new(Resolver::new().register::<Page, _, _>(|n| async move {
+        Ok(Page {
+            items: vec![2 * n, 2 * n + 1],
+            next: (n < 2).then_some(n + 1),
+        })
+    }));
+
+    let s = local(render(&transport, html! { <Feed /> })).await;
+
+    assert_eq!(s, "0,1,2,3");
+    assert_eq!(transport.bodies().len(), 2);
+}
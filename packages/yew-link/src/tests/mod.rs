@@ -0,0 +1,85 @@
+//! Native tests of yew-link.
+//!
+//! The client is rendered with yew's `LocalServerRenderer`, which runs the
+//! client code paths when the `client` feature is enabled without `ssr`, and
+//! reaches the [`Resolver`] through a [`ResolverTransport`]. The resolver and
+//! the services built on it are tested directly, and the server side of the
+//! hooks with the `ssr` feature.
+
+#[cfg(all(feature = "client", not(feature = "ssr")))]
+mod action;
+#[cfg(all(feature = "client", not(feature = "ssr")))]
+mod client;
+mod resolver;
+#[cfg(all(feature = "client", not(feature = "ssr")))]
+mod storage;
+mod stream;
+
+#[cfg(all(feature = "client", not(feature = "ssr")))]
+use std::cell::RefCell;
+#[cfg(all(feature = "client", not(feature = "ssr")))]
+use std::rc::Rc;
+#[cfg(all(feature = "client", not(feature = "ssr")))]
+use std::sync::Arc;
+
+#[cfg(all(feature = "client", not(feature = "ssr")))]
+use futures::future::LocalBoxFuture;
+use serde::{Deserialize, Serialize};
+
+#[cfg(all(feature = "client", not(feature = "ssr")))]
+use crate::{LinkTransport, Resolver, ResolverTransport};
+use crate::{LinkedState, Never};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct Post {
+    pub title: String,
+}
+
+impl LinkedState for Post {
+    type Error = Never;
+    type Input = u32;
+
+    const TYPE_KEY: &'static str = "post";
+}
+
+pub(crate) fn post(id: u32) -> Post {
+    Post {
+        title: format!("post {id}"),
+    }
+}
+
+/// A [`ResolverTransport`] that records the bodies it sends.
+#[cfg(all(feature = "client", not(feature = "ssr")))]
+#[derive(Clone)]
+pub(crate) struct Recording {
+    transport: ResolverTransport,
+    bodies: Rc<RefCell<Vec<Vec<u8>>>>,
+}
+
+#[cfg(all(feature = "client", not(feature = "ssr")))]
+impl Recording {
+    pub fn new(resolver: Resolver) -> Self {
+        Self {
+            transport: ResolverTransport::new(Arc::new(resolver)),
+            bodies: Rc::default(),
+        }
+    }
+
+    /// Returns the bodies sent so far.
+    pub fn bodies(&self) -> Vec<Vec<u8>> {
+        self.bodies.borrow().clone()
+    }
+}
+
+#[cfg(all(feature = "client", not(feature = "ssr")))]
+impl LinkTransport for Recording {
+    fn post(
+        &self,
+        endpoint: &str,
+        content_type: &'static str,
+        body: Vec<u8>,
+    ) -> LocalBoxFuture<'static, Result<Vec<u8>, String>> {
+        self.bodies.borrow_mut().push(body.clone());
+        self.transport.post(endpoint, content_type, body)
+    }
+}
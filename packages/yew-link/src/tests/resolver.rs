@@ -0,0 +1,187 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tokio::test;
+
+use super::{Post, post};
+use crate::{
+    FromRequestContext, LinkClient, LinkCodec, LinkError, LinkedState, Never, RequestContext,
+    Resolver, ResolverTransport,
+};
+
+/// A linked state whose resolver fails for odd inputs.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Even(u32);
+
+impl LinkedState for Even {
+    type Error = String;
+    type Input = u32;
+
+    const TYPE_KEY: &'static str = "even";
+}
+
+fn resolver() -> Resolver {
+    Resolver::new()
+        .register::<Post, _, _>(|id| async move { Ok(post(id)) })
+        .register::<Even, _, _>(|n| async move {
+            if n % 2 == 0 {
+                Ok(Even(n))
+            } else {
+                Err(format!("{n} is odd"))
+            }
+        })
+}
+
+fn client(codec: LinkCodec) -> LinkClient {
+    LinkClient::new("/api/link", ResolverTransport::new(Arc::new(resolver()))).codec(codec)
+}
+
+/// Fetches a value and an error with `codec`.
+async fn round_trip(codec: LinkCodec) {
+    let client = client(codec);
+
+    assert_eq!(client.fetch::<Post>(&1).await.ok(), Some(post(1)));
+    assert_eq!(client.fetch::<Even>(&2).await.ok(), Some(Even(2)));
+    assert!(matches!(
+        client.fetch::<Even>(&3).await,
+        Err(LinkError::Resolve(e)) if e == "3 is odd"
+    ));
+}
+
+#[test]
+async fn json_round_trip() {
+    round_trip(LinkCodec::Json).await;
+}
+
+#[cfg(feature = "bincode")]
+#[test]
+async fn bincode_round_trip() {
+    round_trip(LinkCodec::Bincode).await;
+}
+
+#[cfg(feature = "postcard")]
+#[test]
+async fn postcard_round_trip() {
+    round_trip(LinkCodec::Postcard).await;
+}
+
+#[test]
+#[should_panic(expected = "`post` is registered twice")]
+async fn duplicate_type_key_panics() {
+    let _ = resolver().register::<Post, _, _>(|id| async move { Ok(post(id)) });
+}
+
+/// The tenant a request is made for, from its `X-Tenant` header, `tenant`
+/// cookie or [`Tenant`] extension, in that order.
+#[derive(Clone)]
+struct Tenant(Option<String>);
+
+impl FromRequestContext for Tenant {
+    fn from_request_context(req: &RequestContext) -> Self {
+        let tenant = req
+            .header("x-tenant")
+            .or_else(|| req.cookie("tenant"))
+            .map(str::to_owned)
+            .or_else(|| {
+                req.extension::<Tenant>()
+                    .and_then(|tenant| tenant.0.clone())
+            });
+        Self(tenant)
+    }
+}
+
+async fn fetch_as(request: RequestContext) -> Result<Post, LinkError<Never>> {
+    let resolver = Resolver::new().register_with::<Post, Tenant, _, _>(|id, tenant| async move {
+        Ok(Post {
+            title: format!(
+                "{} for {}",
+                post(id).title,
+                tenant.0.as_deref().unwrap_or("nobody")
+            ),
+        })
+    });
+    let transport = ResolverTransport::new(Arc::new(resolver)).with_request(request);
+
+    LinkClient::new("/api/link", transport)
+        .fetch::<Post>(&1)
+        .await
+}
+
+#[test]
+async fn extractors_see_the_request() {
+    let title = |request| async { fetch_as(request).await.ok().map(|post| post.title) };
+
+    assert_eq!(
+        title(RequestContext::new()).await,
+        Some("post 1 for nobody".to_string())
+    );
+    assert_eq!(
+        title(RequestContext::new().with_header("X-Tenant", "acme")).await,
+        Some("post 1 for acme".to_string())
+    );
+    assert_eq!(
+        title(RequestContext::new().with_header("Cookie", "theme=dark; tenant=globex")).await,
+        Some("post 1 for globex".to_string())
+    );
+    assert_eq!(
+        title(RequestContext::new().with_extension(Tenant(Some("initech".into())))).await,
+        Some("post 1 for initech".to_string())
+    );
+}
+
+#[cfg(feature = "tower")]
+mod service {
+    use std::sync::Arc;
+
+    use bytes::Bytes;
+    use http::{Request, header};
+    use http_body_util::{BodyExt, Full};
+    use tokio::test;
+
+    use super::resolver;
+    use crate::tower::LinkService;
+
+    /// Posts `body` as JSON and returns the status code of the response.
+    async fn status(body: &'static str) -> u16 {
+        let req = Request::post("/api/link")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Full::new(Bytes::from_static(body.as_bytes())))
+            .unwrap();
+        let resp = LinkService::new(Arc::new(resolver())).handle(req).await;
+        let status = resp.status().as_u16();
+        // The body is always readable.
+        resp.into_body().collect().await.unwrap();
+        status
+    }
+
+    #[test]
+    async fn ok() {
+        assert_eq!(status(r#"{"type_key":"post","input":1}"#).await, 200);
+    }
+
+    #[test]
+    async fn malformed_body_is_a_bad_request() {
+        assert_eq!(status(r#"{"type_key":"post""#).await, 400);
+    }
+
+    #[test]
+    async fn version_mismatch_is_a_conflict() {
+        assert_eq!(
+            status(r#"{"type_key":"post","version":1,"input":1}"#).await,
+            409
+        );
+    }
+
+    #[test]
+    async fn resolve_error_is_unprocessable() {
+        assert_eq!(status(r#"{"type_key":"even","input":3}"#).await, 422);
+    }
+
+    #[test]
+    async fn batches_are_ok() {
+        assert_eq!(
+            status(r#"[{"type_key":"even","input":3},{"type_key":"post","input":1}]"#).await,
+            200
+        );
+    }
+}
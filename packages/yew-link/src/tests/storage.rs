@@ -0,0 +1,141 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use serde_json::json;
+
+use super::Post;
+use crate::{LinkPersistence, LinkStorage, LinkedState, Never};
+
+/// A [`LinkStorage`] kept in memory, shared between its clones.
+#[derive(Clone, Default)]
+struct Memory(Rc<RefCell<BTreeMap<String, String>>>);
+
+impl Memory {
+    fn keys_sorted(&self) -> Vec<String> {
+        self.0.borrow().keys().cloned().collect()
+    }
+}
+
+impl LinkStorage for Memory {
+    fn get(&self, key: &str) -> Option<String> {
+        self.0.borrow().get(key).cloned()
+    }
+
+    fn set(&self, key: &str, value: &str) {
+        self.0
+            .borrow_mut()
+            .insert(key.to_string(), value.to_string());
+    }
+
+    fn remove(&self, key: &str) {
+        self.0.borrow_mut().remove(key);
+    }
+
+    fn keys(&self) -> Vec<String> {
+        self.0.borrow().keys().cloned().collect()
+    }
+}
+
+/// [`Post`] after a change to its shape.
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+struct PostV2;
+
+impl LinkedState for PostV2 {
+    type Error = Never;
+    type Input = u32;
+
+    const TYPE_KEY: &'static str = "post";
+    const VERSION: u32 = 2;
+}
+
+#[test]
+fn saved_values_are_loaded() {
+    let storage = Memory::default();
+    let persistence = LinkPersistence::new(storage.clone());
+
+    persistence.save::<Post>(&1, &json!({ "title": "one" }), 10.0, 8);
+
+    assert_eq!(
+        persistence.load::<Post>(&1),
+        Some((json!({ "title": "one" }), 10.0))
+    );
+    assert_eq!(persistence.load::<Post>(&2), None);
+    assert_eq!(storage.keys_sorted(), ["yew-link:post@0:1"]);
+}
+
+#[test]
+fn values_of_other_state_versions_are_not_loaded() {
+    let persistence = LinkPersistence::new(Memory::default());
+
+    persistence.save::<Post>(&1, &json!({ "title": "one" }), 10.0, 8);
+
+    assert_eq!(persistence.load::<PostV2>(&1), None);
+}
+
+#[test]
+fn unreadable_values_are_not_loaded() {
+    let storage = Memory::default();
+    let persistence = LinkPersistence::new(storage.clone());
+
+    storage.set("yew-link:post@0:1", "not json");
+
+    assert_eq!(persistence.load::<Post>(&1), None);
+}
+
+#[test]
+fn least_recently_fetched_values_are_evicted() {
+    let storage = Memory::default();
+    let persistence = LinkPersistence::new(storage.clone());
+    persistence.migrate();
+
+    persistence.save::<Post>(&1, &json!(1), 30.0, 2);
+    persistence.save::<Post>(&2, &json!(2), 10.0, 2);
+    persistence.save::<Post>(&3, &json!(3), 20.0, 2);
+
+    assert_eq!(persistence.load::<Post>(&2), None);
+    assert_eq!(
+        storage.keys_sorted(),
+        ["yew-link:post@0:1", "yew-link:post@0:3", "yew-link:version"]
+    );
+}
+
+#[test]
+fn unreadable_values_are_evicted_first() {
+    let storage = Memory::default();
+    let persistence = LinkPersistence::new(storage.clone());
+    storage.set("yew-link:post@0:9", "not json");
+    storage.set("other", "kept");
+
+    persistence.save::<Post>(&1, &json!(1), 10.0, 1);
+
+    assert_eq!(storage.keys_sorted(), ["other", "yew-link:post@0:1"]);
+}
+
+#[test]
+fn migrating_to_another_version_drops_stored_values() {
+    let storage = Memory::default();
+    LinkPersistence::new(storage.clone()).migrate();
+    LinkPersistence::new(storage.clone()).save::<Post>(&1, &json!(1), 10.0, 8);
+    storage.set("other", "kept");
+
+    let persistence = LinkPersistence::new(storage.clone()).version(1);
+    persistence.migrate();
+
+    assert_eq!(persistence.load::<Post>(&1), None);
+    assert_eq!(storage.keys_sorted(), ["other", "yew-link:version"]);
+    assert_eq!(storage.get("yew-link:version").as_deref(), Some("1"));
+}
+
+#[test]
+fn migrating_to_the_same_version_keeps_stored_values() {
+    let storage = Memory::default();
+    let persistence = LinkPersistence::new(storage.clone()).prefix("app:");
+    persistence.migrate();
+    persistence.save::<Post>(&1, &json!(1), 10.0, 8);
+
+    persistence.migrate();
+
+    assert_eq!(persistence.load::<Post>(&1), Some((json!(1), 10.0)));
+    assert_eq!(storage.keys_sorted(), ["app:post@0:1", "app:version"]);
+}
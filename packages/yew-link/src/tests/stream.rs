@@ -0,0 +1,310 @@
+use futures::stream::{self, BoxStream, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::test;
+
+use crate::{LinkRequest, LinkedStream, LinkedStreamResolve, RequestContext, Resolver};
+
+/// Counts from its input up to the limit in its context.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Count(u32);
+
+impl LinkedStream for Count {
+    type Input = u32;
+
+    const TYPE_KEY: &'static str = "count";
+}
+
+impl LinkedStreamResolve for Count {
+    type Context = u32;
+    type Extract = RequestContext;
+
+    fn subscribe(limit: &u32, from: &u32, request: RequestContext) -> BoxStream<'static, Self> {
+        // A `step` header counts in larger steps.
+        let step = request
+            .header("step")
+            .and_then(|step| step.parse().ok())
+            .unwrap_or(1);
+        stream::iter((*from..*limit).step_by(step).map(Count)).boxed()
+    }
+}
+
+/// A stream that never yields a value.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct Silent;
+
+impl LinkedStream for Silent {
+    type Input = ();
+
+    const TYPE_KEY: &'static str = "silent";
+}
+
+fn resolver() -> Resolver {
+    Resolver::new()
+        .register_linked_stream::<Count>(4)
+        .register_stream::<Silent, _, _>(|()| stream::empty())
+}
+
+fn count(from: u32) -> LinkRequest {
+    LinkRequest {
+        type_key: Count::TYPE_KEY.to_string(),
+        version: 0,
+        input: serde_json::json!(from),
+    }
+}
+
+#[test]
+async fn subscriptions_yield_every_value() {
+    let values: Vec<_> = resolver().subscribe(&count(1)).unwrap().collect().await;
+
+    assert_eq!(values, [1, 2, 3]);
+}
+
+#[test]
+async fn subscriptions_see_the_request() {
+    let request = RequestContext::new().with_header("step", "2");
+    let values: Vec<_> = resolver()
+        .subscribe_with(&count(0), &request)
+        .unwrap()
+        .collect()
+        .await;
+
+    assert_eq!(values, [0, 2]);
+}
+
+#[test]
+async fn unknown_streams_are_not_subscribed() {
+    let req = LinkRequest {
+        type_key: "unknown".to_string(),
+        version: 0,
+        input: serde_json::Value::Null,
+    };
+
+    assert_eq!(
+        resolver().subscribe(&req).err().as_deref(),
+        Some("no stream registered for unknown")
+    );
+}
+
+#[test]
+async fn invalid_inputs_are_not_subscribed() {
+    let req = LinkRequest {
+        input: serde_json::json!("one"),
+        ..count(0)
+    };
+
+    assert!(
+        resolver()
+            .subscribe(&req)
+            .is_err_and(|e| e.starts_with("failed to deserialize input"))
+    );
+}
+
+#[cfg(feature = "axum")]
+mod axum {
+    use std::sync::Arc;
+
+    use axum::extract::{Query, State};
+    use axum::http::{Request, StatusCode};
+    use tokio::test;
+
+    use super::resolver;
+    use crate::LinkStreamQuery;
+    use crate::axum::linked_stream_handler;
+
+    async fn get(type_key: &str, input: &str) -> (StatusCode, String) {
+        let (parts, ()) = Request::get("/api/link/stream")
+            .header("step", "2")
+            .body(())
+            .unwrap()
+            .into_parts();
+        let query = LinkStreamQuery {
+            type_key: type_key.to_string(),
+            input: input.to_string(),
+        };
+        let response =
+            linked_stream_handler(State(Arc::new(resolver())), parts, Query(query)).await;
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[test]
+    async fn values_are_sent_as_events() {
+        assert_eq!(
+            get("count", "0").await,
+            (StatusCode::OK, "data: 0\n\ndata: 2\n\n".to_string())
+        );
+    }
+
+    #[test]
+    async fn unknown_streams_are_unprocessable() {
+        assert_eq!(
+            get("unknown", "null").await,
+            (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "no stream registered for unknown".to_string()
+            )
+        );
+    }
+}
+
+#[cfg(feature = "actix")]
+mod actix {
+    use actix_web::http::StatusCode;
+    use actix_web::http::header::CONTENT_TYPE;
+    use actix_web::test::TestRequest;
+    use actix_web::web::{Data, Query};
+
+    use super::resolver;
+    use crate::LinkStreamQuery;
+    use crate::actix::linked_stream_handler;
+
+    async fn get(type_key: &str, input: &str) -> (StatusCode, Option<String>, String) {
+        let request = TestRequest::get()
+            .insert_header(("step", "2"))
+            .to_http_request();
+        let query = LinkStreamQuery {
+            type_key: type_key.to_string(),
+            input: input.to_string(),
+        };
+        let response = linked_stream_handler(Data::new(resolver()), request, Query(query)).await;
+        let status = response.status();
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let body = actix_web::body::to_bytes(response.into_body())
+            .await
+            .unwrap();
+        (
+            status,
+            content_type,
+            String::from_utf8(body.to_vec()).unwrap(),
+        )
+    }
+
+    #[actix_web::test]
+    async fn values_are_sent_as_events() {
+        assert_eq!(
+            get("count", "0").await,
+            (
+                StatusCode::OK,
+                Some("text/event-stream".to_string()),
+                "data: 0\n\ndata: 2\n\n".to_string()
+            )
+        );
+    }
+
+    #[actix_web::test]
+    async fn unknown_streams_are_unprocessable() {
+        let (status, _, body) = get("unknown", "null").await;
+
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(body, "no stream registered for unknown");
+    }
+}
+
+#[cfg(feature = "ssr")]
+mod ssr {
+    use tokio::task::LocalSet;
+    use tokio::test;
+    use yew::LocalServerRenderer;
+    use yew::prelude::*;
+
+    use super::{Count, Silent, resolver};
+    use crate::{LinkProvider, LinkedStream, ResolverProp, use_linked_stream};
+
+    #[derive(Properties, PartialEq)]
+    struct LatestProps {
+        from: u32,
+    }
+
+    /// Renders the first value of [`Count`], or its error.
+    #[component]
+    fn Latest(props: &LatestProps) -> HtmlResult {
+        let count = use_linked_stream::<Count>(props.from)?;
+        Ok(match count.as_result() {
+            Ok(count) => html! { { count.0 } },
+            Err(e) => html! { { e.to_string() } },
+        })
+    }
+
+    #[component]
+    fn Quiet() -> HtmlResult {
+        let silent = use_linked_stream::<Silent>(())?;
+        Ok(
+            html! { { silent.as_result().as_ref().err().map(ToString::to_string).unwrap_or_default() } },
+        )
+    }
+
+    #[derive(Properties, PartialEq)]
+    struct AppProps {
+        children: Html,
+    }
+
+    #[component]
+    fn App(props: &AppProps) -> Html {
+        html! {
+            <LinkProvider endpoint="/api/link" resolver={ResolverProp::from(resolver())}>
+                <Suspense fallback={html! { "loading" }}>
+                    { props.children.clone() }
+                </Suspense>
+            </LinkProvider>
+        }
+    }
+
+    async fn render(children: Html) -> String {
+        let renderer =
+            LocalServerRenderer::<App>::with_props(AppProps { children }).hydratable(false);
+        LocalSet::new().run_until(renderer.render()).await
+    }
+
+    #[test]
+    async fn the_first_value_is_rendered_and_embedded() {
+        let html = render(html! { <Latest from={2} /> }).await;
+
+        assert!(
+            html.starts_with(r#"2<script type="application/x-yew-comp-state">"#),
+            "{html}"
+        );
+    }
+
+    #[test]
+    async fn streams_without_values_are_errors() {
+        assert!(render(html! { <Quiet /> }).await.contains(&format!(
+            "stream {} ended before yielding a value",
+            Silent::TYPE_KEY
+        )));
+    }
+}
+
+#[cfg(all(feature = "client", not(feature = "ssr")))]
+mod client {
+    use tokio::test;
+    use yew::prelude::*;
+
+    use super::super::Recording;
+    use super::super::client::{local, render};
+    use super::{Count, resolver};
+    use crate::use_linked_stream;
+
+    #[component]
+    fn Latest() -> HtmlResult {
+        let count = use_linked_stream::<Count>(0)?;
+        Ok(
+            html! { { count.as_result().as_ref().err().map(ToString::to_string).unwrap_or_default() } },
+        )
+    }
+
+    #[test]
+    async fn native_clients_report_an_error() {
+        let transport = Recording::new(resolver());
+        let html = local(render(&transport, html! { <Latest /> })).await;
+
+        assert!(html.contains("linked streams require"), "{html}");
+        assert!(transport.bodies().is_empty());
+    }
+}
@@ -0,0 +1,359 @@
+use std::fmt;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use futures::future::LocalBoxFuture;
+
+use crate::{
+    BinaryLinkRequest, BinaryLinkResponse, Encoded, LinkCodec, LinkError, LinkRequest,
+    LinkResponse, LinkedAction, LinkedState, Reply, RequestContext, Resolver,
+};
+
+/// Carries encoded requests to the linked-state endpoint.
+///
+/// On wasm32 targets the [`LinkProvider`](crate::LinkProvider) uses the
+/// browser's `fetch` unless another transport is passed with its `transport`
+/// prop. Native clients, enabled by the `client` feature, must always pass one:
+/// a wrapper around an HTTP client, or a [`ResolverTransport`] that skips the
+/// network entirely.
+///
+/// ```
+/// use futures::future::{FutureExt, LocalBoxFuture};
+/// use yew_link::LinkTransport;
+///
+/// struct Offline;
+///
+/// impl LinkTransport for Offline {
+///     fn post(
+///         &self,
+///         _endpoint: &str,
+///         _content_type: &'static str,
+///         _body: Vec<u8>,
+///     ) -> LocalBoxFuture<'static, Result<Vec<u8>, String>> {
+///         async { Err("offline".to_string()) }.boxed_local()
+///     }
+/// }
+/// ```
+pub trait LinkTransport: 'static {
+    /// Sends a `POST` request to `endpoint` with a `body` of the media type
+    /// `content_type`, and returns the body of the response.
+    ///
+    /// The response body is expected regardless of its status code, as the
+    /// endpoint reports failed requests in the body.
+    fn post(
+        &self,
+        endpoint: &str,
+        content_type: &'static str,
+        body: Vec<u8>,
+    ) -> LocalBoxFuture<'static, Result<Vec<u8>, String>>;
+}
+
+/// A [`LinkTransport`] for the `transport` prop of
+/// [`LinkProvider`](crate::LinkProvider).
+#[derive(Clone)]
+pub struct TransportProp(pub Rc<dyn LinkTransport>);
+
+impl fmt::Debug for TransportProp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("TransportProp(..)")
+    }
+}
+
+impl PartialEq for TransportProp {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl<T: LinkTransport> From<T> for TransportProp {
+    fn from(transport: T) -> Self {
+        Self(Rc::new(transport))
+    }
+}
+
+/// A [`LinkTransport`] that resolves requests in-process with a [`Resolver`].
+///
+/// Requests are encoded, batched and decoded exactly as they would be over the
+/// network, so tests can exercise the full client path without a server or a
+/// browser.
+///
+/// ```
+/// use std::sync::Arc;
+///
+/// use yew_link::{LinkClient, Resolver, ResolverTransport};
+///
+/// let resolver = Arc::new(Resolver::new());
+/// let client = LinkClient::new("/api/link", ResolverTransport::new(resolver));
+/// # let _ = client;
+/// ```
+#[derive(Clone, Debug)]
+pub struct ResolverTransport {
+    resolver: Arc<Resolver>,
+    request: RequestContext,
+}
+
+impl ResolverTransport {
+    /// Creates a transport that resolves requests with `resolver`.
+    pub fn new(resolver: Arc<Resolver>) -> Self {
+        Self {
+            resolver,
+            request: RequestContext::default(),
+        }
+    }
+
+    /// Sets the request the resolvers see. Defaults to an empty
+    /// [`RequestContext`].
+    pub fn with_request(mut self, request: RequestContext) -> Self {
+        self.request = request;
+        self
+    }
+}
+
+impl LinkTransport for ResolverTransport {
+    fn post(
+        &self,
+        _endpoint: &str,
+        content_type: &'static str,
+        body: Vec<u8>,
+    ) -> LocalBoxFuture<'static, Result<Vec<u8>, String>> {
+        let transport = self.clone();
+        Box::pin(async move {
+            let codec = LinkCodec::from_content_type(content_type)
+                .ok_or_else(|| format!("unsupported content type {content_type}"))?;
+            let (_, body) = transport
+                .resolver
+                .resolve_body_with(codec, &body, &transport.request)
+                .await;
+            Ok(body)
+        })
+    }
+}
+
+/// The default [`LinkTransport`] on wasm32 targets, which uses the browser's
+/// `fetch`.
+#[cfg(target_arch = "wasm32")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FetchTransport;
+
+#[cfg(target_arch = "wasm32")]
+impl LinkTransport for FetchTransport {
+    fn post(
+        &self,
+        endpoint: &str,
+        content_type: &'static str,
+        body: Vec<u8>,
+    ) -> LocalBoxFuture<'static, Result<Vec<u8>, String>> {
+        let request = gloo_net::http::Request::post(endpoint)
+            .header("Content-Type", content_type)
+            .header("Accept", content_type)
+            .body(js_sys::Uint8Array::from(body.as_slice()));
+        Box::pin(async move {
+            request
+                .map_err(|e| e.to_string())?
+                .send()
+                .await
+                .map_err(|e| e.to_string())?
+                .binary()
+                .await
+                .map_err(|e| e.to_string())
+        })
+    }
+}
+
+/// The transport a [`LinkProvider`](crate::LinkProvider) uses when none is
+/// passed.
+#[cfg(all(not(feature = "ssr"), target_arch = "wasm32"))]
+pub(crate) fn default_transport() -> Option<Rc<dyn LinkTransport>> {
+    Some(Rc::new(FetchTransport))
+}
+
+#[cfg(all(not(feature = "ssr"), not(target_arch = "wasm32"), feature = "client"))]
+pub(crate) fn default_transport() -> Option<Rc<dyn LinkTransport>> {
+    None
+}
+
+/// Sends `reqs` to `endpoint` in one round trip and returns their replies in
+/// the same order.
+///
+/// Each request is made of the type key, the version and the input of the
+/// linked state or action.
+///
+/// With the JSON codec a single request is sent as a plain [`LinkRequest`].
+/// The binary codecs always send a list of [`BinaryLinkRequest`]s.
+pub(crate) async fn send(
+    transport: &dyn LinkTransport,
+    endpoint: &str,
+    codec: LinkCodec,
+    reqs: Vec<(String, u32, Encoded)>,
+) -> Result<Vec<Reply>, String> {
+    let count = reqs.len();
+    let results = if codec == LinkCodec::Json {
+        let reqs: Vec<LinkRequest> = reqs
+            .into_iter()
+            .map(|(type_key, version, input)| {
+                Ok(LinkRequest {
+                    type_key,
+                    version,
+                    input: input.into_json()?,
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        let body = match reqs.as_slice() {
+            [req] => serde_json::to_vec(req),
+            _ => serde_json::to_vec(&reqs),
+        }
+        .map_err(|e| e.to_string())?;
+        let body = transport.post(endpoint, codec.content_type(), body).await?;
+        let resps: Vec<LinkResponse> = if count == 1 {
+            vec![serde_json::from_slice(&body).map_err(|e| e.to_string())?]
+        } else {
+            serde_json::from_slice(&body).map_err(|e| e.to_string())?
+        };
+
+        resps
+            .into_iter()
+            .map(|resp| match (resp.ok, resp.error, resp.version_mismatch) {
+                (Some(val), ..) => Ok(Reply::Ok(Encoded::Json(val))),
+                (None, Some(err_val), _) => Ok(Reply::Err(Encoded::Json(err_val))),
+                (None, None, Some(mismatch)) => Ok(Reply::VersionMismatch {
+                    client: mismatch.client,
+                    server: mismatch.server,
+                }),
+                (None, None, None) => Err("unknown error".to_string()),
+            })
+            .collect::<Result<Vec<_>, _>>()?
+    } else {
+        let reqs: Vec<BinaryLinkRequest> = reqs
+            .into_iter()
+            .map(|(type_key, version, input)| {
+                Ok(BinaryLinkRequest {
+                    type_key,
+                    version,
+                    input: input.into_bytes()?,
+                })
+            })
+            .collect::<Result<_, String>>()?;
+        let body = codec.encode_to_vec(&reqs)?;
+        let body = transport.post(endpoint, codec.content_type(), body).await?;
+
+        codec
+            .decode_from_slice::<Vec<BinaryLinkResponse>>(&body)?
+            .into_iter()
+            .map(|resp| match resp {
+                BinaryLinkResponse::Ok(val) => Reply::Ok(Encoded::Binary(val)),
+                BinaryLinkResponse::Error(err_val) => Reply::Err(Encoded::Binary(err_val)),
+                BinaryLinkResponse::VersionMismatch { client, server } => {
+                    Reply::VersionMismatch { client, server }
+                }
+            })
+            .collect()
+    };
+
+    if results.len() != count {
+        return Err(format!(
+            "expected {count} responses in batch, got {}",
+            results.len()
+        ));
+    }
+    Ok(results)
+}
+
+/// Fetches linked states and runs linked actions outside of a component tree.
+///
+/// Useful for CLI tools and other native programs that talk to the same
+/// endpoint as the app. Every call is a separate round trip; there is no cache.
+///
+/// ```no_run
+/// # use serde::{Deserialize, Serialize};
+/// # use yew_link::{LinkedState, Never};
+/// # use futures::future::{FutureExt, LocalBoxFuture};
+/// # #[derive(Clone, Debug, Serialize, Deserialize)]
+/// # struct Post { title: String }
+/// # impl LinkedState for Post {
+/// #     type Error = Never;
+/// #     type Input = u32;
+/// #     const TYPE_KEY: &'static str = "Post";
+/// # }
+/// # struct HttpTransport;
+/// # impl yew_link::LinkTransport for HttpTransport {
+/// #     fn post(&self, _: &str, _: &'static str, _: Vec<u8>) -> LocalBoxFuture<'static, Result<Vec<u8>, String>> {
+/// #         async { unimplemented!() }.boxed_local()
+/// #     }
+/// # }
+/// # async fn run() {
+/// use yew_link::LinkClient;
+///
+/// let client = LinkClient::new("https://example.com/api/link", HttpTransport);
+/// let post = client.fetch::<Post>(&1).await;
+/// # let _ = post;
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct LinkClient {
+    endpoint: String,
+    codec: LinkCodec,
+    transport: Rc<dyn LinkTransport>,
+}
+
+impl fmt::Debug for LinkClient {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LinkClient")
+            .field("endpoint", &self.endpoint)
+            .field("codec", &self.codec)
+            .finish_non_exhaustive()
+    }
+}
+
+impl LinkClient {
+    /// Creates a client that sends requests to `endpoint` through `transport`.
+    pub fn new(endpoint: impl Into<String>, transport: impl LinkTransport) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            codec: LinkCodec::default(),
+            transport: Rc::new(transport),
+        }
+    }
+
+    /// Sets the codec requests are encoded with. Defaults to
+    /// [`LinkCodec::Json`].
+    pub fn codec(mut self, codec: LinkCodec) -> Self {
+        self.codec = codec;
+        self
+    }
+
+    /// Fetches the linked state `T` for `input`.
+    pub async fn fetch<T: LinkedState>(&self, input: &T::Input) -> Result<T, LinkError<T::Error>> {
+        self.request(T::TYPE_KEY, T::VERSION, input).await
+    }
+
+    /// Runs the linked action `A` with `input`.
+    pub async fn run<A: LinkedAction>(
+        &self,
+        input: &A::Input,
+    ) -> Result<A::Output, LinkError<A::Error>> {
+        self.request(A::TYPE_KEY, A::VERSION, input).await
+    }
+
+    async fn request<O, E>(
+        &self,
+        type_key: &str,
+        version: u32,
+        input: &impl serde::Serialize,
+    ) -> Result<O, LinkError<E>>
+    where
+        O: serde::de::DeserializeOwned,
+        E: serde::de::DeserializeOwned,
+    {
+        let input = self.codec.encode(input).map_err(LinkError::Internal)?;
+        let result = send(
+            &*self.transport,
+            &self.endpoint,
+            self.codec,
+            vec![(type_key.to_string(), version, input)],
+        )
+        .await
+        .map_err(LinkError::Internal)?
+        .remove(0);
+        self.codec.decode_result(result)
+    }
+}
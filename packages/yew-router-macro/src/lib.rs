@@ -23,7 +23,50 @@ use syn::parse_macro_input;
 ///     NotFound,
 /// }
 /// ```
-#[proc_macro_derive(Routable, attributes(at, not_found))]
+///
+/// # Route syntax
+///
+/// - `{name}` matches a single segment and parses it into the field `name`.
+/// - `{name:Type}` only matches if the segment parses as `Type`, so another route with the same
+///   shape can handle the remaining paths.
+/// - `{/name}` is an optional segment, its field must be an `Option`.
+/// - `{*name}` matches the rest of the path.
+///
+/// Routes that can never be reached, because an earlier route matches the same paths, are
+/// rejected at compile time.
+///
+/// ```
+/// # use yew_router::Routable;
+/// #[derive(Debug, Clone, PartialEq, Routable)]
+/// #[routable(trailing_slash = "redirect")]
+/// enum Routes {
+///     #[at("/posts{/page}")]
+///     Posts { page: Option<u32> },
+///     #[at("/post/{id:u32}")]
+///     Post { id: u32 },
+///     #[at("/post/{slug}")]
+///     PostBySlug { slug: String },
+/// }
+/// ```
+///
+/// `#[routable(trailing_slash = "...")]` accepts `"strict"` (the default), `"ignore"` and
+/// `"redirect"`, see `yew_router::TrailingSlash`.
+///
+/// # Route metadata
+///
+/// `#[route(title = "Settings", layout = AdminLayout, requires_auth)]` attaches typed metadata to a
+/// variant, returned by `Routable::meta`. `Switch` renders the route inside its `layout`, a
+/// component taking `ChildrenProps`. Any other `key = value` of the attribute, where the value is
+/// a string, number or boolean literal, is added to the key-value pairs of the variant, like with
+/// `#[meta]`, and a `key` without a value is set to `true`. They are read with `RouteMeta::get`,
+/// `RouteMeta::get_as` and `RouteMeta::flag`.
+///
+/// # Manifest
+///
+/// The implementation also describes every route in `Routable::manifest`. Arbitrary metadata can
+/// be attached to a variant with `#[meta(key = value)]`, where the value is a string, number or
+/// boolean literal, e.g. `#[meta(priority = 0.8)]` for `yew_router::sitemap::Sitemap`.
+#[proc_macro_derive(Routable, attributes(at, not_found, routable, meta, route))]
 pub fn routable_derive(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as Routable);
     routable_derive_impl(input).into()
@@ -1,42 +1,260 @@
-use proc_macro2::TokenStream;
-use quote::quote;
+use proc_macro2::{Span, TokenStream};
+use quote::{ToTokens, format_ident, quote};
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
-use syn::{Data, DeriveInput, Fields, Ident, LitStr, Variant};
+use syn::{Attribute, Data, DeriveInput, Fields, Ident, Lifetime, Lit, LitStr, Variant};
 
 const AT_ATTR_IDENT: &str = "at";
 const NOT_FOUND_ATTR_IDENT: &str = "not_found";
+const ROUTABLE_ATTR_IDENT: &str = "routable";
+const META_ATTR_IDENT: &str = "meta";
+const ROUTE_ATTR_IDENT: &str = "route";
+
+/// A piece of an `#[at("...")]` route.
+enum Part {
+    /// Literal text, with `{{` and `}}` unescaped.
+    Lit(String),
+    Param(Param),
+}
+
+/// A `{name}`, `{*name}` or `{/name}` parameter, optionally constrained with `{name:Type}`.
+struct Param {
+    name: String,
+    wildcard: bool,
+    /// `{/name}`: the parameter and the slash before it may be left out.
+    optional: bool,
+    constraint: Option<Box<syn::Type>>,
+}
+
+/// The parsed `#[at("...")]` attribute of a variant.
+struct Route {
+    lit: LitStr,
+    parts: Vec<Part>,
+}
+
+impl Route {
+    fn parse(lit: LitStr) -> syn::Result<Self> {
+        let val = lit.value();
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        let mut chars = val.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let mut inner = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => inner.push(c),
+                            None => {
+                                return Err(syn::Error::new_spanned(
+                                    &lit,
+                                    "unclosed `{` in route. Use `{{` for a literal brace.",
+                                ));
+                            }
+                        }
+                    }
 
-/// Extract parameter names from a matchit-style route pattern.
-/// E.g. `"/posts/{id}"` → `["id"]`, `"/files/{*path}"` → `["path"]`.
-fn extract_route_params(route: &str) -> Vec<String> {
-    let mut params = Vec::new();
-    let mut chars = route.chars().peekable();
-    while let Some(c) = chars.next() {
-        if c == '{' {
-            if chars.peek() == Some(&'*') {
-                chars.next();
+                    if !text.is_empty() {
+                        parts.push(Part::Lit(std::mem::take(&mut text)));
+                    }
+                    parts.push(Part::Param(Param::parse(&lit, &inner)?));
+                }
+                c => text.push(c),
             }
-            let name: String = chars.by_ref().take_while(|&c| c != '}').collect();
-            if !name.is_empty() {
-                params.push(name);
+        }
+        if !text.is_empty() {
+            parts.push(Part::Lit(text));
+        }
+
+        Ok(Self { lit, parts })
+    }
+
+    fn params(&self) -> impl Iterator<Item = &Param> {
+        self.parts.iter().filter_map(|part| match part {
+            Part::Param(param) => Some(param),
+            Part::Lit(_) => None,
+        })
+    }
+
+    /// Returns the `matchit` patterns of the route, one for each combination of optional
+    /// parameters.
+    fn patterns(&self) -> Vec<Pattern<'_>> {
+        let optionals = self.params().filter(|param| param.optional).count();
+
+        (0..1usize << optionals)
+            .map(|mask| {
+                let mut pattern = Pattern {
+                    path: String::new(),
+                    shape: String::new(),
+                    params: Vec::new(),
+                };
+                let mut optional = 0;
+                for part in &self.parts {
+                    match part {
+                        Part::Lit(text) => {
+                            let text = text.replace('{', "{{").replace('}', "}}");
+                            pattern.path.push_str(&text);
+                            pattern.shape.push_str(&text);
+                        }
+                        Part::Param(param) => {
+                            if param.optional {
+                                optional += 1;
+                                if mask & (1 << (optional - 1)) == 0 {
+                                    continue;
+                                }
+                                pattern.path.push('/');
+                                pattern.shape.push('/');
+                            }
+                            let wildcard = if param.wildcard { "*" } else { "" };
+                            pattern
+                                .path
+                                .push_str(&format!("{{{wildcard}{}}}", param.name));
+                            pattern.shape.push_str(&format!("{{{wildcard}}}"));
+                            pattern.params.push(param);
+                        }
+                    }
+                }
+                pattern
+            })
+            .collect()
+    }
+}
+
+/// One `matchit` pattern of a route.
+struct Pattern<'a> {
+    /// The pattern passed to `matchit`.
+    path: String,
+    /// The pattern without the names of its parameters. `matchit` cannot tell apart patterns of
+    /// the same shape.
+    shape: String,
+    /// The parameters of the pattern, in order.
+    params: Vec<&'a Param>,
+}
+
+impl Pattern<'_> {
+    /// Returns `true` if a parameter is constrained, so that the pattern may not match every path
+    /// of its shape.
+    fn is_constrained(&self) -> bool {
+        self.params.iter().any(|param| param.constraint.is_some())
+    }
+
+    fn includes(&self, param: &Param) -> bool {
+        self.params.iter().any(|p| p.name == param.name)
+    }
+
+    /// Returns the name `matchit` captures `param` under, when `inserted` is the pattern of the
+    /// same shape that was inserted into it.
+    fn key<'a>(&self, param: &Param, inserted: &'a Pattern<'_>) -> &'a str {
+        let position = self
+            .params
+            .iter()
+            .position(|p| p.name == param.name)
+            .unwrap();
+        &inserted.params[position].name
+    }
+}
+
+/// The patterns of all routes, grouped by shape in declaration order. Each group is listed with
+/// the variants that share it, the first of which is the pattern inserted into `matchit`.
+fn group_patterns(routes: &[Route]) -> Vec<Vec<(usize, Pattern<'_>)>> {
+    let mut groups: Vec<Vec<(usize, Pattern<'_>)>> = Vec::new();
+    for (i, route) in routes.iter().enumerate() {
+        for pattern in route.patterns() {
+            match groups
+                .iter_mut()
+                .find(|group| group[0].1.shape == pattern.shape)
+            {
+                Some(group) => group.push((i, pattern)),
+                None => groups.push(vec![(i, pattern)]),
             }
         }
     }
-    params
+    groups
+}
+
+impl Param {
+    fn parse(lit: &LitStr, inner: &str) -> syn::Result<Self> {
+        let (optional, inner) = match inner.strip_prefix('/') {
+            Some(inner) => (true, inner),
+            None => (false, inner),
+        };
+        let (wildcard, inner) = match inner.strip_prefix('*') {
+            Some(inner) => (true, inner),
+            None => (false, inner),
+        };
+        let (name, constraint) = match inner.split_once(':') {
+            Some((name, ty)) => {
+                let constraint = syn::parse_str::<syn::Type>(ty).map_err(|e| {
+                    syn::Error::new_spanned(
+                        lit,
+                        format!("invalid constraint `{ty}` for route parameter `{name}`: {e}"),
+                    )
+                })?;
+                (name, Some(Box::new(constraint)))
+            }
+            None => (inner, None),
+        };
+
+        if name.is_empty() {
+            return Err(syn::Error::new_spanned(
+                lit,
+                "route parameters must have a name",
+            ));
+        }
+
+        Ok(Self {
+            name: name.to_owned(),
+            wildcard,
+            optional,
+            constraint,
+        })
+    }
 }
 
 pub struct Routable {
     ident: Ident,
-    ats: Vec<LitStr>,
+    routes: Vec<Route>,
     variants: Punctuated<Variant, syn::token::Comma>,
     not_found_route: Option<Ident>,
+    trailing_slash: Option<Ident>,
+    /// The `#[meta(key = value)]` pairs of every variant.
+    meta: Vec<Vec<(String, String)>>,
+    /// The `#[route(...)]` attributes of every variant.
+    route_attrs: Vec<RouteAttrs>,
+}
+
+/// Typed metadata of a variant, from `#[route(title = "...", layout = Component, requires_auth)]`.
+///
+/// The other keys of the attribute are parsed into the key-value pairs of the variant.
+#[derive(Default)]
+struct RouteAttrs {
+    title: Option<LitStr>,
+    layout: Option<syn::Path>,
+    requires_auth: bool,
+}
+
+impl RouteAttrs {
+    fn is_empty(&self) -> bool {
+        self.title.is_none() && self.layout.is_none() && !self.requires_auth
+    }
 }
 
 impl Parse for Routable {
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let DeriveInput { ident, data, .. } = input.parse()?;
+        let DeriveInput {
+            ident, data, attrs, ..
+        } = input.parse()?;
 
         let data = match data {
             Data::Enum(data) => data,
@@ -54,22 +272,157 @@ impl Parse for Routable {
             }
         };
 
-        let (not_found_route, ats) = parse_variants_attributes(&data.variants)?;
+        let trailing_slash = parse_routable_attributes(&attrs)?;
+        let (not_found_route, routes) = parse_variants_attributes(&data.variants)?;
+        check_conflicts(&data.variants, &routes)?;
+        let (meta, route_attrs) = data
+            .variants
+            .iter()
+            .map(|variant| {
+                let mut pairs = parse_meta_attributes(&variant.attrs)?;
+                let route_attrs = parse_route_attributes(&variant.attrs, &mut pairs)?;
+                Ok((pairs, route_attrs))
+            })
+            .collect::<syn::Result<_>>()?;
 
         Ok(Self {
             ident,
             variants: data.variants,
-            ats,
+            routes,
             not_found_route,
+            trailing_slash,
+            meta,
+            route_attrs,
         })
     }
 }
 
+/// Parses `#[routable(trailing_slash = "...")]` into the matching `TrailingSlash` variant.
+fn parse_routable_attributes(attrs: &[Attribute]) -> syn::Result<Option<Ident>> {
+    let mut trailing_slash = None;
+
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident(ROUTABLE_ATTR_IDENT))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("trailing_slash") {
+                let lit: LitStr = meta.value()?.parse()?;
+                let variant = match lit.value().as_str() {
+                    "strict" => "Strict",
+                    "ignore" => "Ignore",
+                    "redirect" => "Redirect",
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            lit,
+                            "expected `\"strict\"`, `\"ignore\"` or `\"redirect\"`",
+                        ));
+                    }
+                };
+                trailing_slash = Some(Ident::new(variant, lit.span()));
+                Ok(())
+            } else {
+                Err(meta.error(format!(
+                    "unknown {ROUTABLE_ATTR_IDENT} attribute, expected `trailing_slash`"
+                )))
+            }
+        })?;
+    }
+
+    Ok(trailing_slash)
+}
+
+/// Parses the `#[meta(key = value)]` attributes of a variant into key-value pairs, with literal
+/// values converted to strings.
+fn parse_meta_attributes(attrs: &[Attribute]) -> syn::Result<Vec<(String, String)>> {
+    let mut pairs: Vec<(String, String)> = vec![];
+
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident(META_ATTR_IDENT))
+    {
+        attr.parse_nested_meta(|meta| {
+            let key = meta.path.require_ident()?.to_string();
+            if pairs.iter().any(|(k, _)| *k == key) {
+                return Err(meta.error(format!("duplicate {META_ATTR_IDENT} key `{key}`")));
+            }
+            pairs.push((key, parse_meta_value(meta.value()?)?));
+            Ok(())
+        })?;
+    }
+
+    Ok(pairs)
+}
+
+/// Parses the literal value of a metadata key into a string.
+fn parse_meta_value(input: ParseStream) -> syn::Result<String> {
+    match input.parse::<Lit>()? {
+        Lit::Str(lit) => Ok(lit.value()),
+        Lit::Int(lit) => Ok(lit.base10_digits().to_owned()),
+        Lit::Float(lit) => Ok(lit.base10_digits().to_owned()),
+        Lit::Bool(lit) => Ok(lit.value.to_string()),
+        lit => Err(syn::Error::new_spanned(
+            lit,
+            "expected a string, number or boolean literal",
+        )),
+    }
+}
+
+/// Parses the `#[route(...)]` attributes of a variant into its typed metadata.
+///
+/// Other keys are added to `pairs`, the key-value pairs of its `#[meta]` attributes. A key without
+/// a value is set to `true`.
+fn parse_route_attributes(
+    attrs: &[Attribute],
+    pairs: &mut Vec<(String, String)>,
+) -> syn::Result<RouteAttrs> {
+    let mut route_attrs = RouteAttrs::default();
+
+    for attr in attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident(ROUTE_ATTR_IDENT))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("title") {
+                if route_attrs.title.is_some() {
+                    return Err(meta.error("duplicate `title`"));
+                }
+                route_attrs.title = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("layout") {
+                if route_attrs.layout.is_some() {
+                    return Err(meta.error("duplicate `layout`"));
+                }
+                route_attrs.layout = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("requires_auth") {
+                route_attrs.requires_auth = if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<syn::LitBool>()?.value
+                } else {
+                    true
+                };
+            } else {
+                let key = meta.path.require_ident()?.to_string();
+                if pairs.iter().any(|(k, _)| *k == key) {
+                    return Err(meta.error(format!("duplicate {META_ATTR_IDENT} key `{key}`")));
+                }
+                let value = if meta.input.peek(syn::Token![=]) {
+                    parse_meta_value(meta.value()?)?
+                } else {
+                    true.to_string()
+                };
+                pairs.push((key, value));
+            }
+            Ok(())
+        })?;
+    }
+
+    Ok(route_attrs)
+}
+
 fn parse_variants_attributes(
     variants: &Punctuated<Variant, syn::token::Comma>,
-) -> syn::Result<(Option<Ident>, Vec<LitStr>)> {
+) -> syn::Result<(Option<Ident>, Vec<Route>)> {
     let mut not_founds = vec![];
-    let mut ats: Vec<LitStr> = vec![];
+    let mut routes: Vec<Route> = vec![];
 
     let mut not_found_attrs = vec![];
 
@@ -143,33 +496,40 @@ fn parse_variants_attributes(
             }
         }
 
-        let route_params = extract_route_params(&val);
-        if !route_params.is_empty() {
-            let field_names: std::collections::HashSet<String> = match &variant.fields {
+        let route = Route::parse(lit)?;
+        for param in route.params() {
+            let field = match &variant.fields {
                 Fields::Named(fields) => fields
                     .named
                     .iter()
-                    .filter_map(|f| f.ident.as_ref().map(|i| i.to_string()))
-                    .collect(),
-                Fields::Unit => std::collections::HashSet::new(),
+                    .find(|f| f.ident.as_ref().is_some_and(|i| *i == param.name)),
+                Fields::Unit => None,
                 Fields::Unnamed(_) => unreachable!(),
             };
 
-            for param in &route_params {
-                if !field_names.contains(param) {
-                    return Err(syn::Error::new_spanned(
-                        &lit,
-                        format!(
-                            "route parameter `{param}` does not have a corresponding field in \
-                             variant `{}`",
-                            variant.ident
-                        ),
-                    ));
-                }
+            let Some(field) = field else {
+                return Err(syn::Error::new_spanned(
+                    &route.lit,
+                    format!(
+                        "route parameter `{}` does not have a corresponding field in variant \
+                         `{}`",
+                        param.name, variant.ident
+                    ),
+                ));
+            };
+
+            if param.optional && !is_option(&field.ty) {
+                return Err(syn::Error::new_spanned(
+                    &field.ty,
+                    format!(
+                        "optional route parameter `{}` must have an `Option` field",
+                        param.name
+                    ),
+                ));
             }
         }
 
-        ats.push(lit);
+        routes.push(route);
 
         for attr in attrs.iter() {
             if attr.path().is_ident(NOT_FOUND_ATTR_IDENT) {
@@ -186,33 +546,128 @@ fn parse_variants_attributes(
         ));
     }
 
-    Ok((not_founds.into_iter().next(), ats))
+    Ok((not_founds.into_iter().next(), routes))
+}
+
+fn is_option(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
+
+/// Rejects routes that `matchit` cannot tell apart, and routes that can never match because an
+/// earlier variant matches every path of the same shape first.
+fn check_conflicts(
+    variants: &Punctuated<Variant, syn::token::Comma>,
+    routes: &[Route],
+) -> syn::Result<()> {
+    let mut router = matchit::Router::new();
+
+    for group in group_patterns(routes) {
+        let (first, inserted) = &group[0];
+        router.insert(inserted.path.clone(), ()).map_err(|e| {
+            syn::Error::new_spanned(
+                &routes[*first].lit,
+                format!(
+                    "route `{}` of variant `{}` conflicts with another route: {e}",
+                    inserted.path, variants[*first].ident
+                ),
+            )
+        })?;
+
+        for pair in group.windows(2) {
+            let [(prev, prev_pattern), (next, next_pattern)] = pair else {
+                unreachable!()
+            };
+            if !prev_pattern.is_constrained() {
+                let prev = &variants[*prev].ident;
+                return Err(syn::Error::new_spanned(
+                    &routes[*next].lit,
+                    format!(
+                        "route `{}` of variant `{}` can never match, because variant `{prev}` \
+                         matches the same paths first. Constrain the parameters of `{prev}`, \
+                         e.g. `{{id:u32}}`, or remove one of them.",
+                        next_pattern.path, variants[*next].ident
+                    ),
+                ));
+            }
+        }
+    }
+
+    Ok(())
 }
 
 impl Routable {
     fn build_from_path(&self) -> TokenStream {
-        let from_path_matches = self.variants.iter().enumerate().map(|(i, variant)| {
-            let ident = &variant.ident;
-            let right = match &variant.fields {
-                Fields::Unit => quote! { Self::#ident },
-                Fields::Named(field) => {
-                    let fields = field.named.iter().map(|it| {
-                        // named fields have idents
-                        it.ident.as_ref().unwrap()
-                    });
-                    quote! { Self::#ident { #(#fields: {
-                        let param = params.get(stringify!(#fields))?;
-                        let param = &*::yew_router::__macro::decode_for_url(param).ok()?;
-                        let param = param.parse().ok()?;
-                        param
-                    },)* } }
+        let label = Lifetime::new("'__yew_router_route", Span::call_site());
+
+        let from_path_matches = group_patterns(&self.routes).into_iter().map(|group| {
+            let inserted = &group[0].1;
+            let path = &inserted.path;
+            let count = group.len();
+            let attempts = group.iter().enumerate().map(|(n, (i, pattern))| {
+                let i = *i;
+                let variant = &self.variants[i];
+                let route = &self.routes[i];
+                let ident = &variant.ident;
+                let right = match &variant.fields {
+                    Fields::Unit => quote! { Self::#ident },
+                    Fields::Named(field) => {
+                        let fields = field.named.iter().map(|it| {
+                            // named fields have idents
+                            let field = it.ident.as_ref().unwrap();
+                            let param = route.params().find(|param| *field == param.name);
+                            let value = match param {
+                                Some(param) if !pattern.includes(param) => {
+                                    quote! { ::std::option::Option::None }
+                                }
+                                Some(param) => {
+                                    let key = pattern.key(param, inserted);
+                                    parse_param(key, Some(param), &label)
+                                }
+                                None => parse_param(&field.to_string(), None, &label),
+                            };
+                            quote! { #field: #value }
+                        });
+                        quote! { Self::#ident { #(#fields,)* } }
+                    }
+                    Fields::Unnamed(_) => unreachable!(), // already checked
+                };
+
+                // Optional parameters left out of the pattern are `None` and cannot fail.
+                let infallible = variant.fields.iter().all(|field| {
+                    route
+                        .params()
+                        .find(|param| field.ident.as_ref().is_some_and(|i| *i == param.name))
+                        .is_some_and(|param| !pattern.includes(param))
+                });
+                match (infallible, n + 1 == count) {
+                    // Only the last variant of a pattern can be infallible, as the earlier ones
+                    // must be constrained.
+                    (true, _) => quote! { ::std::option::Option::Some(#right) },
+                    (false, true) => quote! {
+                        #label: {
+                            return ::std::option::Option::Some(#right);
+                        }
+                        ::std::option::Option::None
+                    },
+                    (false, false) => quote! {
+                        #label: {
+                            return ::std::option::Option::Some(#right);
+                        }
+                    },
                 }
-                Fields::Unnamed(_) => unreachable!(), // already checked
-            };
+            });
 
-            let left = self.ats.get(i).unwrap();
             quote! {
-                #left => ::std::option::Option::Some(#right)
+                #path => {
+                    #(#attempts)*
+                }
             }
         });
 
@@ -227,47 +682,60 @@ impl Routable {
     }
 
     fn build_to_path(&self) -> TokenStream {
-        let to_path_matches = self.variants.iter().enumerate().map(|(i, variant)| {
+        let to_path_matches = self.variants.iter().zip(&self.routes).map(|(variant, route)| {
             let ident = &variant.ident;
-            let mut right = self.ats.get(i).unwrap().value();
-
-            match &variant.fields {
-                Fields::Unit => quote! { Self::#ident => ::std::string::ToString::to_string(#right) },
-                Fields::Named(field) => {
-                    let fields = field
-                        .named
-                        .iter()
-                        .map(|it| it.ident.as_ref().unwrap())
-                        .collect::<Vec<_>>();
-
-                    let mut wildcard_fields = std::collections::HashSet::new();
-                    for field in fields.iter() {
-                        if right.contains(&format!("{{*{field}}}")) {
-                            wildcard_fields.insert((*field).clone());
-                        }
-                        // {*param} -> {param} so we can pass it to `format!("...", param)`
-                        // {param} is already valid format syntax
-                        right =
-                            right.replace(&format!("{{*{field}}}"), &format!("{{{field}}}"));
-                    }
 
-                    let field_encodings = fields.iter().map(|field| {
-                        if wildcard_fields.contains(*field) {
-                            quote! {
-                                #field = ::yew_router::__macro::encode_path_for_url(&::std::format!("{}", #field))
-                            }
-                        } else {
-                            quote! {
-                                #field = ::yew_router::__macro::encode_for_url(&::std::format!("{}", #field))
+            if route.params().next().is_none() {
+                let path = route
+                    .parts
+                    .iter()
+                    .map(|part| match part {
+                        Part::Lit(text) => text.as_str(),
+                        Part::Param(_) => unreachable!(),
+                    })
+                    .collect::<String>();
+                return match &variant.fields {
+                    Fields::Unit => quote! { Self::#ident => ::std::string::ToString::to_string(#path) },
+                    _ => quote! { Self::#ident { .. } => ::std::string::ToString::to_string(#path) },
+                };
+            }
+
+            let fields = route
+                .params()
+                .map(|param| format_ident!("{}", param.name))
+                .collect::<Vec<_>>();
+
+            let pushes = route.parts.iter().map(|part| match part {
+                Part::Lit(text) => quote! { __yew_router_path.push_str(#text); },
+                Part::Param(param) => {
+                    let field = format_ident!("{}", param.name);
+                    let encode = if param.wildcard {
+                        quote! { ::yew_router::__macro::encode_path_for_url }
+                    } else {
+                        quote! { ::yew_router::__macro::encode_for_url }
+                    };
+                    if param.optional {
+                        quote! {
+                            if let ::std::option::Option::Some(#field) = #field {
+                                __yew_router_path.push('/');
+                                __yew_router_path
+                                    .push_str(&#encode(&::std::format!("{}", #field)));
                             }
                         }
-                    });
-
-                    quote! {
-                        Self::#ident { #(#fields),* } => ::std::format!(#right, #(#field_encodings),*)
+                    } else {
+                        quote! {
+                            __yew_router_path.push_str(&#encode(&::std::format!("{}", #field)));
+                        }
                     }
                 }
-                Fields::Unnamed(_) => unreachable!(), // already checked
+            });
+
+            quote! {
+                Self::#ident { #(#fields,)* .. } => {
+                    let mut __yew_router_path = ::std::string::String::new();
+                    #(#pushes)*
+                    __yew_router_path
+                }
             }
         });
 
@@ -279,24 +747,183 @@ impl Routable {
             }
         }
     }
+
+    fn build_meta(&self) -> Option<TokenStream> {
+        if self.route_attrs.iter().all(RouteAttrs::is_empty) {
+            return None;
+        }
+
+        let arms = self
+            .variants
+            .iter()
+            .zip(&self.route_attrs)
+            .map(|(variant, attrs)| {
+                let ident = &variant.ident;
+                let title = attrs.title.as_ref().map(|title| {
+                    quote! { meta.title = ::std::option::Option::Some(#title); }
+                });
+                let layout = attrs.layout.as_ref().map(|layout| {
+                    quote! {
+                        meta.layout =
+                            ::std::option::Option::Some(::yew_router::Layout::of::<#layout>());
+                    }
+                });
+                let requires_auth = attrs.requires_auth.then(|| {
+                    quote! { meta.requires_auth = true; }
+                });
+
+                quote! {
+                    Self::#ident { .. } => {
+                        #title
+                        #layout
+                        #requires_auth
+                    }
+                }
+            });
+
+        // `RouteMeta` is non-exhaustive, so it is built from the manifest and then filled in.
+        Some(quote! {
+            fn meta(&self) -> ::yew_router::RouteMeta {
+                let mut meta = ::yew_router::Routable::route_info(self)
+                    .map(::yew_router::RouteMeta::new)
+                    .unwrap_or_default();
+                match self {
+                    #(#arms),*
+                }
+                meta
+            }
+        })
+    }
+
+    fn build_manifest(&self) -> TokenStream {
+        let entries = self.variants.iter().zip(&self.routes).zip(&self.meta).map(
+            |((variant, route), meta)| {
+                let name = variant.ident.to_string();
+                let path = route.lit.value();
+                let params = route.params().map(|param| {
+                    let Param {
+                        name,
+                        wildcard,
+                        optional,
+                        ..
+                    } = param;
+                    let ty = match &variant.fields {
+                        Fields::Named(fields) => fields
+                            .named
+                            .iter()
+                            .find(|f| f.ident.as_ref().is_some_and(|i| i == name))
+                            .map(|f| f.ty.to_token_stream().to_string().replace(' ', "")),
+                        _ => None,
+                    }
+                    .unwrap_or_default();
+                    quote! {
+                        ::yew_router::RouteParam {
+                            name: #name,
+                            ty: #ty,
+                            optional: #optional,
+                            wildcard: #wildcard,
+                        }
+                    }
+                });
+                let not_found = self
+                    .not_found_route
+                    .as_ref()
+                    .is_some_and(|ident| *ident == variant.ident);
+                let meta = meta.iter().map(|(key, value)| quote! { (#key, #value) });
+
+                quote! {
+                    ::yew_router::RouteInfo {
+                        variant: #name,
+                        path: #path,
+                        params: &[#(#params),*],
+                        not_found: #not_found,
+                        meta: &[#(#meta),*],
+                    }
+                }
+            },
+        );
+
+        let indices = self.variants.iter().enumerate().map(|(index, variant)| {
+            let ident = &variant.ident;
+            quote! { Self::#ident { .. } => #index }
+        });
+
+        quote! {
+            fn manifest() -> &'static [::yew_router::RouteInfo] {
+                const MANIFEST: &[::yew_router::RouteInfo] = &[#(#entries),*];
+                MANIFEST
+            }
+
+            fn route_info(&self) -> ::std::option::Option<&'static ::yew_router::RouteInfo> {
+                let index: usize = match self {
+                    #(#indices),*
+                };
+                Self::manifest().get(index)
+            }
+        }
+    }
+}
+
+/// Returns the expression that parses the route parameter captured under `key`, or breaks out
+/// of `label` if it cannot be parsed.
+fn parse_param(key: &str, param: Option<&Param>, label: &Lifetime) -> TokenStream {
+    let constraint = param.and_then(|param| param.constraint.as_ref()).map(|ty| {
+        quote! {
+            if param.parse::<#ty>().is_err() {
+                break #label;
+            }
+        }
+    });
+    let value = match param {
+        Some(param) if param.optional => quote! { ::std::option::Option::Some(param) },
+        _ => quote! { param },
+    };
+
+    quote! {{
+        let ::std::option::Option::Some(param) = params.get(#key) else {
+            break #label;
+        };
+        let ::std::result::Result::Ok(param) = ::yew_router::__macro::decode_for_url(param) else {
+            break #label;
+        };
+        #constraint
+        let ::std::result::Result::Ok(param) = param.parse() else {
+            break #label;
+        };
+        #value
+    }}
 }
 
 pub fn routable_derive_impl(input: Routable) -> TokenStream {
     let Routable {
-        ats,
         not_found_route,
         ident,
+        trailing_slash,
         ..
     } = &input;
 
     let from_path = input.build_from_path();
     let to_path = input.build_to_path();
+    let manifest = input.build_manifest();
+    let meta = input.build_meta();
+    let patterns = group_patterns(&input.routes)
+        .into_iter()
+        .map(|group| group[0].1.path.clone())
+        .collect::<Vec<_>>();
 
     let maybe_not_found_route = match not_found_route {
         Some(route) => quote! { ::std::option::Option::Some(Self::#route) },
         None => quote! { ::std::option::Option::None },
     };
 
+    let maybe_trailing_slash = trailing_slash.as_ref().map(|trailing_slash| {
+        quote! {
+            fn trailing_slash() -> ::yew_router::TrailingSlash {
+                ::yew_router::TrailingSlash::#trailing_slash
+            }
+        }
+    });
+
     let maybe_default = match not_found_route {
         Some(route) => {
             quote! {
@@ -317,13 +944,19 @@ pub fn routable_derive_impl(input: Routable) -> TokenStream {
             #to_path
 
             fn routes() -> ::std::vec::Vec<&'static str> {
-                ::std::vec![#(#ats),*]
+                ::std::vec![#(#patterns),*]
             }
 
             fn not_found_route() -> ::std::option::Option<Self> {
                 #maybe_not_found_route
             }
 
+            #maybe_trailing_slash
+
+            #manifest
+
+            #meta
+
             fn recognize(pathname: &str) -> ::std::option::Option<Self> {
                 ::std::thread_local! {
                     static ROUTER: ::yew_router::__macro::Router = ::yew_router::__macro::build_router::<#ident>();
@@ -0,0 +1,8 @@
+#[derive(Debug, PartialEq, yew_router::Routable)]
+enum Routes {
+    #[at("/")]
+    #[meta(priority = 1.0, priority = 0.5)]
+    Home,
+}
+
+fn main() {}
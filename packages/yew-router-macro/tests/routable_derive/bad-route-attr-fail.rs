@@ -0,0 +1,9 @@
+#[derive(Debug, PartialEq, yew_router::Routable)]
+enum Routes {
+    #[at("/")]
+    #[meta(section = "home")]
+    #[route(title = "Home", section = "start")]
+    Home,
+}
+
+fn main() {}
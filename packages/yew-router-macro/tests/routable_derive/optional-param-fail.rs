@@ -0,0 +1,7 @@
+#[derive(Debug, PartialEq, yew_router::Routable)]
+enum Routes {
+    #[at("/posts{/page}")]
+    Posts { page: u32 },
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+#[derive(Debug, PartialEq, yew_router::Routable)]
+#[routable(trailing_slash = "sometimes")]
+enum Routes {
+    #[at("/")]
+    Home,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+#[derive(Debug, PartialEq, yew_router::Routable)]
+enum Routes {
+    #[at("/posts/{id}")]
+    Post { id: u32 },
+    #[at("/posts/{slug}")]
+    PostBySlug { slug: String },
+}
+
+fn main() {}
@@ -3,8 +3,10 @@
 #[derive(Debug, PartialEq, Clone, ::yew_router::Routable)]
 enum Routes {
     #[at("/")]
+    #[meta(priority = 1.0, changefreq = "daily", indexed = true)]
     One,
     #[at("/two/{id}")]
+    #[route(title = "Two", requires_auth, section = "admin", order = 2, beta)]
     Two { id: u32 },
     #[at("/{a}/{b}/{*rest}")]
     Three { a: u32, b: u32, rest: ::std::string::String },
@@ -21,4 +23,17 @@ enum MoreRoutes {
     CatchAll { all: ::std::string::String },
 }
 
+#[derive(Debug, PartialEq, Clone, ::yew_router::Routable)]
+#[routable(trailing_slash = "ignore")]
+enum ConstrainedRoutes {
+    #[at("/posts{/page}")]
+    Posts { page: ::std::option::Option<u32> },
+    #[at("/post/{id:u32}")]
+    Post { id: u32 },
+    #[at("/post/{slug}")]
+    PostBySlug { slug: ::std::string::String },
+    #[at("/{{braces}}/{*rest}")]
+    Braces { rest: ::std::string::String },
+}
+
 fn main() {}
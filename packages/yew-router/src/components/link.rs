@@ -31,6 +31,16 @@ where
     /// [`NodeRef`](yew::html::NodeRef) for the `<a>` element.
     #[prop_or_default]
     pub anchor_ref: NodeRef,
+    /// Called with the target route when the anchor is hovered or focused, so
+    /// the data of the next page can be loaded before it is clicked.
+    #[prop_or_default]
+    pub prefetch: Option<Callback<R>>,
+    /// Value of the `aria-current` attribute of the anchor (optional).
+    ///
+    /// [`NavLink`](crate::components::NavLink) sets it to `page` while its
+    /// route is active.
+    #[prop_or_default]
+    pub aria_current: Option<AttrValue>,
     #[prop_or_default]
     pub children: Html,
 }
@@ -50,6 +60,8 @@ where
         state,
         disabled,
         anchor_ref,
+        prefetch,
+        aria_current,
         children,
     } = props.clone();
 
@@ -87,6 +99,15 @@ where
         })
     };
 
+    let onmouseenter = prefetch.clone().map(|prefetch| {
+        let to = to.clone();
+        Callback::from(move |_: MouseEvent| prefetch.emit(to.clone()))
+    });
+    let onfocus = prefetch.map(|prefetch| {
+        let to = to.clone();
+        Callback::from(move |_: FocusEvent| prefetch.emit(to.clone()))
+    });
+
     let href = {
         let route_s = to.to_path();
         let pathname = navigator.prefix_basename(&route_s);
@@ -107,6 +128,9 @@ where
             {href}
             {onclick}
             {disabled}
+            {onmouseenter}
+            {onfocus}
+            aria-current={aria_current}
             ref={anchor_ref}
         >
             { children }
@@ -1,6 +1,8 @@
 //! Components to interface with [Router][crate::Router].
 
 mod link;
+mod nav_link;
 mod redirect;
 pub use link::*;
+pub use nav_link::*;
 pub use redirect::*;
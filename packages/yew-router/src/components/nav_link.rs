@@ -0,0 +1,128 @@
+use serde::Serialize;
+use wasm_bindgen::UnwrapThrowExt;
+use yew::prelude::*;
+use yew::virtual_dom::AttrValue;
+
+use crate::Routable;
+use crate::components::Link;
+use crate::hooks::{use_location, use_navigator};
+
+/// Props for [`NavLink`]
+#[derive(Properties, Clone, PartialEq)]
+pub struct NavLinkProps<R, Q = (), S = ()>
+where
+    R: Routable,
+    Q: Clone + PartialEq + Serialize,
+    S: Clone + PartialEq,
+{
+    /// CSS classes to add to the anchor element (optional).
+    #[prop_or_default]
+    pub classes: Classes,
+    /// CSS classes to add to the anchor element while the route is active
+    /// (optional).
+    #[prop_or_default]
+    pub active_classes: Classes,
+    /// Only treat the route as active when the current path is exactly the
+    /// path of `to`, instead of also when it is nested below it.
+    #[prop_or_default]
+    pub exact: bool,
+    /// Route that will be pushed when the anchor is clicked.
+    pub to: R,
+    /// Route query data
+    #[prop_or_default]
+    pub query: Option<Q>,
+    /// Route state data
+    #[prop_or_default]
+    pub state: Option<S>,
+    #[prop_or_default]
+    pub disabled: bool,
+    /// [`NodeRef`](yew::html::NodeRef) for the `<a>` element.
+    #[prop_or_default]
+    pub anchor_ref: NodeRef,
+    /// Called with the target route when the anchor is hovered or focused, so
+    /// the data of the next page can be loaded before it is clicked.
+    #[prop_or_default]
+    pub prefetch: Option<Callback<R>>,
+    #[prop_or_default]
+    pub children: Html,
+}
+
+/// A [`Link`] that knows whether its route is active, for navigation menus.
+///
+/// The route is active when the current path, without the basename of the
+/// [`Router`](crate::Router), is the path of `to` or, unless `exact` is set, is
+/// nested below it: a link to `/posts` is active on `/posts/1` too. A link to
+/// `/` is only active on `/`, as every path is nested below it. The query is
+/// ignored. While the route is active, `active_classes` are added to the
+/// anchor and its `aria-current` attribute is set to `page`.
+///
+/// ```ignore
+/// html! {
+///     <nav>
+///         <NavLink<Route> to={Route::Home} exact=true active_classes="active">{ "Home" }</NavLink<Route>>
+///         <NavLink<Route> to={Route::Posts} active_classes="active">{ "Posts" }</NavLink<Route>>
+///     </nav>
+/// }
+/// ```
+#[component]
+pub fn NavLink<R, Q = (), S = ()>(props: &NavLinkProps<R, Q, S>) -> Html
+where
+    R: Routable + 'static,
+    Q: Clone + PartialEq + Serialize + 'static,
+    S: Clone + PartialEq + 'static,
+{
+    let NavLinkProps {
+        classes,
+        active_classes,
+        exact,
+        to,
+        query,
+        state,
+        disabled,
+        anchor_ref,
+        prefetch,
+        children,
+    } = props.clone();
+
+    let navigator = use_navigator().expect_throw("failed to get navigator");
+    let location = use_location().expect_throw("failed to get location");
+
+    let path = navigator.strip_basename(location.path().into());
+    let active = is_active(&path, &to.to_path(), exact);
+
+    let (classes, aria_current) = if active {
+        (
+            classes!(classes, active_classes),
+            Some(AttrValue::Static("page")),
+        )
+    } else {
+        (classes, None)
+    };
+
+    html! {
+        <Link<R, Q, S>
+            {classes}
+            {to}
+            {query}
+            {state}
+            {disabled}
+            {anchor_ref}
+            {prefetch}
+            {aria_current}
+        >
+            { children }
+        </Link<R, Q, S>>
+    }
+}
+
+/// Returns whether `target` is active on `path`, ignoring trailing slashes.
+fn is_active(path: &str, target: &str, exact: bool) -> bool {
+    let path = path.trim_end_matches('/');
+    let target = target.trim_end_matches('/');
+
+    match path.strip_prefix(target) {
+        // Every path is nested below the root, so it is always matched exactly.
+        Some(rest) => rest.is_empty() || (!exact && !target.is_empty() && rest.starts_with('/')),
+        None => false,
+    }
+}
@@ -4,7 +4,7 @@ use yew::prelude::*;
 
 use crate::history::*;
 use crate::navigator::Navigator;
-use crate::routable::Routable;
+use crate::routable::{Routable, RouteMeta};
 use crate::router::{LocationContext, NavigatorContext};
 
 /// A hook to access the [`Navigator`].
@@ -19,6 +19,14 @@ pub fn use_location() -> Option<Location> {
     Some(use_context::<LocationContext>()?.location())
 }
 
+/// A hook to access the locale of the closest [`LocaleRouter`](crate::LocaleRouter).
+///
+/// This hook will return [`None`] outside of a [`LocaleRouter`](crate::LocaleRouter).
+#[hook]
+pub fn use_locale() -> Option<String> {
+    use_navigator()?.locale().map(ToOwned::to_owned)
+}
+
 /// A hook to access the current route.
 ///
 /// This hook will return [`None`] if there's no available location or none of the routes match.
@@ -38,3 +46,14 @@ where
 
     R::recognize(&path)
 }
+
+/// A hook to access the [metadata](RouteMeta) of the current route.
+///
+/// This hook will return [`None`] if there's no available location or none of the routes match.
+#[hook]
+pub fn use_route_meta<R>() -> Option<RouteMeta>
+where
+    R: Routable + 'static,
+{
+    use_route::<R>().map(|route| route.meta())
+}
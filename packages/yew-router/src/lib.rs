@@ -74,12 +74,15 @@ pub mod navigator;
 mod routable;
 pub mod router;
 pub mod scope_ext;
+pub mod sitemap;
 pub mod switch;
+pub mod transition_switch;
 pub mod utils;
 
-pub use routable::{AnyRoute, Routable};
-pub use router::{BrowserRouter, HashRouter, Router};
+pub use routable::{AnyRoute, Layout, Routable, RouteInfo, RouteMeta, RouteParam, TrailingSlash};
+pub use router::{BrowserRouter, HashRouter, LocaleRouter, Router};
 pub use switch::Switch;
+pub use transition_switch::TransitionSwitch;
 
 pub mod history {
     //! A module that provides universal session history and location information.
@@ -103,10 +106,12 @@ pub mod prelude {
 
     #[doc(no_inline)]
     pub use crate::Routable;
-    pub use crate::components::{Link, Redirect};
+    pub use crate::components::{Link, NavLink, Redirect};
     pub use crate::history::Location;
     pub use crate::hooks::*;
-    pub use crate::navigator::{NavigationError, NavigationResult, Navigator};
+    pub use crate::navigator::{
+        NavigationError, NavigationResult, Navigator, NoRouteMatch, RoutePath,
+    };
     pub use crate::scope_ext::{LocationHandle, NavigatorHandle, RouterScopeExt};
-    pub use crate::{BrowserRouter, HashRouter, Router, Switch};
+    pub use crate::{BrowserRouter, HashRouter, LocaleRouter, Router, Switch, TransitionSwitch};
 }
@@ -9,7 +9,8 @@ pub fn encode_path_for_url(path: &str) -> String {
 
 use std::collections::HashMap;
 
-use crate::Routable;
+use crate::utils::toggle_trailing_slash;
+use crate::{Routable, TrailingSlash};
 
 // re-export Router because the macro needs to access it
 pub type Router = matchit::Router<String>;
@@ -28,13 +29,18 @@ pub fn build_router<R: Routable>() -> Router {
 
 /// Use a `matchit::Router` to match the route of a `Routable`
 pub fn recognize_with_router<R: Routable>(router: &Router, pathname: &str) -> Option<R> {
-    let matched = router.at(pathname);
-
-    match matched {
-        Ok(matched) => {
-            let params: HashMap<&str, &str> = matched.params.iter().collect();
-            R::from_path(matched.value, &params).or_else(R::not_found_route)
-        }
-        Err(_) => R::not_found_route(),
-    }
+    match_route(router, pathname)
+        .or_else(|| match R::trailing_slash() {
+            TrailingSlash::Strict => None,
+            TrailingSlash::Ignore | TrailingSlash::Redirect => {
+                match_route(router, &toggle_trailing_slash(pathname)?)
+            }
+        })
+        .or_else(R::not_found_route)
+}
+
+fn match_route<R: Routable>(router: &Router, pathname: &str) -> Option<R> {
+    let matched = router.at(pathname).ok()?;
+    let params: HashMap<&str, &str> = matched.params.iter().collect();
+    R::from_path(matched.value, &params)
 }
@@ -1,11 +1,184 @@
 use std::borrow::Cow;
+use std::fmt;
 
-use crate::history::{AnyHistory, History, HistoryError, HistoryResult};
-use crate::query::ToQuery;
+use crate::history::{AnyHistory, History, HistoryError};
+use crate::query::{Raw, ToQuery};
 use crate::routable::Routable;
 
-pub type NavigationError = HistoryError;
-pub type NavigationResult<T> = HistoryResult<T>;
+/// The error returned by the navigation methods of [`Navigator`].
+#[derive(Debug)]
+pub enum NavigationError {
+    /// The history failed to navigate.
+    History(HistoryError),
+    /// No route matches the path to navigate to.
+    NoRouteMatch(NoRouteMatch),
+}
+
+impl fmt::Display for NavigationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::History(e) => e.fmt(f),
+            Self::NoRouteMatch(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for NavigationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::History(e) => Some(e),
+            Self::NoRouteMatch(e) => Some(e),
+        }
+    }
+}
+
+impl From<HistoryError> for NavigationError {
+    fn from(e: HistoryError) -> Self {
+        Self::History(e)
+    }
+}
+
+impl From<NoRouteMatch> for NavigationError {
+    fn from(e: NoRouteMatch) -> Self {
+        Self::NoRouteMatch(e)
+    }
+}
+
+pub type NavigationResult<T> = Result<T, NavigationError>;
+
+/// The error returned by relative navigation when no route matches the resolved path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NoRouteMatch {
+    path: String,
+}
+
+impl NoRouteMatch {
+    /// Returns the path that no route matches.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+}
+
+impl fmt::Display for NoRouteMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no route matches `{}`", self.path)
+    }
+}
+
+impl std::error::Error for NoRouteMatch {}
+
+/// A path made of a parent route followed by the routes nested below it.
+///
+/// Routes of a section that is mounted under several parents can be declared
+/// relative to their parent, and joined with whichever parent they are shown
+/// under:
+///
+/// ```
+/// # use yew_router::prelude::*;
+/// #[derive(Clone, PartialEq, Routable)]
+/// enum Route {
+///     #[at("/posts/{id}")]
+///     Post { id: u32 },
+///     #[at("/posts/{id}/{*rest}")]
+///     PostSection { id: u32, rest: String },
+/// }
+///
+/// #[derive(Clone, PartialEq, Routable)]
+/// enum CommentRoute {
+///     #[at("/comments/{id}")]
+///     Comment { id: u32 },
+/// }
+///
+/// let path = RoutePath::new(&Route::Post { id: 1 }).join(&CommentRoute::Comment { id: 7 });
+/// assert_eq!(path.as_str(), "/posts/1/comments/7");
+/// assert!(path.recognize::<Route>().is_ok());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoutePath {
+    path: String,
+}
+
+impl RoutePath {
+    /// Starts a path at `parent`.
+    pub fn new<R>(parent: &R) -> Self
+    where
+        R: Routable,
+    {
+        Self {
+            path: parent.to_path(),
+        }
+    }
+
+    /// Appends `child` below the path.
+    pub fn join<R>(mut self, child: &R) -> Self
+    where
+        R: Routable,
+    {
+        let child = child.to_path();
+        let child = child.trim_start_matches('/');
+        if !child.is_empty() {
+            self.path = format!("{}/{child}", self.path.trim_end_matches('/'));
+        }
+        self
+    }
+
+    /// Returns the path.
+    pub fn as_str(&self) -> &str {
+        &self.path
+    }
+
+    /// Matches the path against the routes of `R`.
+    ///
+    /// Unlike [`Routable::recognize`], a path that only matches the
+    /// `#[not_found]` route is an error.
+    pub fn recognize<R>(&self) -> Result<R, NoRouteMatch>
+    where
+        R: Routable,
+    {
+        recognize_exact(&self.path)
+    }
+}
+
+/// Matches `path` against the routes of `R`, without falling back to the `#[not_found]` route.
+fn recognize_exact<R>(path: &str) -> Result<R, NoRouteMatch>
+where
+    R: Routable,
+{
+    R::recognize(path)
+        .filter(|route| R::not_found_route().as_ref() != Some(route) || route.to_path() == path)
+        .ok_or_else(|| NoRouteMatch {
+            path: path.to_owned(),
+        })
+}
+
+/// Returns the part of `path` matched by `route`, which stops before its `{*rest}` parameter.
+fn matched_path<R>(route: &R, path: &str) -> String
+where
+    R: Routable,
+{
+    let Some(info) = route.route_info() else {
+        return route.to_path();
+    };
+    // The segments of optional parameters may be missing from `path`.
+    if info.params.iter().any(|param| param.optional) {
+        return route.to_path();
+    }
+    let Some(parent_len) = info
+        .path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .position(|s| s.starts_with("{*"))
+    else {
+        return route.to_path();
+    };
+
+    let parent: Vec<&str> = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .take(parent_len)
+        .collect();
+    format!("/{}", parent.join("/"))
+}
 
 /// The kind of Navigator Provider.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -23,6 +196,7 @@ pub enum NavigatorKind {
 pub struct Navigator {
     inner: AnyHistory,
     basename: Option<String>,
+    locale: Option<String>,
 }
 
 impl Navigator {
@@ -30,14 +204,61 @@ impl Navigator {
         Self {
             inner: history,
             basename,
+            locale: None,
+        }
+    }
+
+    /// Returns a navigator whose paths are prefixed with `locale` after the basename.
+    pub(crate) fn with_locale(&self, locale: &str) -> Self {
+        let basename = self.without_locale().basename;
+        Self {
+            inner: self.inner.clone(),
+            basename: Some(format!(
+                "{}/{locale}",
+                basename.as_deref().unwrap_or_default()
+            )),
+            locale: Some(locale.to_owned()),
         }
     }
 
+    fn without_locale(&self) -> Self {
+        let basename = match &self.locale {
+            Some(locale) => self
+                .basename()
+                .and_then(|basename| basename.strip_suffix(locale.as_str()))
+                .and_then(|basename| basename.strip_suffix('/'))
+                .filter(|basename| !basename.is_empty())
+                .map(ToOwned::to_owned),
+            None => self.basename.clone(),
+        };
+        Self::new(self.inner.clone(), basename)
+    }
+
     /// Returns basename of current navigator.
+    ///
+    /// Below a [`LocaleRouter`](crate::LocaleRouter), it ends with the current locale.
     pub fn basename(&self) -> Option<&str> {
         self.basename.as_deref()
     }
 
+    /// Returns the locale of the closest [`LocaleRouter`](crate::LocaleRouter).
+    pub fn locale(&self) -> Option<&str> {
+        self.locale.as_deref()
+    }
+
+    /// Navigates to the current location in another `locale`, keeping its query.
+    ///
+    /// Below a [`LocaleRouter`](crate::LocaleRouter), the locale prefix of the path is replaced,
+    /// otherwise `locale` is added as the prefix.
+    pub fn switch_locale(&self, locale: &str) {
+        let location = self.inner.location();
+        let path = self.strip_basename(location.path().into());
+        let path = self.with_locale(locale).prefix_basename(&path).into_owned();
+        self.inner
+            .push_with_query(path, Raw(location.query_str().trim_start_matches('?')))
+            .unwrap_or_else(|never| match never {});
+    }
+
     /// Navigate back 1 page.
     pub fn back(&self) {
         self.go(-1);
@@ -146,6 +367,61 @@ impl Navigator {
         )
     }
 
+    /// Pushes the route at `path`, relative to the route of `R` that matches the current location.
+    ///
+    /// `path` is resolved segment by segment against the path of that route, without the
+    /// basename: `..` goes up one level, `.` stays, and a leading `/` starts at the root of the
+    /// router. On `/posts/1/view`, `../edit` pushes `/posts/1/edit`.
+    ///
+    /// A route ending with a `{*rest}` parameter is the parent of a nested section, and paths are
+    /// resolved against the part of the location it matches before `rest`. With
+    /// `#[at("/posts/{id}/comments/{*rest}")]` matching `/posts/1/comments/7`, `..` pushes
+    /// `/posts/1`. If no route of `R` matches, the current location is used.
+    ///
+    /// Returns the route that was pushed, or [`NavigationError::NoRouteMatch`] without navigating
+    /// if no route of `R` matches the resolved path.
+    pub fn push_relative<R>(&self, path: &str) -> NavigationResult<R>
+    where
+        R: Routable,
+    {
+        let route = recognize_exact(&self.resolve_relative::<R>(path))?;
+        self.push(&route);
+        Ok(route)
+    }
+
+    /// Same as `.push_relative()` but replaces the current history entry.
+    pub fn replace_relative<R>(&self, path: &str) -> NavigationResult<R>
+    where
+        R: Routable,
+    {
+        let route = recognize_exact(&self.resolve_relative::<R>(path))?;
+        self.replace(&route);
+        Ok(route)
+    }
+
+    /// Pushes the route of `R` at a [`RoutePath`].
+    ///
+    /// Returns the route that was pushed, or [`NavigationError::NoRouteMatch`]
+    /// without navigating if no route of `R` matches the path.
+    pub fn push_path<R>(&self, path: &RoutePath) -> NavigationResult<R>
+    where
+        R: Routable,
+    {
+        let route = path.recognize()?;
+        self.push(&route);
+        Ok(route)
+    }
+
+    /// Same as `.push_path()` but replaces the current history entry.
+    pub fn replace_path<R>(&self, path: &RoutePath) -> NavigationResult<R>
+    where
+        R: Routable,
+    {
+        let route = path.recognize()?;
+        self.replace(&route);
+        Ok(route)
+    }
+
     /// Returns the Navigator kind.
     pub fn kind(&self) -> NavigatorKind {
         match &self.inner {
@@ -168,6 +444,41 @@ impl Navigator {
         }
     }
 
+    /// Resolves a relative `path` against the route of `R` matching the current location, without
+    /// the basename.
+    fn resolve_relative<R>(&self, path: &str) -> String
+    where
+        R: Routable,
+    {
+        let location = self.inner.location();
+        let current = self.strip_basename(location.path().into());
+        let current = match recognize_exact::<R>(&current) {
+            Ok(route) => matched_path(&route, &current),
+            Err(_) => current.into_owned(),
+        };
+
+        let mut segments: Vec<&str> = if path.starts_with('/') {
+            Vec::new()
+        } else {
+            current.split('/').filter(|s| !s.is_empty()).collect()
+        };
+        for segment in path.split('/') {
+            match segment {
+                "" | "." => {}
+                ".." => {
+                    segments.pop();
+                }
+                segment => segments.push(segment),
+            }
+        }
+
+        let mut resolved = format!("/{}", segments.join("/"));
+        if path.ends_with('/') && !segments.is_empty() {
+            resolved.push('/');
+        }
+        resolved
+    }
+
     pub(crate) fn strip_basename<'a>(&self, path: Cow<'a, str>) -> Cow<'a, str> {
         match self.basename() {
             Some(m) => {
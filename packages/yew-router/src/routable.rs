@@ -1,4 +1,9 @@
 use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use yew::html::{BaseComponent, ChildrenProps};
+use yew::{Html, html};
 
 pub use yew_router_macro::Routable;
 
@@ -26,8 +31,193 @@ pub trait Routable: Clone + PartialEq {
     /// The route to redirect to on 404
     fn not_found_route() -> Option<Self>;
 
+    /// How paths that only differ from a route by a trailing slash are matched
+    fn trailing_slash() -> TrailingSlash {
+        TrailingSlash::Strict
+    }
+
     /// Match a route based on the path
     fn recognize(pathname: &str) -> Option<Self>;
+
+    /// Lists the routes in declaration order, along with their parameters and metadata
+    fn manifest() -> &'static [RouteInfo] {
+        &[]
+    }
+
+    /// The entry of [`manifest`](Self::manifest) describing this route
+    fn route_info(&self) -> Option<&'static RouteInfo> {
+        None
+    }
+
+    /// The metadata of this route, from its `route` and `meta` attributes
+    fn meta(&self) -> RouteMeta {
+        self.route_info().map(RouteMeta::new).unwrap_or_default()
+    }
+}
+
+/// Metadata of a route.
+///
+/// The typed fields are set with `#[route(title = "Settings", layout = AdminLayout,
+/// requires_auth)]` on a variant. Any other key of the `route` attribute, or of a
+/// `#[meta(key = value)]` attribute, is metadata of the application, which is read with
+/// [`get`](Self::get), [`get_as`](Self::get_as) or [`flag`](Self::flag):
+///
+/// ```
+/// # use yew_router::prelude::*;
+/// #[derive(Clone, PartialEq, Routable)]
+/// enum Route {
+///     #[at("/settings")]
+///     #[route(title = "Settings", section = "admin", order = 2, beta)]
+///     Settings,
+/// }
+///
+/// let meta = Route::Settings.meta();
+/// assert_eq!(meta.get("section"), Some("admin"));
+/// assert_eq!(meta.get_as::<u32>("order"), Some(2));
+/// assert!(meta.flag("beta"));
+/// ```
+///
+/// The metadata of a route is returned by [`Routable::meta`] or
+/// [`use_route_meta`](crate::hooks::use_route_meta).
+///
+/// More typed fields may be added in the future, so it cannot be built with a struct expression.
+#[derive(Debug, Clone, Copy, Default)]
+#[non_exhaustive]
+pub struct RouteMeta {
+    /// Title of the page.
+    pub title: Option<&'static str>,
+    /// Layout that [`Switch`](crate::Switch) renders the route in.
+    pub layout: Option<Layout>,
+    /// Whether the route is only accessible to authenticated users.
+    ///
+    /// The router does not enforce it, it is up to guards of the application to do so.
+    pub requires_auth: bool,
+    /// Key-value pairs from the `meta` and `route` attributes of the route.
+    pairs: &'static [(&'static str, &'static str)],
+}
+
+impl RouteMeta {
+    /// Creates the metadata of the route described by `info`, with its key-value pairs and without
+    /// typed metadata.
+    pub fn new(info: &'static RouteInfo) -> Self {
+        Self {
+            pairs: info.meta,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the value of the metadata `key`, from `#[meta(key = value)]` or
+    /// `#[route(key = value)]`, if it is set.
+    pub fn get(&self, key: &str) -> Option<&'static str> {
+        self.pairs.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+    }
+
+    /// Returns the value of the metadata `key` parsed as a `T`, or `None` if it is not set or
+    /// cannot be parsed.
+    pub fn get_as<T>(&self, key: &str) -> Option<T>
+    where
+        T: FromStr,
+    {
+        self.get(key)?.parse().ok()
+    }
+
+    /// Returns `true` if the metadata `key` is set to `true`, as with `#[route(key)]`.
+    pub fn flag(&self, key: &str) -> bool {
+        self.get_as(key).unwrap_or(false)
+    }
+}
+
+/// A component that wraps the content of a route.
+///
+/// The component takes its content as [`children`](ChildrenProps).
+#[derive(Clone, Copy)]
+pub struct Layout {
+    render: fn(Html) -> Html,
+}
+
+impl Layout {
+    /// Creates the layout rendered with the component `C`.
+    pub fn of<C>() -> Self
+    where
+        C: BaseComponent<Properties = ChildrenProps>,
+    {
+        Self {
+            render: |children| html! { <C>{ children }</C> },
+        }
+    }
+
+    /// Renders `children` inside the layout.
+    pub fn render(&self, children: Html) -> Html {
+        (self.render)(children)
+    }
+}
+
+impl fmt::Debug for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Layout").finish_non_exhaustive()
+    }
+}
+
+/// An entry of the route manifest of a [`Routable`].
+///
+/// The derive macro generates one for every variant, which can be used to build
+/// [sitemaps](crate::sitemap::Sitemap) or lists of paths served by the application.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RouteInfo {
+    /// Name of the variant.
+    pub variant: &'static str,
+    /// The route, as written in the `at` attribute.
+    pub path: &'static str,
+    /// The parameters captured by the route.
+    pub params: &'static [RouteParam],
+    /// Whether this is the route marked with `not_found`.
+    pub not_found: bool,
+    /// Key-value pairs from the `meta` attributes of the variant, e.g. `#[meta(priority = 0.8)]`,
+    /// and from the keys of its `route` attribute other than the typed ones.
+    pub meta: &'static [(&'static str, &'static str)],
+}
+
+impl RouteInfo {
+    /// Returns the value of the metadata `key`, if it is set.
+    pub fn meta(&self, key: &str) -> Option<&'static str> {
+        self.meta.iter().find(|(k, _)| *k == key).map(|(_, v)| *v)
+    }
+
+    /// Returns the path of the route if it has no parameters.
+    pub fn static_path(&self) -> Option<String> {
+        self.params
+            .is_empty()
+            .then(|| self.path.replace("{{", "{").replace("}}", "}"))
+    }
+}
+
+/// A parameter captured by a route.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RouteParam {
+    /// Name of the parameter, and of the field it is parsed into.
+    pub name: &'static str,
+    /// Type of the field, e.g. `u32` or `Option<String>`.
+    pub ty: &'static str,
+    /// Whether the parameter is an optional segment, `{/name}`.
+    pub optional: bool,
+    /// Whether the parameter matches the rest of the path, `{*name}`.
+    pub wildcard: bool,
+}
+
+/// How a [`Routable`] matches paths that only differ from one of its routes by a trailing slash.
+///
+/// Set with `#[routable(trailing_slash = "...")]` on the enum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TrailingSlash {
+    /// `/about` and `/about/` are distinct paths, and only match a route declared with the same
+    /// form.
+    #[default]
+    Strict,
+    /// A path that matches no route is matched again with its trailing slash added or removed.
+    Ignore,
+    /// Like [`Ignore`](Self::Ignore), and [`Switch`](crate::Switch) then replaces the path with
+    /// the one of the route.
+    Redirect,
 }
 
 /// A special route that accepts any route.
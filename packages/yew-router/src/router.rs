@@ -3,10 +3,13 @@ use std::borrow::Cow;
 use std::rc::Rc;
 
 use gloo::history::query::Raw;
+use wasm_bindgen::UnwrapThrowExt;
 use yew::prelude::*;
 use yew::virtual_dom::AttrValue;
 
+use crate::AnyRoute;
 use crate::history::{AnyHistory, BrowserHistory, HashHistory, History, Location};
+use crate::hooks::{use_location, use_navigator};
 use crate::navigator::Navigator;
 use crate::utils::{base_url, strip_slash_suffix};
 
@@ -207,3 +210,71 @@ pub fn hash_router(props: &ConcreteRouterProps) -> Html {
         </BaseRouter>
     }
 }
+
+/// Props for [`LocaleRouter`].
+#[derive(Properties, PartialEq, Clone)]
+pub struct LocaleRouterProps {
+    #[prop_or_default]
+    pub children: Html,
+    /// The supported locales, e.g. `en` and `de`.
+    pub locales: Vec<AttrValue>,
+    /// The locale of paths without a supported locale prefix.
+    ///
+    /// During server-side rendering, this is the locale of the request, e.g. from its
+    /// `Accept-Language` header.
+    pub fallback: AttrValue,
+}
+
+/// A router for paths prefixed with a locale, e.g. `/en/about` and `/de/about`.
+///
+/// It must be used inside of a [`Router`]. The first segment of the path after the basename is
+/// matched against `locales`, and stripped before the routes are recognized by its children, so
+/// `/de/about` is recognized as `/about`. [`Link`](crate::components::Link) and
+/// [`Navigator`] re-apply the current locale, which is returned by
+/// [`use_locale`](crate::hooks::use_locale).
+///
+/// A path without a supported locale is replaced with the same path prefixed with `fallback`.
+/// During server-side rendering, it is rendered with `fallback` as is.
+#[component(LocaleRouter)]
+pub fn locale_router(props: &LocaleRouterProps) -> Html {
+    let navigator = use_navigator().expect_throw("LocaleRouter must be used inside of a Router");
+    let location = use_location().expect_throw("LocaleRouter must be used inside of a Router");
+
+    let path = navigator.strip_basename(location.path().into());
+    let prefix = path.split('/').nth(1).unwrap_or_default();
+    let locale = props
+        .locales
+        .iter()
+        .find(|locale| locale.as_str() == prefix)
+        .cloned();
+
+    let redirect = locale.is_none().then(|| {
+        (
+            props.fallback.clone(),
+            path.clone().into_owned(),
+            location.query_str().trim_start_matches('?').to_owned(),
+        )
+    });
+    {
+        let navigator = navigator.clone();
+        use_effect_with(redirect, move |redirect| {
+            if let Some((fallback, path, query)) = redirect {
+                navigator
+                    .with_locale(fallback)
+                    .replace_with_query(&AnyRoute::new(path.as_str()), Raw(query.as_str()))
+                    .unwrap_or_else(|never| match never {});
+            }
+        });
+    }
+
+    let locale = locale.unwrap_or_else(|| props.fallback.clone());
+    let navi_ctx = NavigatorContext {
+        navigator: navigator.with_locale(&locale),
+    };
+
+    html! {
+        <ContextProvider<NavigatorContext> context={navi_ctx}>
+            { props.children.clone() }
+        </ContextProvider<NavigatorContext>>
+    }
+}
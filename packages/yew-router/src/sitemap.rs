@@ -0,0 +1,140 @@
+//! Rendering `sitemap.xml` from the manifest of a [`Routable`].
+
+use std::fmt::Write;
+
+use crate::Routable;
+
+/// The metadata keys of a route that are copied into its sitemap entry.
+const SITEMAP_META: [&str; 3] = ["lastmod", "changefreq", "priority"];
+
+/// Builder for a [`sitemap.xml`](https://www.sitemaps.org/protocol.html) document.
+///
+/// The `lastmod`, `changefreq` and `priority` of an entry are read from the `meta` attributes of
+/// its route.
+///
+/// # Example
+///
+/// ```
+/// # use yew_router::prelude::*;
+/// # use yew_router::sitemap::Sitemap;
+/// #[derive(Debug, Clone, PartialEq, Routable)]
+/// enum Route {
+///     #[at("/")]
+///     #[meta(priority = 1.0, changefreq = "daily")]
+///     Home,
+///     #[at("/post/{id}")]
+///     #[meta(priority = 0.8)]
+///     Post { id: u32 },
+///     #[at("/404")]
+///     #[not_found]
+///     NotFound,
+/// }
+///
+/// let xml = Sitemap::new("https://example.com")
+///     .static_routes::<Route>()
+///     .routes((1..=2).map(|id| Route::Post { id }))
+///     .render();
+///
+/// assert!(xml.contains("<loc>https://example.com/post/2</loc>"));
+/// assert!(!xml.contains("/404"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sitemap {
+    base_url: String,
+    entries: Vec<SitemapEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SitemapEntry {
+    path: String,
+    meta: Vec<(&'static str, &'static str)>,
+}
+
+impl Sitemap {
+    /// Creates an empty sitemap whose locations are prefixed with `base_url`, e.g.
+    /// `https://example.com`.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        let mut base_url = base_url.into();
+        while base_url.ends_with('/') {
+            base_url.pop();
+        }
+
+        Self {
+            base_url,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Adds every route of `R` that has no parameters, except the `not_found` route.
+    pub fn static_routes<R: Routable>(mut self) -> Self {
+        for info in R::manifest().iter().filter(|info| !info.not_found) {
+            if let Some(path) = info.static_path() {
+                self.entries.push(SitemapEntry {
+                    path,
+                    meta: sitemap_meta(info.meta),
+                });
+            }
+        }
+        self
+    }
+
+    /// Adds a route, with the values of its parameters.
+    pub fn route<R: Routable>(mut self, route: &R) -> Self {
+        self.entries.push(SitemapEntry {
+            path: route.to_path(),
+            meta: route
+                .route_info()
+                .map(|info| sitemap_meta(info.meta))
+                .unwrap_or_default(),
+        });
+        self
+    }
+
+    /// Adds every route of an iterator, see [`route`](Self::route).
+    pub fn routes<R: Routable>(self, routes: impl IntoIterator<Item = R>) -> Self {
+        routes
+            .into_iter()
+            .fold(self, |sitemap, route| sitemap.route(&route))
+    }
+
+    /// Renders the `sitemap.xml` document.
+    pub fn render(&self) -> String {
+        let mut xml = String::from(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset \
+             xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n",
+        );
+        for entry in &self.entries {
+            xml.push_str("  <url>\n");
+            let loc = format!("{}{}", self.base_url, entry.path);
+            let _ = writeln!(xml, "    <loc>{}</loc>", escape_xml(&loc));
+            for (key, value) in &entry.meta {
+                let _ = writeln!(xml, "    <{key}>{}</{key}>", escape_xml(value));
+            }
+            xml.push_str("  </url>\n");
+        }
+        xml.push_str("</urlset>\n");
+        xml
+    }
+}
+
+fn sitemap_meta(meta: &[(&'static str, &'static str)]) -> Vec<(&'static str, &'static str)> {
+    SITEMAP_META
+        .iter()
+        .filter_map(|key| meta.iter().find(|(k, _)| k == key).copied())
+        .collect()
+}
+
+fn escape_xml(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -2,7 +2,10 @@
 
 use yew::prelude::*;
 
+use crate::TrailingSlash;
 use crate::prelude::*;
+use crate::query::Raw;
+use crate::utils::toggle_trailing_slash;
 
 /// Props for [`Switch`]
 #[derive(Properties, PartialEq, Clone)]
@@ -23,25 +26,72 @@ where
 /// If such a route is provided, it redirects to the specified route.
 /// Otherwise `html! {}` is rendered and a message is logged to console
 /// stating that no route can be matched.
+///
+/// If `R` is declared with `#[routable(trailing_slash = "redirect")]` and the current path only
+/// matches a route once its trailing slash is added or removed, the path is replaced with the one
+/// of the route.
+///
+/// If the matched route has a [`layout`](crate::RouteMeta::layout), the rendered route is wrapped
+/// in it.
 /// See the [crate level document][crate] for more information.
 #[component]
 pub fn Switch<R>(props: &SwitchProps<R>) -> Html
 where
     R: Routable + 'static,
 {
-    let route = use_route::<R>();
-
-    let route = props
-        .pathname
-        .as_ref()
-        .and_then(|p| R::recognize(p))
-        .or(route);
+    let route = use_switch_route::<R>(props.pathname.as_deref());
 
     match route {
-        Some(route) => props.render.emit(route),
+        Some(route) => render_route(&props.render, route),
         None => {
             tracing::warn!("no route matched");
             Html::default()
         }
     }
 }
+
+/// Returns the route to render, recognized from `pathname` if it is set or from the current
+/// location otherwise, and applies the [`TrailingSlash::Redirect`] policy of `R`.
+#[hook]
+pub(crate) fn use_switch_route<R>(pathname: Option<&str>) -> Option<R>
+where
+    R: Routable + 'static,
+{
+    let route = use_route::<R>();
+    let navigator = use_navigator();
+    let location = use_location();
+
+    let redirect = match (&route, &navigator, &location) {
+        (Some(route), Some(navigator), Some(location))
+            if pathname.is_none() && R::trailing_slash() == TrailingSlash::Redirect =>
+        {
+            let path = navigator.strip_basename(location.path().into());
+            let canonical = route.to_path();
+            (toggle_trailing_slash(&path).as_deref() == Some(canonical.as_str()))
+                .then(|| (route.clone(), location.query_str().to_owned()))
+        }
+        _ => None,
+    };
+    use_effect_with(redirect, move |redirect| {
+        if let (Some((route, query)), Some(navigator)) = (redirect, navigator) {
+            navigator
+                .replace_with_query(route, Raw(query.as_str()))
+                .unwrap_or_else(|never| match never {});
+        }
+    });
+
+    pathname.and_then(R::recognize).or(route)
+}
+
+/// Renders `route` with `render`, inside the layout of the route if it has one.
+pub(crate) fn render_route<R>(render: &Callback<R, Html>, route: R) -> Html
+where
+    R: Routable,
+{
+    let layout = route.meta().layout;
+    let html = render.emit(route);
+    match layout {
+        Some(layout) => layout.render(html),
+        None => html,
+    }
+}
@@ -0,0 +1,284 @@
+//! The [`TransitionSwitch`] Component.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use js_sys::{Function, Promise, Reflect};
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::{JsCast, JsValue};
+use yew::platform::spawn_local;
+use yew::platform::time::sleep;
+use yew::prelude::*;
+use yew::virtual_dom::AttrValue;
+
+use crate::Routable;
+use crate::switch::{render_route, use_switch_route};
+
+/// Props for [`TransitionSwitch`]
+#[derive(Properties, PartialEq, Clone)]
+pub struct TransitionSwitchProps<R>
+where
+    R: Routable,
+{
+    /// Callback which returns [`Html`] to be rendered for the current route.
+    pub render: Callback<R, Html>,
+    #[prop_or_default]
+    pub pathname: Option<String>,
+    /// Class of the element wrapping each route. `{class}-enter` is added to it while the route
+    /// enters, and `{class}-exit` while it exits.
+    #[prop_or(AttrValue::Static("route"))]
+    pub class: AttrValue,
+    /// Milliseconds after which an exiting route is removed if its exit animation has not
+    /// finished yet.
+    #[prop_or(500)]
+    pub timeout: u32,
+    /// Whether to use the [View Transitions API] when the browser supports it, instead of the
+    /// enter and exit classes.
+    ///
+    /// [View Transitions API]: https://developer.mozilla.org/en-US/docs/Web/API/View_Transition_API
+    #[prop_or(true)]
+    pub view_transition: bool,
+}
+
+/// A [`Switch`](crate::Switch) that animates the transitions between routes.
+///
+/// Every route is rendered inside of a `<div>` with the `class` of the props. When the route
+/// changes, the outgoing route stays mounted with the `{class}-exit` class until an
+/// `animationend` or `transitionend` event is fired on its `<div>`, or until `timeout` has
+/// elapsed. The incoming route is mounted at the same time with the `{class}-enter` class.
+///
+/// ```css
+/// .route { grid-area: 1 / 1; }
+/// .route-enter { animation: fade-in 200ms; }
+/// .route-exit { animation: fade-out 200ms forwards; }
+/// ```
+///
+/// If `view_transition` is set and the browser supports the [View Transitions API], the route is
+/// swapped inside of `document.startViewTransition()` instead, which animates the snapshots of
+/// the page with the `::view-transition-*` pseudo-elements.
+///
+/// The `<div>` of a route is keyed by its path, so the components of the route are not remounted
+/// when it starts exiting, or when it is shown again while it is still exiting.
+///
+/// [View Transitions API]: https://developer.mozilla.org/en-US/docs/Web/API/View_Transition_API
+#[component]
+pub fn TransitionSwitch<R>(props: &TransitionSwitchProps<R>) -> Html
+where
+    R: Routable + 'static,
+{
+    let route = use_switch_route::<R>(props.pathname.as_deref());
+    let state = use_reducer({
+        let route = route.clone();
+        move || Transition::new(route)
+    });
+    let resolve_update = use_mut_ref(|| None::<Function>);
+
+    {
+        // The update of a view transition is done once the new route is rendered.
+        let resolve_update = resolve_update.clone();
+        use_effect_with(state.next_id, move |_| {
+            if let Some(resolve) = resolve_update.borrow_mut().take() {
+                let _ = resolve.call0(&JsValue::NULL);
+            }
+        });
+    }
+
+    {
+        let dispatcher = state.dispatcher();
+        let shown = state.current.as_ref().map(|current| current.route.clone());
+        let exiting_id = state.current.as_ref().map(|current| current.id);
+        let timeout = props.timeout;
+        let view_transition = props.view_transition;
+        use_effect_with(route, move |route| {
+            if *route == shown {
+                return;
+            }
+            let route = route.clone();
+
+            if view_transition
+                && start_view_transition(update_in_view_transition(
+                    dispatcher.clone(),
+                    resolve_update,
+                    route.clone(),
+                ))
+            {
+                return;
+            }
+
+            dispatcher.dispatch(TransitionAction::Show {
+                route,
+                animate: true,
+            });
+            if let Some(id) = exiting_id {
+                spawn_local(async move {
+                    sleep(Duration::from_millis(timeout.into())).await;
+                    dispatcher.dispatch(TransitionAction::Remove(id));
+                });
+            }
+        });
+    }
+
+    let class = &props.class;
+    let exiting = state.exiting.iter().map(|exiting| {
+        let id = exiting.id;
+        let remove = {
+            let dispatcher = state.dispatcher();
+            move |e: &Event| {
+                // Ignore the animations of the content of the route.
+                if e.target() == e.current_target() {
+                    dispatcher.dispatch(TransitionAction::Remove(id));
+                }
+            }
+        };
+        let onanimationend = {
+            let remove = remove.clone();
+            Callback::from(move |e: AnimationEvent| remove(&e))
+        };
+        let ontransitionend = Callback::from(move |e: TransitionEvent| remove(&e));
+
+        html! {
+            <div
+                key={exiting.route.to_path()}
+                class={classes!(class, format!("{class}-exit"))}
+                {onanimationend}
+                {ontransitionend}
+            >
+                { render_route(&props.render, exiting.route.clone()) }
+            </div>
+        }
+    });
+    let current = state.current.as_ref().map(|current| {
+        let enter = state.entering.then(|| format!("{class}-enter"));
+        html! {
+            <div key={current.route.to_path()} class={classes!(class, enter)}>
+                { render_route(&props.render, current.route.clone()) }
+            </div>
+        }
+    });
+    if current.is_none() {
+        tracing::warn!("no route matched");
+    }
+
+    // Both are in one keyed list, so a route moving between them is not remounted.
+    exiting.chain(current).collect()
+}
+
+/// Calls `document.startViewTransition(update)`, returning `false` if the browser does not
+/// support view transitions.
+fn start_view_transition(update: impl FnOnce() -> Promise + 'static) -> bool {
+    let document = gloo::utils::document();
+    let Ok(start) = Reflect::get(&document, &JsValue::from_str("startViewTransition")) else {
+        return false;
+    };
+    let Some(start) = start.dyn_ref::<Function>() else {
+        return false;
+    };
+
+    start
+        .call1(&document, &Closure::once_into_js(update))
+        .is_ok()
+}
+
+/// Returns the update callback of a view transition, which shows `route` and resolves once it has
+/// been rendered.
+fn update_in_view_transition<R>(
+    dispatcher: UseReducerDispatcher<Transition<R>>,
+    resolve_update: Rc<RefCell<Option<Function>>>,
+    route: Option<R>,
+) -> impl FnOnce() -> Promise + 'static
+where
+    R: Routable + 'static,
+{
+    move || {
+        Promise::new(&mut |resolve, _reject| {
+            *resolve_update.borrow_mut() = Some(resolve);
+            dispatcher.dispatch(TransitionAction::Show {
+                route: route.clone(),
+                animate: false,
+            });
+        })
+    }
+}
+
+#[derive(Clone, PartialEq)]
+struct Rendered<R> {
+    id: u32,
+    route: R,
+}
+
+#[derive(PartialEq)]
+struct Transition<R> {
+    next_id: u32,
+    current: Option<Rendered<R>>,
+    /// Whether `current` was shown with an enter animation.
+    entering: bool,
+    exiting: Vec<Rendered<R>>,
+}
+
+impl<R> Transition<R> {
+    fn new(route: Option<R>) -> Self {
+        Self {
+            next_id: 1,
+            current: route.map(|route| Rendered { id: 0, route }),
+            entering: false,
+            exiting: Vec::new(),
+        }
+    }
+}
+
+enum TransitionAction<R> {
+    Show { route: Option<R>, animate: bool },
+    Remove(u32),
+}
+
+impl<R> Reducible for Transition<R>
+where
+    R: Routable,
+{
+    type Action = TransitionAction<R>;
+
+    fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
+        match action {
+            TransitionAction::Show { route, animate } => {
+                // A route shown again while it is exiting becomes the current route.
+                let mut exiting: Vec<_> = self
+                    .exiting
+                    .iter()
+                    .filter(|exiting| Some(&exiting.route) != route.as_ref())
+                    .cloned()
+                    .collect();
+                if animate {
+                    exiting.extend(self.current.clone());
+                }
+
+                Rc::new(Self {
+                    next_id: self.next_id + 1,
+                    current: route.map(|route| Rendered {
+                        id: self.next_id,
+                        route,
+                    }),
+                    entering: animate,
+                    exiting,
+                })
+            }
+            TransitionAction::Remove(id) => {
+                if !self.exiting.iter().any(|exiting| exiting.id == id) {
+                    return self;
+                }
+
+                Rc::new(Self {
+                    next_id: self.next_id,
+                    current: self.current.clone(),
+                    entering: self.entering,
+                    exiting: self
+                        .exiting
+                        .iter()
+                        .filter(|exiting| exiting.id != id)
+                        .cloned()
+                        .collect(),
+                })
+            }
+        }
+    }
+}
@@ -6,6 +6,15 @@ pub(crate) fn strip_slash_suffix(path: &str) -> &str {
     path.strip_suffix('/').unwrap_or(path)
 }
 
+/// Adds a trailing slash to `path`, or removes it. Returns `None` for the root path.
+pub(crate) fn toggle_trailing_slash(path: &str) -> Option<String> {
+    match path.strip_suffix('/') {
+        Some("") => None,
+        Some(path) => Some(path.to_owned()),
+        None => Some(format!("{path}/")),
+    }
+}
+
 static BASE_URL_LOADED: std::sync::Once = std::sync::Once::new();
 thread_local! {
     static BASE_URL: RefCell<Option<String>> = const { RefCell::new(None) };
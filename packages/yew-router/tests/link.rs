@@ -1,6 +1,7 @@
 // TODO: remove the cfg after wasm-bindgen-test stops emitting the function unconditionally
 #![cfg(all(target_arch = "wasm32", any(target_os = "unknown", target_os = "none")))]
 
+use std::cell::RefCell;
 use std::sync::atomic::{AtomicU8, Ordering};
 use std::time::Duration;
 
@@ -47,8 +48,12 @@ impl SearchParams {
 
 #[derive(Debug, Clone, Copy, PartialEq, Routable)]
 enum Routes {
+    #[at("/")]
+    Home,
     #[at("/posts")]
     Posts,
+    #[at("/posts/{id}")]
+    Post { id: u32 },
     #[at("/search")]
     Search,
 }
@@ -319,6 +324,148 @@ async fn link_in_hash_router() {
     handle.destroy();
 }
 
+thread_local! {
+    static PREFETCHED: RefCell<Vec<Routes>> = const { RefCell::new(Vec::new()) };
+}
+
+#[component(RootForPrefetch)]
+fn root_for_prefetch() -> Html {
+    let prefetch = Callback::from(|route: Routes| PREFETCHED.with(|p| p.borrow_mut().push(route)));
+
+    html! {
+        <BrowserRouter>
+            <Link<Routes> to={Routes::Search} {prefetch}>{ "Search" }</Link<Routes>>
+        </BrowserRouter>
+    }
+}
+
+async fn link_with_prefetch() {
+    let div = gloo::utils::document().create_element("div").unwrap();
+    let _ = div.set_attribute("id", "prefetch-router");
+    let _ = gloo::utils::body().append_child(&div);
+    let handle = yew::Renderer::<RootForPrefetch>::with_root(div).render();
+
+    sleep(Duration::ZERO).await;
+
+    let link = gloo::utils::document()
+        .query_selector("#prefetch-router a")
+        .unwrap()
+        .expect("No such link");
+    for event_type in ["mouseenter", "focus"] {
+        link.dispatch_event(&web_sys::Event::new(event_type).unwrap())
+            .unwrap();
+    }
+
+    sleep(Duration::ZERO).await;
+
+    assert_eq!(
+        PREFETCHED.with(|p| p.take()),
+        vec![Routes::Search, Routes::Search]
+    );
+
+    handle.destroy();
+}
+
+#[component(RootForNavLink)]
+fn root_for_nav_link() -> Html {
+    html! {
+        <BrowserRouter basename="/base/">
+            <ul>
+                <li class="home">
+                    <NavLink<Routes> to={Routes::Home} active_classes="active">
+                        { "Home" }
+                    </NavLink<Routes>>
+                </li>
+                <li class="posts">
+                    <NavLink<Routes> to={Routes::Posts} classes="nav" active_classes="active">
+                        { "Posts" }
+                    </NavLink<Routes>>
+                </li>
+                <li class="posts-exact">
+                    <NavLink<Routes> to={Routes::Posts} exact=true active_classes="active">
+                        { "Posts" }
+                    </NavLink<Routes>>
+                </li>
+                <li class="post">
+                    <NavLink<Routes> to={Routes::Post { id: 1 }} active_classes="active">
+                        { "Post 1" }
+                    </NavLink<Routes>>
+                </li>
+                <li class="search">
+                    <NavLink<Routes> to={Routes::Search} active_classes="active">
+                        { "Search" }
+                    </NavLink<Routes>>
+                </li>
+            </ul>
+        </BrowserRouter>
+    }
+}
+
+/// Returns the classes and the `aria-current` attribute of the link in the `li` with `class`.
+fn nav_link_state(class: &str) -> (String, Option<String>) {
+    let link = gloo::utils::document()
+        .query_selector(&format!("#nav-link-router li.{class} > a"))
+        .unwrap()
+        .unwrap_or_else(|| panic!("No such link: {class}"));
+    (link.class_name(), link.get_attribute("aria-current"))
+}
+
+async fn nav_link_active() {
+    let div = gloo::utils::document().create_element("div").unwrap();
+    let _ = div.set_attribute("id", "nav-link-router");
+    let _ = gloo::utils::body().append_child(&div);
+    let handle = yew::Renderer::<RootForNavLink>::with_root(div).render();
+
+    sleep(Duration::ZERO).await;
+
+    let page = Some("page".to_string());
+    assert_eq!(nav_link_state("posts"), ("nav".to_string(), None));
+
+    click("#nav-link-router li.posts > a");
+    sleep(Duration::ZERO).await;
+
+    assert_eq!(
+        "/base/posts",
+        gloo::utils::window().location().pathname().unwrap()
+    );
+    assert_eq!(
+        nav_link_state("posts"),
+        ("nav active".to_string(), page.clone())
+    );
+    assert_eq!(
+        nav_link_state("posts-exact"),
+        ("active".to_string(), page.clone())
+    );
+    assert_eq!(nav_link_state("post"), (String::new(), None));
+    assert_eq!(nav_link_state("search"), (String::new(), None));
+    assert_eq!(nav_link_state("home"), (String::new(), None));
+
+    click("#nav-link-router li.post > a");
+    sleep(Duration::ZERO).await;
+
+    assert_eq!(
+        nav_link_state("posts"),
+        ("nav active".to_string(), page.clone())
+    );
+    assert_eq!(nav_link_state("posts-exact"), (String::new(), None));
+    assert_eq!(nav_link_state("post"), ("active".to_string(), page.clone()));
+    assert_eq!(nav_link_state("search"), (String::new(), None));
+    assert_eq!(nav_link_state("home"), (String::new(), None));
+
+    click("#nav-link-router li.home > a");
+    sleep(Duration::ZERO).await;
+
+    assert_eq!(
+        "/base/",
+        gloo::utils::window().location().pathname().unwrap()
+    );
+    assert_eq!(nav_link_state("home"), ("active".to_string(), page));
+    assert_eq!(nav_link_state("posts"), ("nav".to_string(), None));
+    assert_eq!(nav_link_state("post"), (String::new(), None));
+
+    handle.destroy();
+}
+
 // These cannot be run in concurrently because they all read/write the URL.
 #[test]
 async fn sequential_tests() {
@@ -326,4 +473,6 @@ async fn sequential_tests() {
     link_in_browser_router().await;
     link_with_basename(false).await;
     link_with_basename(true).await;
+    link_with_prefetch().await;
+    nav_link_active().await;
 }
@@ -0,0 +1,110 @@
+// TODO: remove the cfg after wasm-bindgen-test stops emitting the function unconditionally
+#![cfg(all(target_arch = "wasm32", any(target_os = "unknown", target_os = "none")))]
+
+use std::cell::RefCell;
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+use yew::functional::component;
+use yew::platform::time::sleep;
+use yew::prelude::*;
+use yew_router::history::{AnyHistory, History, MemoryHistory};
+use yew_router::prelude::*;
+use yew_router::query::Raw;
+
+mod utils;
+use utils::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Debug, Clone, PartialEq, Routable)]
+enum Routes {
+    #[at("/")]
+    Home,
+    #[at("/posts/{id}")]
+    Post { id: u32 },
+    #[at("/404")]
+    #[not_found]
+    NotFound,
+}
+
+thread_local! {
+    static NAVIGATOR: RefCell<Option<Navigator>> = const { RefCell::new(None) };
+}
+
+#[component(Page)]
+fn page() -> Html {
+    let navigator = use_navigator().unwrap();
+    NAVIGATOR.with(|n| *n.borrow_mut() = Some(navigator));
+    let locale = use_locale().unwrap();
+    let route = use_route::<Routes>().unwrap();
+
+    html! {
+        <>
+            <p id="locale">{ locale }</p>
+            <p id="route">{ format!("{route:?}") }</p>
+            <Link<Routes> to={Routes::Post { id: 2 }}>{ "Next" }</Link<Routes>>
+        </>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct RootProps {
+    history: AnyHistory,
+}
+
+#[component(Root)]
+fn root(props: &RootProps) -> Html {
+    html! {
+        <Router history={props.history.clone()} basename="/base">
+            <LocaleRouter locales={vec!["en".into(), "de".into()]} fallback="en">
+                <Page />
+            </LocaleRouter>
+        </Router>
+    }
+}
+
+fn navigator() -> Navigator {
+    NAVIGATOR.with(|n| n.borrow().clone().unwrap())
+}
+
+#[test]
+async fn locale_router() {
+    let history = MemoryHistory::with_entries(["/base/de/posts/1"]);
+
+    let div = gloo::utils::document().create_element("div").unwrap();
+    let _ = div.set_attribute("id", "locale-router");
+    let _ = gloo::utils::body().append_child(&div);
+    let _handle = yew::Renderer::<Root>::with_root_and_props(
+        div,
+        RootProps {
+            history: history.clone().into(),
+        },
+    )
+    .render();
+
+    sleep(Duration::ZERO).await;
+    assert_eq!("de", obtain_result_by_id("locale"));
+    assert_eq!("Post { id: 1 }", obtain_result_by_id("route"));
+    assert_eq!("/base/de/posts/2", link_href("#locale-router a"));
+
+    navigator().push(&Routes::Home);
+    sleep(Duration::ZERO).await;
+    assert_eq!("/base/de/", history.location().path());
+    assert_eq!("Home", obtain_result_by_id("route"));
+
+    history
+        .push_with_query("/base/posts/3", Raw("page=2"))
+        .unwrap();
+    sleep(Duration::ZERO).await;
+    assert_eq!("/base/en/posts/3", history.location().path());
+    assert_eq!("?page=2", history.location().query_str());
+    assert_eq!("en", obtain_result_by_id("locale"));
+    assert_eq!("Post { id: 3 }", obtain_result_by_id("route"));
+
+    navigator().switch_locale("de");
+    sleep(Duration::ZERO).await;
+    assert_eq!("/base/de/posts/3", history.location().path());
+    assert_eq!("?page=2", history.location().query_str());
+    assert_eq!("de", obtain_result_by_id("locale"));
+}
@@ -0,0 +1,146 @@
+// TODO: remove the cfg after wasm-bindgen-test stops emitting the function unconditionally
+#![cfg(all(target_arch = "wasm32", any(target_os = "unknown", target_os = "none")))]
+
+use std::cell::RefCell;
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+use yew::functional::component;
+use yew::platform::time::sleep;
+use yew::prelude::*;
+use yew_router::history::{AnyHistory, History, MemoryHistory};
+use yew_router::prelude::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Debug, Clone, PartialEq, Routable)]
+enum Routes {
+    #[at("/")]
+    Home,
+    #[at("/posts/{id}")]
+    Post { id: u32 },
+    #[at("/posts/{id}/view")]
+    View { id: u32 },
+    #[at("/posts/{id}/edit")]
+    Edit { id: u32 },
+    #[at("/posts/{id}/comments/{*rest}")]
+    Comments { id: u32, rest: String },
+    #[at("/404")]
+    #[not_found]
+    NotFound,
+}
+
+#[derive(Debug, Clone, PartialEq, Routable)]
+enum CommentRoutes {
+    #[at("/comments/{id}")]
+    Comment { id: u32 },
+}
+
+thread_local! {
+    static NAVIGATOR: RefCell<Option<Navigator>> = const { RefCell::new(None) };
+}
+
+#[component(Capture)]
+fn capture() -> Html {
+    let navigator = use_navigator().unwrap();
+    NAVIGATOR.with(|n| *n.borrow_mut() = Some(navigator));
+    Html::default()
+}
+
+#[derive(Properties, PartialEq)]
+struct RootProps {
+    history: AnyHistory,
+}
+
+#[component(Root)]
+fn root(props: &RootProps) -> Html {
+    html! {
+        <Router history={props.history.clone()} basename="/base">
+            <Capture />
+        </Router>
+    }
+}
+
+fn navigator() -> Navigator {
+    NAVIGATOR.with(|n| n.borrow().clone().unwrap())
+}
+
+#[test]
+fn route_path_joins_parent_and_child() {
+    let path = RoutePath::new(&Routes::Post { id: 1 }).join(&CommentRoutes::Comment { id: 7 });
+    assert_eq!(path.as_str(), "/posts/1/comments/7");
+    assert_eq!(
+        path.recognize::<Routes>().unwrap(),
+        Routes::Comments {
+            id: 1,
+            rest: "7".to_string()
+        }
+    );
+
+    let path = RoutePath::new(&Routes::Home).join(&CommentRoutes::Comment { id: 7 });
+    assert_eq!(path.as_str(), "/comments/7");
+    assert!(matches!(
+        path.recognize::<Routes>(),
+        Err(e) if e.path() == "/comments/7"
+    ));
+
+    assert_eq!(
+        RoutePath::new(&Routes::NotFound)
+            .recognize::<Routes>()
+            .unwrap(),
+        Routes::NotFound
+    );
+}
+
+#[test]
+async fn navigate_relative() {
+    let history = MemoryHistory::with_entries(["/base/posts/1/view"]);
+    let div = gloo::utils::document().create_element("div").unwrap();
+    let _ = gloo::utils::body().append_child(&div);
+    let handle = yew::Renderer::<Root>::with_root_and_props(
+        div,
+        RootProps {
+            history: history.clone().into(),
+        },
+    )
+    .render();
+
+    sleep(Duration::ZERO).await;
+
+    assert_eq!(
+        navigator().push_relative::<Routes>("../edit").unwrap(),
+        Routes::Edit { id: 1 }
+    );
+    assert_eq!(history.location().path(), "/base/posts/1/edit");
+
+    assert_eq!(
+        navigator().push_relative::<Routes>("..").unwrap(),
+        Routes::Post { id: 1 }
+    );
+    assert_eq!(history.location().path(), "/base/posts/1");
+
+    assert!(matches!(
+        navigator().push_relative::<Routes>("./missing"),
+        Err(NavigationError::NoRouteMatch(e)) if e.path() == "/posts/1/missing"
+    ));
+    assert_eq!(history.location().path(), "/base/posts/1");
+
+    assert_eq!(
+        navigator().replace_relative::<Routes>("/").unwrap(),
+        Routes::Home
+    );
+    assert_eq!(history.location().path(), "/base/");
+
+    let path = RoutePath::new(&Routes::Post { id: 2 }).join(&CommentRoutes::Comment { id: 3 });
+    navigator().push_path::<Routes>(&path).unwrap();
+    assert_eq!(history.location().path(), "/base/posts/2/comments/3");
+
+    // The comments are a nested section, which is left with `..`.
+    assert_eq!(
+        navigator().push_relative::<Routes>("../edit").unwrap(),
+        Routes::Edit { id: 2 }
+    );
+    assert_eq!(history.location().path(), "/base/posts/2/edit");
+
+    handle.destroy();
+}
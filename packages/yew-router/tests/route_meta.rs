@@ -0,0 +1,120 @@
+// TODO: remove the cfg after wasm-bindgen-test stops emitting the function unconditionally
+#![cfg(all(target_arch = "wasm32", any(target_os = "unknown", target_os = "none")))]
+
+use std::time::Duration;
+
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+use yew::functional::component;
+use yew::html::ChildrenProps;
+use yew::platform::time::sleep;
+use yew::prelude::*;
+use yew_router::history::{AnyHistory, History, MemoryHistory};
+use yew_router::prelude::*;
+
+mod utils;
+use utils::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Debug, Clone, PartialEq, Routable)]
+enum Routes {
+    #[at("/")]
+    Home,
+    #[at("/settings")]
+    #[route(title = "Settings", layout = AdminLayout, requires_auth, order = 2, beta)]
+    #[meta(section = "admin")]
+    Settings,
+}
+
+#[component(AdminLayout)]
+fn admin_layout(props: &ChildrenProps) -> Html {
+    html! {
+        <div id="admin-layout">{ props.children.clone() }</div>
+    }
+}
+
+#[component(Title)]
+fn title() -> Html {
+    let meta = use_route_meta::<Routes>().unwrap_or_default();
+    html! {
+        <>
+            <h1 id="title">{ meta.title.unwrap_or("Untitled") }</h1>
+            <p id="requires-auth">{ meta.requires_auth }</p>
+        </>
+    }
+}
+
+fn switch(route: Routes) -> Html {
+    match route {
+        Routes::Home => html! { <p id="page">{ "Home" }</p> },
+        Routes::Settings => html! { <p id="page">{ "Settings page" }</p> },
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct RootProps {
+    history: AnyHistory,
+}
+
+#[component(Root)]
+fn root(props: &RootProps) -> Html {
+    html! {
+        <Router history={props.history.clone()}>
+            <Title />
+            <Switch<Routes> render={switch} />
+        </Router>
+    }
+}
+
+#[test]
+fn route_meta() {
+    assert_eq!(Some("Settings"), Routes::Settings.meta().title);
+    assert!(Routes::Settings.meta().requires_auth);
+    assert!(Routes::Settings.meta().layout.is_some());
+    assert_eq!(Some("admin"), Routes::Settings.meta().get("section"));
+    assert_eq!(Some(2), Routes::Settings.meta().get_as::<u32>("order"));
+    assert_eq!(None, Routes::Settings.meta().get_as::<u32>("section"));
+    assert!(Routes::Settings.meta().flag("beta"));
+
+    assert_eq!(None, Routes::Home.meta().title);
+    assert!(!Routes::Home.meta().requires_auth);
+    assert!(Routes::Home.meta().layout.is_none());
+    assert_eq!(None, Routes::Home.meta().get("section"));
+    assert!(!Routes::Home.meta().flag("beta"));
+}
+
+#[test]
+async fn switch_renders_layout() {
+    let history = MemoryHistory::with_entries(["/settings"]);
+
+    let div = gloo::utils::document().create_element("div").unwrap();
+    let _ = div.set_attribute("id", "route-meta");
+    let _ = gloo::utils::body().append_child(&div);
+    let _handle = yew::Renderer::<Root>::with_root_and_props(
+        div,
+        RootProps {
+            history: history.clone().into(),
+        },
+    )
+    .render();
+
+    sleep(Duration::ZERO).await;
+
+    assert_eq!("Settings", obtain_result_by_id("title"));
+    assert_eq!("true", obtain_result_by_id("requires-auth"));
+    assert_eq!(
+        "<p id=\"page\">Settings page</p>",
+        obtain_result_by_id("admin-layout")
+    );
+
+    history.push("/");
+    sleep(Duration::ZERO).await;
+
+    assert_eq!("Untitled", obtain_result_by_id("title"));
+    assert_eq!("Home", obtain_result_by_id("page"));
+    assert!(
+        gloo::utils::document()
+            .get_element_by_id("admin-layout")
+            .is_none()
+    );
+}
@@ -254,3 +254,134 @@ fn router_root_not_affected() {
 
     assert_eq!(Some(AppRoute::Home), AppRoute::recognize("/"));
 }
+
+#[test]
+fn router_trailing_slash_ignore() {
+    #[derive(Routable, Debug, Clone, PartialEq)]
+    #[routable(trailing_slash = "ignore")]
+    enum AppRoute {
+        #[at("/")]
+        Home,
+        #[at("/about")]
+        About,
+        #[at("/category/{name}/")]
+        Category { name: String },
+        #[at("/404")]
+        #[not_found]
+        NotFound,
+    }
+
+    assert_eq!(Some(AppRoute::About), AppRoute::recognize("/about/"));
+    assert_eq!(
+        Some(AppRoute::Category {
+            name: "cooking-recipes".to_string()
+        }),
+        AppRoute::recognize("/category/cooking-recipes")
+    );
+    assert_eq!(Some(AppRoute::Home), AppRoute::recognize("/"));
+
+    // to_path still uses the form from the route definition
+    assert_eq!("/about", AppRoute::About.to_path());
+}
+
+#[test]
+fn router_constrained_params() {
+    #[derive(Routable, Debug, Clone, PartialEq)]
+    enum AppRoute {
+        #[at("/post/{id:u32}")]
+        Post { id: u32 },
+        #[at("/post/{slug}")]
+        PostBySlug { slug: String },
+        #[at("/404")]
+        #[not_found]
+        NotFound,
+    }
+
+    assert_eq!(
+        Some(AppRoute::Post { id: 42 }),
+        AppRoute::recognize("/post/42")
+    );
+    assert_eq!(
+        Some(AppRoute::PostBySlug {
+            slug: "hello-world".to_string()
+        }),
+        AppRoute::recognize("/post/hello-world")
+    );
+    assert_eq!(
+        "/post/hello-world",
+        AppRoute::PostBySlug {
+            slug: "hello-world".to_string()
+        }
+        .to_path()
+    );
+}
+
+#[test]
+fn router_optional_segments() {
+    #[derive(Routable, Debug, Clone, PartialEq)]
+    enum AppRoute {
+        #[at("/posts{/page}")]
+        Posts { page: Option<u32> },
+        #[at("/404")]
+        #[not_found]
+        NotFound,
+    }
+
+    assert_eq!(
+        Some(AppRoute::Posts { page: None }),
+        AppRoute::recognize("/posts")
+    );
+    assert_eq!(
+        Some(AppRoute::Posts { page: Some(2) }),
+        AppRoute::recognize("/posts/2")
+    );
+    assert_eq!(Some(AppRoute::NotFound), AppRoute::recognize("/posts/two"));
+
+    assert_eq!("/posts", AppRoute::Posts { page: None }.to_path());
+    assert_eq!("/posts/2", AppRoute::Posts { page: Some(2) }.to_path());
+}
+
+#[test]
+fn router_manifest_and_sitemap() {
+    use yew_router::sitemap::Sitemap;
+
+    #[derive(Routable, Debug, Clone, PartialEq)]
+    enum AppRoute {
+        #[at("/")]
+        #[meta(priority = 1.0)]
+        Home,
+        #[at("/post/{id}")]
+        #[meta(priority = 0.8, changefreq = "weekly")]
+        Post { id: u32 },
+        #[at("/404")]
+        #[not_found]
+        NotFound,
+    }
+
+    let manifest = AppRoute::manifest();
+    assert_eq!(3, manifest.len());
+    assert_eq!("/post/{id}", manifest[1].path);
+    assert_eq!("id", manifest[1].params[0].name);
+    assert_eq!("u32", manifest[1].params[0].ty);
+    assert_eq!(Some("0.8"), manifest[1].meta("priority"));
+    assert!(manifest[2].not_found);
+    assert_eq!(
+        Some("Post"),
+        AppRoute::Post { id: 1 }
+            .route_info()
+            .map(|info| info.variant)
+    );
+
+    let xml = Sitemap::new("https://example.com/")
+        .static_routes::<AppRoute>()
+        .route(&AppRoute::Post { id: 7 })
+        .render();
+    assert_eq!(
+        xml,
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<urlset \
+         xmlns=\"http://www.sitemaps.org/schemas/sitemap/0.9\">\n  <url>\n    \
+         <loc>https://example.com/</loc>\n    <priority>1.0</priority>\n  </url>\n  <url>\n    \
+         <loc>https://example.com/post/7</loc>\n    <changefreq>weekly</changefreq>\n    \
+         <priority>0.8</priority>\n  </url>\n</urlset>\n"
+    );
+}
@@ -0,0 +1,201 @@
+// TODO: remove the cfg after wasm-bindgen-test stops emitting the function unconditionally
+#![cfg(all(target_arch = "wasm32", any(target_os = "unknown", target_os = "none")))]
+
+use std::cell::Cell;
+use std::time::Duration;
+
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::{wasm_bindgen_test as test, wasm_bindgen_test_configure};
+use yew::functional::component;
+use yew::platform::time::sleep;
+use yew::prelude::*;
+use yew_router::history::{AnyHistory, History, MemoryHistory};
+use yew_router::prelude::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[derive(Debug, Clone, PartialEq, Routable)]
+enum Routes {
+    #[at("/")]
+    Home,
+    #[at("/about")]
+    About,
+}
+
+fn switch(route: Routes) -> Html {
+    match route {
+        Routes::Home => html! { { "Home" } },
+        Routes::About => html! { { "About" } },
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct RootProps {
+    history: AnyHistory,
+}
+
+#[component(Root)]
+fn root(props: &RootProps) -> Html {
+    html! {
+        <Router history={props.history.clone()}>
+            <TransitionSwitch<Routes>
+                render={switch}
+                class="page"
+                timeout={100}
+                view_transition={false}
+            />
+        </Router>
+    }
+}
+
+fn rendered_routes(id: &str) -> Vec<(String, String)> {
+    let nodes = gloo::utils::document()
+        .query_selector_all(&format!("#{id} > div"))
+        .unwrap();
+    (0..nodes.length())
+        .map(|i| {
+            let element = nodes.get(i).unwrap().unchecked_into::<web_sys::Element>();
+            (element.class_name(), element.inner_html())
+        })
+        .collect()
+}
+
+#[test]
+async fn transition_switch_keeps_exiting_route() {
+    let history = MemoryHistory::with_entries(["/"]);
+
+    let div = gloo::utils::document().create_element("div").unwrap();
+    let _ = div.set_attribute("id", "transition-switch");
+    let _ = gloo::utils::body().append_child(&div);
+    let _handle = yew::Renderer::<Root>::with_root_and_props(
+        div,
+        RootProps {
+            history: history.clone().into(),
+        },
+    )
+    .render();
+
+    sleep(Duration::ZERO).await;
+    assert_eq!(
+        vec![("page".to_string(), "Home".to_string())],
+        rendered_routes("transition-switch")
+    );
+
+    history.push("/about");
+    sleep(Duration::from_millis(10)).await;
+    assert_eq!(
+        vec![
+            ("page page-exit".to_string(), "Home".to_string()),
+            ("page page-enter".to_string(), "About".to_string()),
+        ],
+        rendered_routes("transition-switch")
+    );
+
+    // Nothing is animated, so the exiting route is removed after the timeout.
+    sleep(Duration::from_millis(200)).await;
+    assert_eq!(
+        vec![("page page-enter".to_string(), "About".to_string())],
+        rendered_routes("transition-switch")
+    );
+}
+
+thread_local! {
+    static MOUNTS: Cell<u32> = const { Cell::new(0) };
+}
+
+/// Renders the number of clicks on it, and counts how many times it was mounted.
+#[component(Counter)]
+fn counter() -> Html {
+    let clicks = use_state(|| 0);
+    use_effect_with((), |_| MOUNTS.set(MOUNTS.get() + 1));
+    let onclick = {
+        let clicks = clicks.clone();
+        Callback::from(move |_| clicks.set(*clicks + 1))
+    };
+
+    html! { <button id="counter" {onclick}>{ *clicks }</button> }
+}
+
+fn counter_switch(route: Routes) -> Html {
+    match route {
+        Routes::Home => html! { <Counter /> },
+        Routes::About => html! { { "About" } },
+    }
+}
+
+#[component(CounterRoot)]
+fn counter_root(props: &RootProps) -> Html {
+    html! {
+        <Router history={props.history.clone()}>
+            <TransitionSwitch<Routes>
+                render={counter_switch}
+                class="page"
+                timeout={100}
+                view_transition={false}
+            />
+        </Router>
+    }
+}
+
+#[test]
+async fn transition_switch_keeps_state_of_exiting_route() {
+    let history = MemoryHistory::with_entries(["/"]);
+
+    let div = gloo::utils::document().create_element("div").unwrap();
+    let _ = div.set_attribute("id", "transition-switch-state");
+    let _ = gloo::utils::body().append_child(&div);
+    let _handle = yew::Renderer::<CounterRoot>::with_root_and_props(
+        div,
+        RootProps {
+            history: history.clone().into(),
+        },
+    )
+    .render();
+
+    sleep(Duration::ZERO).await;
+    gloo::utils::document()
+        .get_element_by_id("counter")
+        .unwrap()
+        .unchecked_into::<web_sys::HtmlElement>()
+        .click();
+    sleep(Duration::ZERO).await;
+    let counter = || {
+        (
+            MOUNTS.get(),
+            gloo::utils::document()
+                .get_element_by_id("counter")
+                .map(|counter| counter.inner_html()),
+        )
+    };
+    assert_eq!((1, Some("1".to_string())), counter());
+
+    // The exiting route is not remounted.
+    history.push("/about");
+    sleep(Duration::from_millis(10)).await;
+    assert_eq!(
+        vec![
+            (
+                "page page-exit".to_string(),
+                r#"<button id="counter">1</button>"#.to_string()
+            ),
+            ("page page-enter".to_string(), "About".to_string()),
+        ],
+        rendered_routes("transition-switch-state")
+    );
+    assert_eq!((1, Some("1".to_string())), counter());
+
+    // Neither is a route shown again before it is removed.
+    history.push("/");
+    sleep(Duration::from_millis(10)).await;
+    assert_eq!(
+        vec![
+            ("page page-exit".to_string(), "About".to_string()),
+            (
+                "page page-enter".to_string(),
+                r#"<button id="counter">1</button>"#.to_string()
+            ),
+        ],
+        rendered_routes("transition-switch-state")
+    );
+    assert_eq!((1, Some("1".to_string())), counter());
+}
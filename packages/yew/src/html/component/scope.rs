@@ -299,6 +299,7 @@ mod feat_ssr {
     use crate::platform::fmt::BufWriter;
     use crate::platform::pinned::oneshot;
     use crate::scheduler;
+    use crate::ssr_cache::SsrCache;
     use crate::virtual_dom::Collectable;
 
     impl<COMP: BaseComponent> Scope<COMP> {
@@ -340,8 +341,21 @@ mod feat_ssr {
             let html = rx.await.unwrap();
 
             let self_any_scope = AnyScope::from(self.clone());
-            html.render_into_stream(w, &self_any_scope, hydratable, parent_vtag_kind)
-                .await;
+            // The boundary is cloned so the component is not borrowed across the await point.
+            let cache_boundary = (&*self.get_component().unwrap() as &dyn Any)
+                .downcast_ref::<SsrCache>()
+                .and_then(|m| m.cache_boundary().cloned());
+
+            match cache_boundary {
+                Some(m) => {
+                    m.render_into_stream(w, &html, &self_any_scope, hydratable, parent_vtag_kind)
+                        .await
+                }
+                None => {
+                    html.render_into_stream(w, &self_any_scope, hydratable, parent_vtag_kind)
+                        .await
+                }
+            }
 
             if let Some(prepared_state) = self.get_component().unwrap().prepare_state() {
                 let _ = w.write_str(r#"<script type="application/x-yew-comp-state">"#);
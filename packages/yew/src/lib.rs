@@ -281,6 +281,7 @@ pub mod scheduler;
 mod sealed;
 #[cfg(feature = "ssr")]
 mod server_renderer;
+pub mod ssr_cache;
 pub mod suspense;
 pub mod utils;
 pub mod virtual_dom;
@@ -0,0 +1,464 @@
+//! This module provides component-level caching of server-side rendered output.
+//!
+//! Wrap a subtree whose markup only depends on a known key in an [`SsrCache`] boundary and provide
+//! an [`SsrCacheStore`] through a [`ContextProvider`](crate::context::ContextProvider) above it.
+//! On a cache hit, the stored HTML fragment (including hydration markers and prepared state) is
+//! written into the stream and the subtree is not rendered at all. On a cache miss, the subtree is
+//! rendered as usual and the resulting fragment is stored in the backend.
+//!
+//! ```
+//! use yew::prelude::*;
+//! use yew::ssr_cache::{SsrCache, SsrCacheStore};
+//!
+//! #[component]
+//! fn Footer() -> Html {
+//!     html! { <footer>{"© Example Inc."}</footer> }
+//! }
+//!
+//! #[derive(Properties, PartialEq)]
+//! struct AppProps {
+//!     cache: SsrCacheStore,
+//! }
+//!
+//! #[component]
+//! fn App(props: &AppProps) -> Html {
+//!     html! {
+//!         <ContextProvider<SsrCacheStore> context={props.cache.clone()}>
+//!             <main>{"Dynamic content"}</main>
+//!             <SsrCache cache_key="footer">
+//!                 <Footer />
+//!             </SsrCache>
+//!         </ContextProvider<SsrCacheStore>>
+//!     }
+//! }
+//! ```
+//!
+//! On the client side, [`SsrCache`] renders (and hydrates) its children like any other component.
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::html::{BaseComponent, Context, Html, HtmlResult, Properties};
+use crate::virtual_dom::AttrValue;
+
+/// A storage backend for server-side rendered fragments.
+///
+/// Backends are shared between rendering tasks and therefore must be `Send + Sync`.
+///
+/// A fragment rendered with `hydratable` set to `true` contains hydration markers while one
+/// rendered with `hydratable` set to `false` does not. A backend should therefore only be shared
+/// between renderers that use the same setting.
+pub trait SsrCacheBackend: Send + Sync + 'static {
+    /// Returns the fragment stored under `key`, if it exists and has not expired.
+    fn get(&self, key: &str) -> Option<String>;
+
+    /// Stores a fragment under `key`.
+    ///
+    /// If `ttl` is `Some`, the fragment should not be returned by [`get`](Self::get) once the
+    /// duration has elapsed.
+    fn set(&self, key: &str, fragment: String, ttl: Option<Duration>);
+}
+
+/// A shared handle to an [`SsrCacheBackend`].
+///
+/// This type is intended to be provided through a
+/// [`ContextProvider`](crate::context::ContextProvider) so that [`SsrCache`] boundaries can access
+/// it. Two stores are equal if they point to the same backend.
+#[derive(Clone)]
+pub struct SsrCacheStore(Arc<dyn SsrCacheBackend>);
+
+impl SsrCacheStore {
+    /// Creates a store from a backend.
+    pub fn new<B>(backend: B) -> Self
+    where
+        B: SsrCacheBackend,
+    {
+        Self(Arc::new(backend))
+    }
+}
+
+impl fmt::Debug for SsrCacheStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SsrCacheStore").finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for SsrCacheStore {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl From<Arc<dyn SsrCacheBackend>> for SsrCacheStore {
+    fn from(backend: Arc<dyn SsrCacheBackend>) -> Self {
+        Self(backend)
+    }
+}
+
+#[cfg(feature = "ssr")]
+mod feat_ssr {
+    use std::collections::HashMap;
+    use std::fmt::Write;
+    use std::sync::Mutex;
+    use std::time::Instant;
+
+    use futures::stream::StreamExt;
+
+    use super::*;
+    use crate::feat_ssr::VTagKind;
+    use crate::html::AnyScope;
+    use crate::platform::fmt::{self, BufWriter};
+
+    /// An in-memory [`SsrCacheBackend`].
+    ///
+    /// Expired entries are removed when they are looked up, and whenever a fragment is stored.
+    /// A cache created with [`with_capacity`](Self::with_capacity) also evicts the least recently
+    /// used entry once it is full.
+    #[derive(Debug, Default)]
+    pub struct MemorySsrCache {
+        capacity: Option<usize>,
+        inner: Mutex<MemorySsrCacheInner>,
+    }
+
+    #[derive(Debug, Default)]
+    struct MemorySsrCacheInner {
+        entries: HashMap<String, MemorySsrCacheEntry>,
+        /// Incremented on every access to order the entries by their last use.
+        clock: u64,
+    }
+
+    #[derive(Debug)]
+    struct MemorySsrCacheEntry {
+        fragment: String,
+        expires_at: Option<Instant>,
+        last_used: u64,
+    }
+
+    impl MemorySsrCacheInner {
+        fn tick(&mut self) -> u64 {
+            self.clock += 1;
+            self.clock
+        }
+    }
+
+    impl MemorySsrCache {
+        /// Creates an empty cache.
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Creates an empty cache that holds at most `capacity` fragments.
+        pub fn with_capacity(capacity: usize) -> Self {
+            Self {
+                capacity: Some(capacity.max(1)),
+                inner: Mutex::default(),
+            }
+        }
+
+        /// Returns the number of fragments in the cache, including expired ones that have not been
+        /// removed yet.
+        pub fn len(&self) -> usize {
+            self.inner.lock().unwrap().entries.len()
+        }
+
+        /// Returns `true` if the cache holds no fragments.
+        pub fn is_empty(&self) -> bool {
+            self.len() == 0
+        }
+
+        /// Removes all entries from the cache.
+        pub fn clear(&self) {
+            self.inner.lock().unwrap().entries.clear();
+        }
+    }
+
+    impl SsrCacheBackend for MemorySsrCache {
+        fn get(&self, key: &str) -> Option<String> {
+            let mut inner = self.inner.lock().unwrap();
+            let now = inner.tick();
+            let entry = inner.entries.get_mut(key)?;
+
+            if entry.expires_at.is_some_and(|m| m <= Instant::now()) {
+                inner.entries.remove(key);
+                return None;
+            }
+
+            entry.last_used = now;
+            Some(entry.fragment.clone())
+        }
+
+        fn set(&self, key: &str, fragment: String, ttl: Option<Duration>) {
+            let mut inner = self.inner.lock().unwrap();
+            let now = Instant::now();
+            inner
+                .entries
+                .retain(|_, m| m.expires_at.is_none_or(|expires_at| expires_at > now));
+
+            if let Some(capacity) = self.capacity {
+                while inner.entries.len() >= capacity && !inner.entries.contains_key(key) {
+                    let lru = inner
+                        .entries
+                        .iter()
+                        .min_by_key(|(_, m)| m.last_used)
+                        .map(|(key, _)| key.clone());
+                    match lru {
+                        Some(lru) => inner.entries.remove(&lru),
+                        None => break,
+                    };
+                }
+            }
+
+            let last_used = inner.tick();
+            inner.entries.insert(
+                key.to_owned(),
+                MemorySsrCacheEntry {
+                    fragment,
+                    expires_at: ttl.map(|m| now + m),
+                    last_used,
+                },
+            );
+        }
+    }
+
+    /// The store and key the output of an [`SsrCache`] is cached under.
+    #[derive(Debug, Clone)]
+    pub(crate) struct SsrCacheBoundary {
+        pub(super) store: SsrCacheStore,
+        pub(super) key: AttrValue,
+        pub(super) ttl: Option<Duration>,
+    }
+
+    impl SsrCacheBoundary {
+        pub(crate) async fn render_into_stream(
+            &self,
+            w: &mut BufWriter,
+            html: &Html,
+            parent_scope: &AnyScope,
+            hydratable: bool,
+            parent_vtag_kind: VTagKind,
+        ) {
+            if let Some(fragment) = self.store.0.get(&self.key) {
+                let _ = w.write_str(&fragment);
+                return;
+            }
+
+            let (mut next_w, next_r) = fmt::buffer();
+            // Move buf writer into an async block for it to be dropped at the end of the future.
+            let render_fur = async move {
+                html.render_into_stream(&mut next_w, parent_scope, hydratable, parent_vtag_kind)
+                    .await;
+            };
+            let ((), fragment) = futures::join!(render_fur, next_r.collect::<String>());
+
+            let _ = w.write_str(&fragment);
+            self.store.0.set(&self.key, fragment, self.ttl);
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+pub use feat_ssr::MemorySsrCache;
+#[cfg(feature = "ssr")]
+use feat_ssr::SsrCacheBoundary;
+
+/// Properties for [SsrCache].
+#[derive(Properties, PartialEq, Debug, Clone)]
+pub struct SsrCacheProps {
+    /// The key the rendered fragment is stored under.
+    ///
+    /// Subtrees that render different markup must use different keys. This is separate from the
+    /// `key` attribute, which is used to identify nodes in a list.
+    pub cache_key: AttrValue,
+
+    /// How long the rendered fragment stays valid. Defaults to no expiry.
+    #[prop_or_default]
+    pub ttl: Option<Duration>,
+
+    /// The Children of the current SsrCache Component.
+    #[prop_or_default]
+    pub children: Html,
+}
+
+/// Caches the server-side rendered output of its children.
+///
+/// During server-side rendering, this component looks up an [`SsrCacheStore`] provided by an
+/// ancestor [`ContextProvider`](crate::context::ContextProvider). If no store is provided, the
+/// children are always rendered.
+///
+/// When the fragment for the current [`cache_key`](SsrCacheProps::cache_key) is missing, the
+/// children are rendered into a buffer before being written into the stream, so a cached subtree
+/// is never streamed incrementally.
+///
+/// On the client side, this component renders its children.
+#[derive(Debug)]
+pub struct SsrCache {
+    #[cfg(feature = "ssr")]
+    boundary: Option<SsrCacheBoundary>,
+}
+
+impl SsrCache {
+    /// Returns the boundary the output of this component is cached under, if a store is provided.
+    #[cfg(feature = "ssr")]
+    pub(crate) fn cache_boundary(&self) -> Option<&SsrCacheBoundary> {
+        self.boundary.as_ref()
+    }
+
+    #[cfg(feature = "ssr")]
+    fn boundary(ctx: &Context<Self>) -> Option<SsrCacheBoundary> {
+        let props = ctx.props();
+
+        ctx.link()
+            .context::<SsrCacheStore>(crate::Callback::noop())
+            .map(|(store, _)| SsrCacheBoundary {
+                store,
+                key: props.cache_key.clone(),
+                ttl: props.ttl,
+            })
+    }
+}
+
+impl BaseComponent for SsrCache {
+    type Message = ();
+    type Properties = SsrCacheProps;
+
+    #[allow(unused_variables)]
+    fn create(ctx: &Context<Self>) -> Self {
+        Self {
+            #[cfg(feature = "ssr")]
+            boundary: Self::boundary(ctx),
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, _msg: Self::Message) -> bool {
+        false
+    }
+
+    #[allow(unused_variables)]
+    fn changed(&mut self, ctx: &Context<Self>, _old_props: &Self::Properties) -> bool {
+        #[cfg(feature = "ssr")]
+        {
+            self.boundary = Self::boundary(ctx);
+        }
+
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> HtmlResult {
+        Ok(ctx.props().children.clone())
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {}
+
+    fn destroy(&mut self, _ctx: &Context<Self>) {}
+
+    fn prepare_state(&self) -> Option<String> {
+        None
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32"), feature = "ssr"))]
+mod ssr_tests {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use tokio::test;
+
+    use super::*;
+    use crate::ServerRenderer;
+    use crate::prelude::*;
+
+    static RENDER_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    #[component]
+    fn Expensive() -> Html {
+        RENDER_COUNT.fetch_add(1, Ordering::SeqCst);
+
+        html! { <div>{"expensive"}</div> }
+    }
+
+    #[derive(PartialEq, Properties, Debug)]
+    struct AppProps {
+        cache: SsrCacheStore,
+        name: AttrValue,
+    }
+
+    #[component]
+    fn App(props: &AppProps) -> Html {
+        html! {
+            <ContextProvider<SsrCacheStore> context={props.cache.clone()}>
+                <span>{props.name.clone()}</span>
+                <SsrCache cache_key="expensive">
+                    <Expensive />
+                </SsrCache>
+            </ContextProvider<SsrCacheStore>>
+        }
+    }
+
+    #[test]
+    async fn test_cache_hit_skips_rendering() {
+        let backend = Arc::new(MemorySsrCache::new());
+        let cache = SsrCacheStore::from(backend.clone() as Arc<dyn SsrCacheBackend>);
+
+        let render = |name: &'static str| {
+            let cache = cache.clone();
+            ServerRenderer::<App>::with_props(move || AppProps {
+                cache,
+                name: name.into(),
+            })
+            .hydratable(false)
+            .render()
+        };
+
+        let first = render("Jane").await;
+        assert_eq!(first, "<span>Jane</span><div>expensive</div>");
+        assert_eq!(RENDER_COUNT.load(Ordering::SeqCst), 1);
+
+        let second = render("John").await;
+        assert_eq!(second, "<span>John</span><div>expensive</div>");
+        assert_eq!(RENDER_COUNT.load(Ordering::SeqCst), 1);
+
+        backend.clear();
+
+        let third = render("Josh").await;
+        assert_eq!(third, "<span>Josh</span><div>expensive</div>");
+        assert_eq!(RENDER_COUNT.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    async fn test_memory_cache_expiry() {
+        let backend = MemorySsrCache::new();
+        backend.set("key", "stale".to_owned(), Some(Duration::ZERO));
+
+        assert_eq!(backend.get("key"), None);
+    }
+
+    #[test]
+    async fn test_memory_cache_sweeps_expired_entries_on_insert() {
+        let backend = MemorySsrCache::new();
+        backend.set("stale", "stale".to_owned(), Some(Duration::ZERO));
+        backend.set("fresh", "fresh".to_owned(), None);
+
+        assert_eq!(backend.len(), 1);
+        assert_eq!(backend.get("fresh").as_deref(), Some("fresh"));
+    }
+
+    #[test]
+    async fn test_memory_cache_evicts_least_recently_used() {
+        let backend = MemorySsrCache::with_capacity(2);
+        backend.set("a", "a".to_owned(), None);
+        backend.set("b", "b".to_owned(), None);
+        // Makes `b` the least recently used entry.
+        backend.get("a");
+        backend.set("c", "c".to_owned(), None);
+
+        assert_eq!(backend.len(), 2);
+        assert_eq!(backend.get("a").as_deref(), Some("a"));
+        assert_eq!(backend.get("b"), None);
+        assert_eq!(backend.get("c").as_deref(), Some("c"));
+
+        // Replacing an entry does not evict another one.
+        backend.set("c", "c2".to_owned(), None);
+        assert_eq!(backend.get("a").as_deref(), Some("a"));
+    }
+}